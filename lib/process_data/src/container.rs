@@ -0,0 +1,370 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Containerization;
+
+/// A container (as opposed to a sandboxed app) a process is running in
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Container {
+    pub runtime: Containerization,
+    /// The full ID for Docker and Podman containers, the name for the other runtimes
+    pub id: String,
+    /// The name of the container if it's known and differs from `id`
+    pub name: Option<String>,
+}
+
+impl Container {
+    /// Detects the container a process is running in using its cgroup v2 path and, where the cgroup alone is
+    /// ambiguous, the marker files in its root directory
+    pub fn detect<S: AsRef<str>, P: AsRef<Path>>(cgroup_path: S, proc_path: P) -> Option<Self> {
+        let mut container = Self::from_cgroup_path(cgroup_path)?;
+
+        if !matches!(
+            container.runtime,
+            Containerization::Docker | Containerization::Podman
+        ) {
+            return Some(container);
+        }
+
+        // Toolbox and Distrobox containers are regular Podman (or Docker, in case of Distrobox) containers, they can
+        // only be told apart by the files their entry points leave in the container. We might not be allowed to look
+        // into the root directory of a container, in that case we have to live with the more generic runtime.
+        let root = proc_path.as_ref().join("root");
+
+        if root.join("run/.toolboxenv").exists() {
+            container.runtime = Containerization::Toolbox;
+        } else if root.join("run/.containersetupdone").exists() {
+            container.runtime = Containerization::Distrobox;
+        }
+
+        container.name = std::fs::read_to_string(root.join("run/.containerenv"))
+            .ok()
+            .and_then(parse_containerenv_name)
+            .filter(|name| *name != container.id);
+
+        Some(container)
+    }
+
+    /// Detects the container a process is running in only using its cgroup v2 path (without the `0::` prefix), the
+    /// supported layouts are those of the systemd and cgroupfs drivers of Docker and Podman, LXC 4.0+ (and older
+    /// ones using `/lxc/<NAME>`) and systemd-nspawn
+    pub fn from_cgroup_path<S: AsRef<str>>(cgroup_path: S) -> Option<Self> {
+        Self::parse_cgroup_path(cgroup_path).map(|(container, _)| container)
+    }
+
+    /// Returns the path of the cgroup of the container a process with the cgroup v2 path `cgroup_path` is running in,
+    /// relative to the cgroup root (like `system.slice/docker-<ID>.scope`). Processes of a container can be in
+    /// sub-cgroups of it, this is the cgroup that contains all of them.
+    pub fn cgroup_of<S: AsRef<str>>(cgroup_path: S) -> Option<String> {
+        let (_, depth) = Self::parse_cgroup_path(&cgroup_path)?;
+
+        Some(
+            cgroup_path
+                .as_ref()
+                .trim()
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .take(depth)
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    }
+
+    /// Returns the container along with the number of segments of `cgroup_path` that make up its cgroup
+    fn parse_cgroup_path<S: AsRef<str>>(cgroup_path: S) -> Option<(Self, usize)> {
+        let segments: Vec<&str> = cgroup_path
+            .as_ref()
+            .trim()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .collect();
+
+        for (i, segment) in segments.iter().enumerate() {
+            let previous = i.checked_sub(1).and_then(|i| segments.get(i)).copied();
+            let next = segments.get(i + 1).copied();
+
+            // for the cgroupfs layouts, the container's cgroup is the one below the current segment
+            let (container, depth) = if let Some(id) = unit_name(segment, "docker-", ".scope") {
+                (Self::with_id(Containerization::Docker, id), i + 1)
+            } else if let Some(id) = unit_name(segment, "libpod-", ".scope") {
+                // this doesn't match `libpod-conmon-<ID>.scope`, which contains Podman's container monitor rather than
+                // the container itself
+                (Self::with_id(Containerization::Podman, id), i + 1)
+            } else if *segment == "docker" {
+                (
+                    next.and_then(|id| Self::with_id(Containerization::Docker, id)),
+                    i + 2,
+                )
+            } else if *segment == "libpod_parent" {
+                (
+                    next.and_then(|next| next.strip_prefix("libpod-"))
+                        .and_then(|id| Self::with_id(Containerization::Podman, id)),
+                    i + 2,
+                )
+            } else if let Some(name) = segment.strip_prefix("lxc.payload.") {
+                (Self::with_name(Containerization::Lxc, name), i + 1)
+            } else if *segment == "lxc" || *segment == "lxc.payload" {
+                (
+                    next.and_then(|name| Self::with_name(Containerization::Lxc, name)),
+                    i + 2,
+                )
+            } else if let Some(name) = unit_name(segment, "systemd-nspawn@", ".service") {
+                (Self::with_name(Containerization::Nspawn, name), i + 1)
+            } else if previous == Some("machine.slice") {
+                // libvirt registers its virtual machines with systemd-machined as well
+                (
+                    unit_name(segment, "machine-", ".scope")
+                        .and_then(|name| Self::with_name(Containerization::Nspawn, name))
+                        .filter(|container| !container.id.starts_with("qemu-")),
+                    i + 1,
+                )
+            } else {
+                (None, 0)
+            };
+
+            if let Some(container) = container {
+                return Some((container, depth));
+            }
+        }
+
+        None
+    }
+
+    /// Returns the name of the container or, if it's unknown, its ID shortened like `docker ps` does
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or_else(|| {
+            if matches!(
+                self.runtime,
+                Containerization::Docker
+                    | Containerization::Podman
+                    | Containerization::Toolbox
+                    | Containerization::Distrobox
+            ) {
+                self.id.get(..12).unwrap_or(&self.id)
+            } else {
+                &self.id
+            }
+        })
+    }
+
+    fn with_id(runtime: Containerization, id: &str) -> Option<Self> {
+        is_container_id(id).then(|| Self {
+            runtime,
+            id: id.to_string(),
+            name: None,
+        })
+    }
+
+    fn with_name<S: AsRef<str>>(runtime: Containerization, name: S) -> Option<Self> {
+        let name = name.as_ref();
+        let name = unescape::unescape(name).unwrap_or_else(|| name.to_string());
+
+        (!name.is_empty()).then_some(Self {
+            runtime,
+            id: name,
+            name: None,
+        })
+    }
+}
+
+fn unit_name<'a>(segment: &'a str, prefix: &str, suffix: &str) -> Option<&'a str> {
+    segment.strip_prefix(prefix)?.strip_suffix(suffix)
+}
+
+/// Docker and Podman use 64 hex digits as container IDs
+fn is_container_id(id: &str) -> bool {
+    id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Parses the `name` out of `/run/.containerenv`, which Podman places in its containers and consists of lines like
+/// `name="fedora-toolbox-41"`
+fn parse_containerenv_name<S: AsRef<str>>(containerenv: S) -> Option<String> {
+    containerenv.as_ref().lines().find_map(|line| {
+        line.strip_prefix("name=")
+            .map(|name| name.trim().trim_matches('"').to_string())
+            .filter(|name| !name.is_empty())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::Containerization;
+
+    use super::{parse_containerenv_name, Container};
+
+    const ID: &str = "4f1c0b3b8e5d2a6f9c7e0d1b2a3c4d5e6f708192a3b4c5d6e7f8091a2b3c4d5e";
+
+    fn container(runtime: Containerization, id: &str) -> Option<Container> {
+        Some(Container {
+            runtime,
+            id: id.to_string(),
+            name: None,
+        })
+    }
+
+    #[test]
+    fn docker_cgroups() {
+        assert_eq!(
+            Container::from_cgroup_path(format!("/system.slice/docker-{ID}.scope")),
+            container(Containerization::Docker, ID)
+        );
+
+        assert_eq!(
+            Container::from_cgroup_path(format!("/docker/{ID}")),
+            container(Containerization::Docker, ID)
+        );
+
+        assert_eq!(
+            Container::from_cgroup_path("/system.slice/docker.service"),
+            None
+        );
+    }
+
+    #[test]
+    fn podman_cgroups() {
+        assert_eq!(
+            Container::from_cgroup_path(format!(
+                "/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-{ID}.scope/container"
+            )),
+            container(Containerization::Podman, ID)
+        );
+
+        assert_eq!(
+            Container::from_cgroup_path(format!("/machine.slice/libpod-{ID}.scope")),
+            container(Containerization::Podman, ID)
+        );
+
+        assert_eq!(
+            Container::from_cgroup_path(format!("/libpod_parent/libpod-{ID}")),
+            container(Containerization::Podman, ID)
+        );
+
+        assert_eq!(
+            Container::from_cgroup_path(format!(
+                "/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-conmon-{ID}.scope"
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn lxc_cgroups() {
+        assert_eq!(
+            Container::from_cgroup_path("/lxc.payload.webserver/system.slice/nginx.service"),
+            container(Containerization::Lxc, "webserver")
+        );
+
+        assert_eq!(
+            Container::from_cgroup_path("/lxc/webserver"),
+            container(Containerization::Lxc, "webserver")
+        );
+
+        assert_eq!(Container::from_cgroup_path("/lxc.monitor.webserver"), None);
+    }
+
+    #[test]
+    fn nspawn_cgroups() {
+        assert_eq!(
+            Container::from_cgroup_path("/machine.slice/systemd-nspawn@debian.service/payload"),
+            container(Containerization::Nspawn, "debian")
+        );
+
+        assert_eq!(
+            Container::from_cgroup_path(
+                "/machine.slice/machine-arch\\x2dbuild.scope/payload/system.slice/dbus.service"
+            ),
+            container(Containerization::Nspawn, "arch-build")
+        );
+
+        assert_eq!(
+            Container::from_cgroup_path(
+                "/machine.slice/machine-qemu\\x2d1\\x2dwin11.scope/libvirt"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn no_container_cgroups() {
+        assert_eq!(
+            Container::from_cgroup_path(
+                "/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-firefox-4242.scope"
+            ),
+            None
+        );
+
+        assert_eq!(Container::from_cgroup_path("/"), None);
+        assert_eq!(Container::from_cgroup_path(""), None);
+    }
+
+    #[test]
+    fn container_cgroups() {
+        assert_eq!(
+            Container::cgroup_of(format!(
+                "/user.slice/user-1000.slice/user@1000.service/user.slice/libpod-{ID}.scope/container"
+            )),
+            Some(format!(
+                "user.slice/user-1000.slice/user@1000.service/user.slice/libpod-{ID}.scope"
+            ))
+        );
+
+        assert_eq!(
+            Container::cgroup_of(format!("/docker/{ID}")),
+            Some(format!("docker/{ID}"))
+        );
+
+        assert_eq!(
+            Container::cgroup_of("/lxc.payload.webserver/system.slice/nginx.service"),
+            Some(String::from("lxc.payload.webserver"))
+        );
+
+        assert_eq!(
+            Container::cgroup_of("/machine.slice/systemd-nspawn@debian.service/payload"),
+            Some(String::from("machine.slice/systemd-nspawn@debian.service"))
+        );
+
+        assert_eq!(Container::cgroup_of("/system.slice/docker.service"), None);
+    }
+
+    #[test]
+    fn display_names() {
+        let mut docker = Container {
+            runtime: Containerization::Docker,
+            id: ID.to_string(),
+            name: None,
+        };
+
+        assert_eq!(docker.display_name(), "4f1c0b3b8e5d");
+
+        docker.name = Some(String::from("postgres"));
+        assert_eq!(docker.display_name(), "postgres");
+
+        let lxc = Container {
+            runtime: Containerization::Lxc,
+            id: String::from("webserver"),
+            name: None,
+        };
+
+        assert_eq!(lxc.display_name(), "webserver");
+    }
+
+    #[test]
+    fn containerenv_parsing() {
+        let containerenv = "engine=\"podman-5.2.3\"\n\
+                            name=\"fedora-toolbox-41\"\n\
+                            id=\"4f1c0b3b8e5d\"\n\
+                            image=\"registry.fedoraproject.org/fedora-toolbox:41\"\n\
+                            rootless=1\n";
+
+        assert_eq!(
+            parse_containerenv_name(containerenv),
+            Some(String::from("fedora-toolbox-41"))
+        );
+
+        assert_eq!(parse_containerenv_name(""), None);
+        assert_eq!(parse_containerenv_name("name=\"\"\n"), None);
+    }
+}
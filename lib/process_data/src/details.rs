@@ -0,0 +1,275 @@
+use std::{
+    os::linux::fs::MetadataExt,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    limits::{parse_limits, ResourceLimit},
+    memory_maps::{parse_smaps, MemoryMapping},
+    sockets::{self, socket_inode, Protocol, Socket, INET_PROTOCOLS},
+    unix_as_millis,
+    wait_state::WaitState,
+    ProcessData, ThreadData,
+};
+
+/// Information about a process that is too expensive to gather for every process on every refresh and is therefore
+/// only gathered for the process that is currently being inspected by the user.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessDetails {
+    pub pid: libc::pid_t,
+    pub threads: Vec<ThreadData>,
+    /// Empty if we're not allowed to read `/proc/<pid>/limits`
+    pub limits: Vec<ResourceLimit>,
+    pub open_files: Vec<OpenFile>,
+    /// The sockets among the open files, sorted by file descriptor
+    pub sockets: Vec<Socket>,
+    /// Empty if we're not allowed to read `/proc/<pid>/smaps`
+    pub memory_maps: Vec<MemoryMapping>,
+    /// Current working directory, `None` if we're not allowed to read it
+    pub cwd: Option<String>,
+    /// Root directory (e.g. changed by chroot(2)), `None` if we're not allowed to read it
+    pub root: Option<String>,
+    /// Environment variables in the order they appear in `/proc/<pid>/environ`, `None` if we're not allowed to read
+    /// them
+    pub environment: Option<Vec<(String, String)>>,
+    pub wait_state: WaitState,
+    pub timestamp: u64,
+}
+
+impl ProcessDetails {
+    pub fn try_from_pid(pid: libc::pid_t) -> Result<Self> {
+        let proc_path = PathBuf::from("/proc").join(pid.to_string());
+
+        // make sure that the process exists at all, everything else is best-effort
+        std::fs::metadata(&proc_path).context("process does not exist")?;
+
+        let threads = ProcessData::threads(&proc_path);
+
+        let limits = std::fs::read_to_string(proc_path.join("limits"))
+            .map(parse_limits)
+            .unwrap_or_default();
+
+        let open_files = open_files(&proc_path);
+
+        let sockets = process_sockets(&proc_path, &open_files);
+
+        let memory_maps = std::fs::read_to_string(proc_path.join("smaps"))
+            .map(parse_smaps)
+            .unwrap_or_default();
+
+        let read_link = |name: &str| {
+            std::fs::read_link(proc_path.join(name))
+                .ok()
+                .map(|target| target.to_string_lossy().into_owned())
+        };
+
+        let cwd = read_link("cwd");
+        let root = read_link("root");
+
+        let environment = std::fs::read(proc_path.join("environ"))
+            .ok()
+            .map(|environ| parse_environ(&environ));
+
+        let wait_state = WaitState::read(&proc_path);
+
+        let timestamp = unix_as_millis();
+
+        Ok(Self {
+            pid,
+            threads,
+            limits,
+            open_files,
+            sockets,
+            memory_maps,
+            cwd,
+            root,
+            environment,
+            wait_state,
+            timestamp,
+        })
+    }
+}
+
+/// What kind of file a file descriptor refers to
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileKind {
+    File,
+    Directory,
+    CharDevice,
+    BlockDevice,
+    Socket,
+    Pipe,
+    AnonInode,
+    #[default]
+    Other,
+}
+
+/// A file descriptor of a process, see man proc_pid_fd(5) and proc_pid_fdinfo(5)
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpenFile {
+    pub fd: i32,
+    /// The target of the `/proc/<pid>/fd/<fd>` symlink, e.g. a path or `socket:[12345]`
+    pub target: String,
+    pub kind: FileKind,
+    /// The flags the file has been opened with, `None` if fdinfo couldn't be read
+    pub flags: Option<u32>,
+    /// The file offset, `None` if fdinfo couldn't be read
+    pub pos: Option<u64>,
+}
+
+/// Returns all file descriptors of the process at `proc_path` that we are allowed to see
+pub fn open_files<P: AsRef<Path>>(proc_path: P) -> Vec<OpenFile> {
+    let proc_path = proc_path.as_ref();
+
+    let Ok(entries) = std::fs::read_dir(proc_path.join("fd")) else {
+        return Vec::new();
+    };
+
+    let mut open_files: Vec<OpenFile> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let fd: i32 = entry.file_name().to_str()?.parse().ok()?;
+
+            // the fd might have been closed in the meantime
+            let target = std::fs::read_link(entry.path())
+                .ok()?
+                .to_string_lossy()
+                .into_owned();
+
+            let kind = file_kind(&target, &entry.path());
+
+            let (flags, pos) =
+                std::fs::read_to_string(proc_path.join("fdinfo").join(fd.to_string()))
+                    .map(|fdinfo| parse_fdinfo(&fdinfo))
+                    .unwrap_or_default();
+
+            Some(OpenFile {
+                fd,
+                target,
+                kind,
+                flags,
+                pos,
+            })
+        })
+        .collect();
+
+    open_files.sort_by_key(|open_file| open_file.fd);
+
+    open_files
+}
+
+/// Looks up the sockets among `open_files` in the socket tables of the network namespace of the process at `proc_path`
+pub fn process_sockets<P: AsRef<Path>>(proc_path: P, open_files: &[OpenFile]) -> Vec<Socket> {
+    let inodes: Vec<u64> = open_files
+        .iter()
+        .filter(|open_file| open_file.kind == FileKind::Socket)
+        .filter_map(|open_file| socket_inode(&open_file.target))
+        .collect();
+
+    if inodes.is_empty() {
+        return Vec::new();
+    }
+
+    let protocols: Vec<Protocol> = INET_PROTOCOLS
+        .into_iter()
+        .chain(std::iter::once(Protocol::Unix))
+        .collect();
+
+    let mut all_sockets = sockets::sockets(proc_path.as_ref().join("net"), &protocols);
+
+    inodes
+        .into_iter()
+        .filter_map(|inode| all_sockets.remove(&inode))
+        .collect()
+}
+
+fn file_kind(target: &str, fd_path: &Path) -> FileKind {
+    if target.starts_with("socket:") {
+        return FileKind::Socket;
+    } else if target.starts_with("pipe:") {
+        return FileKind::Pipe;
+    } else if target.starts_with("anon_inode:") {
+        return FileKind::AnonInode;
+    }
+
+    // follows the symlink, so this is the metadata of the file the fd refers to
+    let Ok(metadata) = std::fs::metadata(fd_path) else {
+        return FileKind::Other;
+    };
+
+    match metadata.st_mode() & libc::S_IFMT {
+        libc::S_IFREG => FileKind::File,
+        libc::S_IFDIR => FileKind::Directory,
+        libc::S_IFCHR => FileKind::CharDevice,
+        libc::S_IFBLK => FileKind::BlockDevice,
+        libc::S_IFSOCK => FileKind::Socket,
+        libc::S_IFIFO => FileKind::Pipe,
+        _ => FileKind::Other,
+    }
+}
+
+/// Parses the NUL-separated `KEY=VALUE` pairs of `/proc/<pid>/environ`
+fn parse_environ(environ: &[u8]) -> Vec<(String, String)> {
+    environ
+        .split(|byte| *byte == 0)
+        .filter(|variable| !variable.is_empty())
+        .map(|variable| {
+            let variable = String::from_utf8_lossy(variable);
+            match variable.split_once('=') {
+                Some((key, value)) => (key.to_string(), value.to_string()),
+                None => (variable.into_owned(), String::new()),
+            }
+        })
+        .collect()
+}
+
+/// Parses the `flags` (octal) and `pos` fields of a `/proc/<pid>/fdinfo/<fd>` file
+fn parse_fdinfo(fdinfo: &str) -> (Option<u32>, Option<u64>) {
+    let mut flags = None;
+    let mut pos = None;
+
+    for line in fdinfo.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            match key.trim() {
+                "flags" => flags = u32::from_str_radix(value.trim(), 8).ok(),
+                "pos" => pos = value.trim().parse().ok(),
+                _ => {}
+            }
+        }
+    }
+
+    (flags, pos)
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{parse_environ, parse_fdinfo};
+
+    #[test]
+    fn fdinfo_parsing() {
+        let fdinfo = "pos:\t4096\nflags:\t02100002\nmnt_id:\t25\nino:\t1234\n";
+
+        assert_eq!(parse_fdinfo(fdinfo), (Some(0o2100002), Some(4096)));
+        assert_eq!(parse_fdinfo("mnt_id:\t25\n"), (None, None));
+    }
+
+    #[test]
+    fn environ_parsing() {
+        let environ = b"HOME=/home/user\0EMPTY=\0EQUALS=a=b\0NO_VALUE\0";
+
+        assert_eq!(
+            parse_environ(environ),
+            vec![
+                (String::from("HOME"), String::from("/home/user")),
+                (String::from("EMPTY"), String::new()),
+                (String::from("EQUALS"), String::from("a=b")),
+                (String::from("NO_VALUE"), String::new()),
+            ]
+        );
+    }
+}
@@ -1,6 +1,14 @@
+pub mod container;
+pub mod details;
+pub mod limits;
+pub mod memory_maps;
 pub mod pci_slot;
+pub mod pidfd;
+pub mod sockets;
+pub mod wait_state;
 
 use anyhow::{bail, Context, Result};
+use container::Container;
 use glob::glob;
 use lazy_regex::{lazy_regex, Lazy, Regex};
 use nutype::nutype;
@@ -10,21 +18,35 @@ use nvml_wrapper::struct_wrappers::device::{ProcessInfo, ProcessUtilizationSampl
 use nvml_wrapper::{Device, Nvml};
 use pci_slot::PciSlot;
 use serde::{Deserialize, Serialize};
+use sockets::{socket_inode, ListeningPort, INET_PROTOCOLS};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
 use std::io::{Read, Write};
 use std::os::linux::fs::MetadataExt;
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{LazyLock, RwLock};
 use std::{path::PathBuf, time::SystemTime};
 
 const STAT_OFFSET: usize = 2; // we split the stat contents where the executable name ends, which is the second element
+const STAT_STATE: usize = 2 - STAT_OFFSET;
 const STAT_PARENT_PID: usize = 3 - STAT_OFFSET;
+const STAT_MINOR_FAULTS: usize = 9 - STAT_OFFSET;
+const STAT_MAJOR_FAULTS: usize = 11 - STAT_OFFSET;
 const STAT_USER_CPU_TIME: usize = 13 - STAT_OFFSET;
 const STAT_SYSTEM_CPU_TIME: usize = 14 - STAT_OFFSET;
 const STAT_NICE: usize = 18 - STAT_OFFSET;
+const STAT_NUM_THREADS: usize = 19 - STAT_OFFSET;
 const STAT_STARTTIME: usize = 21 - STAT_OFFSET;
+const STAT_PROCESSOR: usize = 38 - STAT_OFFSET;
+const STAT_RT_PRIORITY: usize = 39 - STAT_OFFSET;
+const STAT_POLICY: usize = 40 - STAT_OFFSET;
+const STAT_DELAYACCT_BLKIO_TICKS: usize = 41 - STAT_OFFSET;
+
+const IOPRIO_CLASS_SHIFT: i32 = 13;
+const IOPRIO_PRIO_MASK: i32 = (1 << IOPRIO_CLASS_SHIFT) - 1;
+const IOPRIO_WHO_PROCESS: i32 = 1;
 
 static USERS_CACHE: LazyLock<HashMap<libc::uid_t, String>> = LazyLock::new(|| unsafe {
     uzers::all_users()
@@ -42,10 +64,26 @@ static RE_AFFINITY: Lazy<Regex> = lazy_regex!(r"Cpus_allowed:\s*([0-9A-Fa-f]+)")
 
 static RE_SWAP_USAGGE: Lazy<Regex> = lazy_regex!(r"VmSwap:\s*([0-9]+)\s*kB");
 
+static RE_VOLUNTARY_CTXT_SWITCHES: Lazy<Regex> =
+    lazy_regex!(r"(?m)^voluntary_ctxt_switches:\s*(\d+)");
+
+static RE_NONVOLUNTARY_CTXT_SWITCHES: Lazy<Regex> =
+    lazy_regex!(r"(?m)^nonvoluntary_ctxt_switches:\s*(\d+)");
+
 static RE_IO_READ: Lazy<Regex> = lazy_regex!(r"read_bytes:\s*(\d+)");
 
 static RE_IO_WRITE: Lazy<Regex> = lazy_regex!(r"write_bytes:\s*(\d+)");
 
+static RE_IO_RCHAR: Lazy<Regex> = lazy_regex!(r"(?m)^rchar:\s*(\d+)");
+
+static RE_IO_WCHAR: Lazy<Regex> = lazy_regex!(r"(?m)^wchar:\s*(\d+)");
+
+static RE_IO_SYSCR: Lazy<Regex> = lazy_regex!(r"(?m)^syscr:\s*(\d+)");
+
+static RE_IO_SYSCW: Lazy<Regex> = lazy_regex!(r"(?m)^syscw:\s*(\d+)");
+
+static RE_IO_CANCELLED_WRITE: Lazy<Regex> = lazy_regex!(r"(?m)^cancelled_write_bytes:\s*(\d+)");
+
 static RE_DRM_PDEV: Lazy<Regex> =
     lazy_regex!(r"drm-pdev:\s*([0-9A-Fa-f]{4}:[0-9A-Fa-f]{2}:[0-9A-Fa-f]{2}\.[0-9A-Fa-f])");
 
@@ -101,6 +139,21 @@ static NVIDIA_PROCESSES_STATS: Lazy<RwLock<HashMap<PciSlot, Vec<ProcessUtilizati
 static NVIDIA_PROCESS_INFOS: Lazy<RwLock<HashMap<PciSlot, Vec<ProcessInfo>>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
 
+/// Whether the kernel currently accounts the time tasks spend waiting for block I/O, see
+/// `kernel.task_delayacct` in the kernel's sysctl documentation
+static DELAY_ACCOUNTING: AtomicBool = AtomicBool::new(true);
+
+/// How long the values read from a process' `smaps_rollup` are reused for as long as its `statm` doesn't change
+const SMAPS_ROLLUP_MAX_AGE_MILLIS: u64 = 10_000;
+
+/// Key: PID
+static SMAPS_ROLLUP_CACHE: Lazy<RwLock<HashMap<libc::pid_t, CachedSmapsRollup>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Key: inode of the socket
+static LISTENING_SOCKETS: Lazy<RwLock<HashMap<u64, ListeningPort>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
 #[nutype(
     validate(less_or_equal = 19),
     validate(greater_or_equal = -20),
@@ -115,6 +168,340 @@ pub enum Containerization {
     None,
     Flatpak,
     Snap,
+    Docker,
+    Podman,
+    Lxc,
+    Nspawn,
+    /// A Podman container created by Toolbox
+    Toolbox,
+    /// A Podman or Docker container created by Distrobox
+    Distrobox,
+}
+
+/// The state of a process or thread as reported in the third field of `/proc/<pid>/stat`, see man proc(5)
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize, Copy)]
+pub enum ProcessState {
+    Running,
+    Sleeping,
+    DiskSleep,
+    Zombie,
+    Stopped,
+    TracingStop,
+    Dead,
+    Idle,
+    #[default]
+    Unknown,
+}
+
+impl From<char> for ProcessState {
+    fn from(value: char) -> Self {
+        match value {
+            'R' => ProcessState::Running,
+            'S' => ProcessState::Sleeping,
+            'D' => ProcessState::DiskSleep,
+            'Z' => ProcessState::Zombie,
+            'T' => ProcessState::Stopped,
+            't' => ProcessState::TracingStop,
+            'X' | 'x' => ProcessState::Dead,
+            'I' => ProcessState::Idle,
+            _ => ProcessState::Unknown,
+        }
+    }
+}
+
+/// Parts of `ProcessData` that are too expensive to gather for every process on every refresh if they aren't shown, so
+/// Resources only requests them while they are
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessDataOptions {
+    /// Whether the file descriptors of every process are resolved to find the ports it is listening on
+    pub listening_ports: bool,
+    /// Whether the wait counters of every thread of every process are read
+    pub thread_delays: bool,
+}
+
+impl ProcessDataOptions {
+    /// Options that gather everything
+    pub fn all() -> Self {
+        Self {
+            listening_ports: true,
+            thread_delays: true,
+        }
+    }
+
+    /// Returns the request for `resources-processes` that gathers the data of all processes with these options, which
+    /// consists of a word for every enabled option
+    pub fn to_request(self) -> String {
+        let mut words = Vec::new();
+
+        if self.listening_ports {
+            words.push("listening-ports");
+        }
+
+        if self.thread_delays {
+            words.push("thread-delays");
+        }
+
+        words.join(" ")
+    }
+
+    /// Parses a request created by `to_request()`, unknown words are ignored
+    pub fn from_request<S: AsRef<str>>(request: S) -> Self {
+        let mut options = Self::default();
+
+        for word in request.as_ref().split_whitespace() {
+            match word {
+                "listening-ports" => options.listening_ports = true,
+                "thread-delays" => options.thread_delays = true,
+                _ => {}
+            }
+        }
+
+        options
+    }
+}
+
+/// I/O scheduling class of a process, see man ioprio_set(2)
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IoPriorityClass {
+    /// No class has been set explicitly, the kernel derives the I/O priority from the niceness
+    #[default]
+    None = 0,
+    RealTime = 1,
+    BestEffort = 2,
+    Idle = 3,
+}
+
+impl IoPriorityClass {
+    pub fn from_raw(class: i32) -> Option<Self> {
+        match class {
+            0 => Some(IoPriorityClass::None),
+            1 => Some(IoPriorityClass::RealTime),
+            2 => Some(IoPriorityClass::BestEffort),
+            3 => Some(IoPriorityClass::Idle),
+            _ => None,
+        }
+    }
+}
+
+/// I/O priority of a process, consisting of a scheduling class and a level between 0 (highest priority) and 7
+/// (lowest priority), see man ioprio_set(2)
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IoPriority {
+    pub class: IoPriorityClass,
+    pub level: u8,
+}
+
+impl IoPriority {
+    pub fn from_raw(ioprio: i32) -> Self {
+        let class = IoPriorityClass::from_raw(ioprio >> IOPRIO_CLASS_SHIFT).unwrap_or_default();
+
+        let level = (ioprio & IOPRIO_PRIO_MASK).clamp(0, 7) as u8;
+
+        Self { class, level }
+    }
+
+    pub fn to_raw(self) -> i32 {
+        // the kernel rejects a level for the "none" class
+        if self.class == IoPriorityClass::None {
+            0
+        } else {
+            ((self.class as i32) << IOPRIO_CLASS_SHIFT) | (self.level.min(7) as i32)
+        }
+    }
+
+    /// Sets this I/O priority for the thread with the ID `tid` using ioprio_set(2), returns the errno on failure
+    pub fn apply(self, tid: libc::pid_t) -> Result<(), i32> {
+        unsafe {
+            syscalls::syscall!(
+                syscalls::Sysno::ioprio_set,
+                IOPRIO_WHO_PROCESS,
+                tid,
+                self.to_raw()
+            )
+        }
+        .map(|_| ())
+        .map_err(|errno| errno.into_raw())
+    }
+}
+
+/// CPU scheduling policy of a process as reported in the 41st field of `/proc/<pid>/stat`, see man sched(7)
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchedulingPolicy {
+    #[default]
+    Other,
+    Fifo,
+    RoundRobin,
+    Batch,
+    Idle,
+    Deadline,
+    Unknown,
+}
+
+impl SchedulingPolicy {
+    pub fn from_raw(policy: i32) -> Self {
+        match policy {
+            libc::SCHED_OTHER => SchedulingPolicy::Other,
+            libc::SCHED_FIFO => SchedulingPolicy::Fifo,
+            libc::SCHED_RR => SchedulingPolicy::RoundRobin,
+            libc::SCHED_BATCH => SchedulingPolicy::Batch,
+            libc::SCHED_IDLE => SchedulingPolicy::Idle,
+            6 => SchedulingPolicy::Deadline, // SCHED_DEADLINE, not exposed by libc
+            _ => SchedulingPolicy::Unknown,
+        }
+    }
+
+    /// Returns the raw value of this policy, `None` for policies that can't be set using `sched_setscheduler()`
+    pub fn to_raw(self) -> Option<i32> {
+        match self {
+            SchedulingPolicy::Other => Some(libc::SCHED_OTHER),
+            SchedulingPolicy::Fifo => Some(libc::SCHED_FIFO),
+            SchedulingPolicy::RoundRobin => Some(libc::SCHED_RR),
+            SchedulingPolicy::Batch => Some(libc::SCHED_BATCH),
+            SchedulingPolicy::Idle => Some(libc::SCHED_IDLE),
+            SchedulingPolicy::Deadline | SchedulingPolicy::Unknown => None,
+        }
+    }
+
+    /// Whether this is a real-time policy, i.e. whether a real-time priority between 1 and 99 applies
+    pub fn is_realtime(self) -> bool {
+        matches!(self, SchedulingPolicy::Fifo | SchedulingPolicy::RoundRobin)
+    }
+}
+
+/// Statistics of a single thread of a process, gathered from `/proc/<pid>/task/<tid>`
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadData {
+    pub tid: libc::pid_t,
+    pub comm: String,
+    pub state: ProcessState,
+    pub user_cpu_time: u64,
+    pub system_cpu_time: u64,
+    /// The logical CPU this thread was last executed on
+    pub last_cpu: usize,
+    pub niceness: Niceness,
+}
+
+impl ThreadData {
+    fn try_from_path(task_path: &Path) -> Result<Self> {
+        let stat = std::fs::read_to_string(task_path.join("stat"))?;
+        let comm = std::fs::read_to_string(task_path.join("comm"))?;
+
+        let tid = task_path
+            .file_name()
+            .context("task_path terminates in ..")?
+            .to_str()
+            .context("can't turn OsStr to str")?
+            .parse()?;
+
+        Self::from_stat(tid, comm.replace('\n', ""), &stat)
+    }
+
+    fn from_stat<S: AsRef<str>>(tid: libc::pid_t, comm: String, stat: S) -> Result<Self> {
+        let stat = ProcessData::split_stat(stat.as_ref())?;
+
+        let state = stat
+            .get(STAT_STATE)
+            .and_then(|state| state.chars().next())
+            .map(ProcessState::from)
+            .unwrap_or_default();
+        let user_cpu_time = stat
+            .get(STAT_USER_CPU_TIME)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let system_cpu_time = stat
+            .get(STAT_SYSTEM_CPU_TIME)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let niceness = stat
+            .get(STAT_NICE)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let last_cpu = stat
+            .get(STAT_PROCESSOR)
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default();
+
+        Ok(Self {
+            tid,
+            comm,
+            state,
+            user_cpu_time,
+            system_cpu_time,
+            last_cpu,
+            niceness,
+        })
+    }
+}
+
+/// How long a single thread of a process has been waiting, gathered from `/proc/<pid>/task/<tid>`. The counters of
+/// a process' own `stat` and `schedstat` files only cover its main thread, so the waits of a process are calculated
+/// from the changes of the counters of each of its threads.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadDelays {
+    pub tid: libc::pid_t,
+    /// Clock ticks this thread has spent waiting for block I/O (`delayacct_blkio_ticks`), `None` if delay accounting
+    /// is disabled or `stat` couldn't be read
+    pub io_delay: Option<u64>,
+    /// Nanoseconds this thread has spent runnable but waiting for a CPU, `None` if `schedstat` couldn't be read
+    pub run_queue_wait: Option<u64>,
+}
+
+impl ThreadDelays {
+    fn read(task_path: &Path) -> Option<Self> {
+        let tid = task_path.file_name()?.to_str()?.parse().ok()?;
+
+        let stat = std::fs::read_to_string(task_path.join("stat")).ok();
+        let schedstat = std::fs::read_to_string(task_path.join("schedstat")).ok();
+
+        Some(Self::from_files(
+            tid,
+            stat.as_deref(),
+            schedstat.as_deref(),
+            DELAY_ACCOUNTING.load(Ordering::Relaxed),
+        ))
+    }
+
+    fn from_files(
+        tid: libc::pid_t,
+        stat: Option<&str>,
+        schedstat: Option<&str>,
+        delay_accounting: bool,
+    ) -> Self {
+        let io_delay = stat
+            .filter(|_| delay_accounting)
+            .and_then(|stat| ProcessData::split_stat(stat).ok())
+            .and_then(|stat| stat.get(STAT_DELAYACCT_BLKIO_TICKS)?.parse().ok());
+
+        let run_queue_wait = schedstat.and_then(parse_schedstat);
+
+        Self {
+            tid,
+            io_delay,
+            run_queue_wait,
+        }
+    }
+}
+
+/// The memory usage of a process according to its `smaps_rollup`, every value is `None` if we're not permitted to read
+/// it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct SmapsRollupUsage {
+    proportional: Option<usize>,
+    unique: Option<usize>,
+    shared: Option<usize>,
+    anonymous: Option<usize>,
+}
+
+/// Reading `smaps_rollup` takes the mmap lock of the process and walks all of its mappings, which is a lot more
+/// expensive than reading `statm`. The usage is therefore only read again once `statm` has changed or the cached usage
+/// is older than `SMAPS_ROLLUP_MAX_AGE_MILLIS`, since the proportional set size of a process also changes when
+/// processes it shares memory with come and go.
+#[derive(Debug, Clone)]
+struct CachedSmapsRollup {
+    starttime: u64,
+    statm: String,
+    timestamp: u64,
+    usage: SmapsRollupUsage,
 }
 
 /// Represents GPU usage statistics per-process. Depending on the GPU manufacturer (which should be determined in
@@ -145,26 +532,79 @@ pub struct ProcessData {
     pub user: String,
     pub comm: String,
     pub commandline: String,
+    /// Target of `/proc/<pid>/exe`, `None` if we're not allowed to read it or if it's a kernel thread
+    pub exe: Option<String>,
+    pub state: ProcessState,
+    pub num_threads: usize,
+    pub voluntary_context_switches: u64,
+    pub involuntary_context_switches: u64,
+    pub minor_faults: u64,
+    pub major_faults: u64,
     pub user_cpu_time: u64,
     pub system_cpu_time: u64,
     pub niceness: Niceness,
+    pub scheduling_policy: SchedulingPolicy,
+    /// Real-time priority between 1 and 99 for real-time scheduling policies, 0 otherwise
+    pub realtime_priority: u32,
+    pub io_priority: IoPriority,
+    /// The badness score the OOM killer uses to choose which process to kill, see man proc_pid_oom_score(5)
+    pub oom_score: u32,
+    /// Between -1000 and 1000, see man proc_pid_oom_score_adj(5)
+    pub oom_score_adj: i32,
     pub affinity: Vec<bool>,
     pub memory_usage: usize,
+    /// Proportional set size read from `smaps_rollup`, `None` if not permitted to read
+    pub proportional_memory_usage: Option<usize>,
+    /// Unique set size (private clean + private dirty) read from `smaps_rollup`
+    pub unique_memory_usage: Option<usize>,
+    /// Shared clean + shared dirty read from `smaps_rollup`
+    pub shared_memory_usage: Option<usize>,
+    /// Anonymous memory (i.e. not backed by a file) read from `smaps_rollup`
+    pub anonymous_memory_usage: Option<usize>,
     pub swap_usage: usize,
     pub starttime: u64, // in clock ticks, see man proc(5)!
     pub cgroup: Option<String>,
+    /// The cgroup v2 path of the process relative to the root of the cgroup hierarchy, without the `0::` prefix
+    pub cgroup_path: Option<String>,
+    /// Whether the process' cgroup (or one of its ancestors) has been frozen using `cgroup.freeze`
+    pub frozen: bool,
     pub containerization: Containerization,
+    /// The container the process is running in, `None` if it isn't running in one or it's sandboxed by Flatpak or
+    /// Snap instead
+    pub container: Option<Container>,
     pub read_bytes: Option<u64>,
     pub write_bytes: Option<u64>,
+    /// Bytes read using read(2) and similar system calls, including those served from the page cache (`rchar`)
+    pub read_chars: Option<u64>,
+    /// Bytes written using write(2) and similar system calls, including those that haven't reached the drive yet
+    /// (`wchar`)
+    pub write_chars: Option<u64>,
+    /// Number of read system calls (`syscr`)
+    pub read_syscalls: Option<u64>,
+    /// Number of write system calls (`syscw`)
+    pub write_syscalls: Option<u64>,
+    /// Bytes that have been accounted to `write_bytes` but were never written to the drive, e.g. because the file
+    /// has been truncated before its dirty pages were written back
+    pub cancelled_write_bytes: Option<u64>,
     pub timestamp: u64,
     /// Key: PCI Slot ID of the GPU
     pub gpu_usage_stats: BTreeMap<PciSlot, GpuUsageStats>,
+    /// Always empty unless requested using `ProcessDataOptions::thread_delays`
+    pub thread_delays: Vec<ThreadDelays>,
+    /// Number of open file descriptors, `None` if we're not allowed to see them
+    pub fd_count: Option<usize>,
+    /// Ports the process is listening on, sorted and without duplicates. Always empty unless requested using
+    /// `ProcessDataOptions::listening_ports`.
+    pub listening_ports: Vec<ListeningPort>,
 }
 
 impl ProcessData {
     fn sanitize_cgroup<S: AsRef<str>>(cgroup: S) -> Option<String> {
         let cgroups_v2_line = cgroup.as_ref().split('\n').find(|s| s.starts_with("0::"))?;
-        if cgroups_v2_line.ends_with(".scope") {
+        if let Some(container) = Container::from_cgroup_path(&cgroups_v2_line[3..]) {
+            // the scopes of containers are named after the runtime rather than an app
+            Some(container.id)
+        } else if cgroups_v2_line.ends_with(".scope") {
             let cgroups_segments: Vec<&str> = cgroups_v2_line.split('-').collect();
             if cgroups_segments.len() > 1 {
                 cgroups_segments
@@ -221,22 +661,53 @@ impl ProcessData {
         }
     }
 
-    pub fn all_process_data() -> Result<Vec<Self>> {
+    /// Reads the listening internet sockets of our network namespace so that they can be matched with the file
+    /// descriptors of the processes.
+    pub fn update_listening_sockets() {
+        let listening_sockets = sockets::sockets("/proc/net", &INET_PROTOCOLS)
+            .into_iter()
+            .filter_map(|(inode, socket)| Some((inode, socket.listening_port()?)));
+
+        let mut sockets = LISTENING_SOCKETS.write().unwrap();
+        sockets.clear();
+        sockets.extend(listening_sockets);
+    }
+
+    /// Checks whether delay accounting is enabled. Kernels before 5.14 don't have the sysctl and always enable delay
+    /// accounting unless booted with `nodelayacct`.
+    pub fn update_delay_accounting() {
+        let enabled = std::fs::read_to_string("/proc/sys/kernel/task_delayacct")
+            .map_or(true, |task_delayacct| task_delayacct.trim() != "0");
+
+        DELAY_ACCOUNTING.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn all_process_data(options: ProcessDataOptions) -> Result<Vec<Self>> {
         Self::update_nvidia_stats();
+        if options.listening_ports {
+            Self::update_listening_sockets();
+        }
+        Self::update_delay_accounting();
 
         let mut process_data = vec![];
         for entry in glob("/proc/[0-9]*/").context("unable to glob")?.flatten() {
-            let data = ProcessData::try_from_path(&entry);
+            let data = ProcessData::try_from_path(&entry, options);
 
             if let Ok(data) = data {
                 process_data.push(data);
             }
         }
 
+        let pids: HashSet<libc::pid_t> = process_data.iter().map(|data| data.pid).collect();
+        SMAPS_ROLLUP_CACHE
+            .write()
+            .unwrap()
+            .retain(|pid, _| pids.contains(pid));
+
         Ok(process_data)
     }
 
-    pub fn try_from_path(proc_path: &PathBuf) -> Result<Self> {
+    pub fn try_from_path(proc_path: &PathBuf, options: ProcessDataOptions) -> Result<Self> {
         let stat = std::fs::read_to_string(proc_path.join("stat"))?;
         let statm = std::fs::read_to_string(proc_path.join("statm"))?;
         let status = std::fs::read_to_string(proc_path.join("status"))?;
@@ -244,6 +715,10 @@ impl ProcessData {
         let commandline = std::fs::read_to_string(proc_path.join("cmdline"))?;
         let io = std::fs::read_to_string(proc_path.join("io")).ok();
 
+        let exe = std::fs::read_link(proc_path.join("exe"))
+            .ok()
+            .map(|exe| exe.to_string_lossy().into_owned());
+
         let pid = proc_path
             .file_name()
             .context("proc_path terminates in ..")?
@@ -256,15 +731,10 @@ impl ProcessData {
             .cloned()
             .unwrap_or(String::from("root"));
 
-        let stat = stat
-            .split(')') // since we don't care about the pid or the executable name, split after the executable name to make our life easier
-            .last()
-            .context("stat doesn't have ')'")?
-            .split(' ')
-            .skip(1) // the first element would be a space, let's ignore that
-            .collect::<Vec<_>>();
+        let stat = Self::split_stat(&stat)?;
 
-        let statm = statm.split(' ').collect::<Vec<_>>();
+        let statm_raw = statm.trim();
+        let statm = statm_raw.split(' ').collect::<Vec<_>>();
 
         let comm = comm.replace('\n', "");
 
@@ -289,6 +759,46 @@ impl ProcessData {
             .get(STAT_STARTTIME)
             .context("wrong stat file format")
             .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let state = stat
+            .get(STAT_STATE)
+            .and_then(|state| state.chars().next())
+            .map(ProcessState::from)
+            .unwrap_or_default();
+        let num_threads = stat
+            .get(STAT_NUM_THREADS)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let minor_faults = stat
+            .get(STAT_MINOR_FAULTS)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let major_faults = stat
+            .get(STAT_MAJOR_FAULTS)
+            .context("wrong stat file format")
+            .and_then(|x| x.parse().context("couldn't parse stat file content"))?;
+        let realtime_priority = stat
+            .get(STAT_RT_PRIORITY)
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default();
+        let scheduling_policy = stat
+            .get(STAT_POLICY)
+            .and_then(|x| x.parse().ok())
+            .map(SchedulingPolicy::from_raw)
+            .unwrap_or_default();
+
+        let io_priority = Self::io_priority(pid);
+
+        let oom_score = std::fs::read_to_string(proc_path.join("oom_score"))
+            .ok()
+            .and_then(|oom_score| oom_score.trim().parse().ok())
+            .unwrap_or_default();
+        let oom_score_adj = std::fs::read_to_string(proc_path.join("oom_score_adj"))
+            .ok()
+            .and_then(|oom_score_adj| oom_score_adj.trim().parse().ok())
+            .unwrap_or_default();
+
+        let (voluntary_context_switches, involuntary_context_switches) =
+            Self::context_switches(&status);
 
         let mut affinity = Vec::with_capacity(*NUM_CPUS);
         RE_AFFINITY
@@ -336,34 +846,76 @@ impl ProcessData {
             )
             .saturating_mul(*PAGESIZE);
 
-        let cgroup = std::fs::read_to_string(proc_path.join("cgroup"))
-            .ok()
-            .and_then(|raw| Self::sanitize_cgroup(raw));
+        let SmapsRollupUsage {
+            proportional: proportional_memory_usage,
+            unique: unique_memory_usage,
+            shared: shared_memory_usage,
+            anonymous: anonymous_memory_usage,
+        } = Self::smaps_rollup_usage(proc_path, pid, starttime, statm_raw);
+
+        let raw_cgroup = std::fs::read_to_string(proc_path.join("cgroup")).ok();
 
-        let containerization = if commandline.starts_with("/snap/") {
-            Containerization::Snap
+        let cgroup = raw_cgroup.as_ref().and_then(Self::sanitize_cgroup);
+
+        let cgroup_path = raw_cgroup.as_ref().and_then(|raw| {
+            raw.lines()
+                .find_map(|line| line.strip_prefix("0::"))
+                .map(str::to_string)
+        });
+
+        let frozen = cgroup_path
+            .as_ref()
+            .and_then(|cgroup_path| {
+                std::fs::read_to_string(
+                    Path::new("/sys/fs/cgroup")
+                        .join(cgroup_path.trim_start_matches('/'))
+                        .join("cgroup.events"),
+                )
+                .ok()
+            })
+            .is_some_and(parse_cgroup_frozen);
+
+        let (containerization, container) = if commandline.starts_with("/snap/") {
+            (Containerization::Snap, None)
         } else if proc_path.join("root").join(".flatpak-info").exists() {
-            Containerization::Flatpak
+            (Containerization::Flatpak, None)
+        } else if let Some(container) = cgroup_path
+            .as_ref()
+            .and_then(|cgroup_path| Container::detect(cgroup_path, proc_path))
+        {
+            (container.runtime, Some(container))
         } else {
-            Containerization::None
+            (Containerization::None, None)
         };
 
-        let read_bytes = io.as_ref().and_then(|io| {
-            RE_IO_READ
-                .captures(io)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-        });
+        let io_counter = |regex: &Regex| {
+            io.as_ref().and_then(|io| {
+                regex
+                    .captures(io)
+                    .and_then(|captures| captures.get(1))
+                    .and_then(|capture| capture.as_str().parse::<u64>().ok())
+            })
+        };
 
-        let write_bytes = io.as_ref().and_then(|io| {
-            RE_IO_WRITE
-                .captures(io)
-                .and_then(|captures| captures.get(1))
-                .and_then(|capture| capture.as_str().parse::<u64>().ok())
-        });
+        let read_bytes = io_counter(&RE_IO_READ);
+        let write_bytes = io_counter(&RE_IO_WRITE);
+        let read_chars = io_counter(&RE_IO_RCHAR);
+        let write_chars = io_counter(&RE_IO_WCHAR);
+        let read_syscalls = io_counter(&RE_IO_SYSCR);
+        let write_syscalls = io_counter(&RE_IO_SYSCW);
+        let cancelled_write_bytes = io_counter(&RE_IO_CANCELLED_WRITE);
 
         let gpu_usage_stats = Self::gpu_usage_stats(proc_path, pid);
 
+        let thread_delays = if options.thread_delays {
+            Self::thread_delays(proc_path)
+        } else {
+            Vec::new()
+        };
+
+        let (fd_count, listening_ports) =
+            Self::file_descriptors(proc_path, options.listening_ports);
+
         let timestamp = unix_as_millis();
 
         Ok(Self {
@@ -372,22 +924,214 @@ impl ProcessData {
             user,
             comm,
             commandline,
+            exe,
+            state,
+            num_threads,
+            voluntary_context_switches,
+            involuntary_context_switches,
+            minor_faults,
+            major_faults,
             user_cpu_time,
             system_cpu_time,
             niceness: nice,
+            scheduling_policy,
+            realtime_priority,
+            io_priority,
+            oom_score,
+            oom_score_adj,
             affinity,
             memory_usage,
+            proportional_memory_usage,
+            unique_memory_usage,
+            shared_memory_usage,
+            anonymous_memory_usage,
             swap_usage,
             starttime,
             cgroup,
+            cgroup_path,
+            frozen,
             containerization,
+            container,
             read_bytes,
             write_bytes,
+            read_chars,
+            write_chars,
+            read_syscalls,
+            write_syscalls,
+            cancelled_write_bytes,
             timestamp,
             gpu_usage_stats,
+            thread_delays,
+            fd_count,
+            listening_ports,
         })
     }
 
+    /// Returns the number of open file descriptors and the ports the process is listening on
+    fn file_descriptors(
+        proc_path: &Path,
+        listening_ports: bool,
+    ) -> (Option<usize>, Vec<ListeningPort>) {
+        let Ok(entries) = std::fs::read_dir(proc_path.join("fd")) else {
+            return (None, Vec::new());
+        };
+
+        let listening_sockets = LISTENING_SOCKETS.read().unwrap();
+
+        // counting the file descriptors only requires listing the directory, resolving them to find sockets requires a
+        // readlink() for every single one, so that's only done if needed and if there's anything listening at all
+        let resolve = listening_ports && !listening_sockets.is_empty();
+
+        let mut fd_count = 0;
+        let mut listening_ports = Vec::new();
+
+        for entry in entries.flatten() {
+            fd_count += 1;
+
+            if !resolve {
+                continue;
+            }
+
+            if let Some(listening_port) = std::fs::read_link(entry.path())
+                .ok()
+                .and_then(|target| socket_inode(target.to_string_lossy()))
+                .and_then(|inode| listening_sockets.get(&inode))
+            {
+                listening_ports.push(*listening_port);
+            }
+        }
+
+        listening_ports.sort_unstable();
+        listening_ports.dedup();
+
+        (Some(fd_count), listening_ports)
+    }
+
+    /// Splits the contents of `/proc/<pid>/stat` into its fields after the executable name, so that the fields can be
+    /// accessed using the `STAT_*` constants
+    fn split_stat(stat: &str) -> Result<Vec<&str>> {
+        Ok(stat
+            .split(')') // since we don't care about the pid or the executable name, split after the executable name to make our life easier
+            .last()
+            .context("stat doesn't have ')'")?
+            .split(' ')
+            .skip(1) // the first element would be a space, let's ignore that
+            .collect())
+    }
+
+    /// Returns the voluntary and involuntary context switches from the contents of `/proc/<pid>/status`, 0 for those
+    /// that are missing
+    fn context_switches(status: &str) -> (u64, u64) {
+        let parse = |regex: &Regex| {
+            regex
+                .captures(status)
+                .and_then(|captures| captures.get(1))
+                .and_then(|capture| capture.as_str().parse::<u64>().ok())
+                .unwrap_or_default()
+        };
+
+        (
+            parse(&RE_VOLUNTARY_CTXT_SWITCHES),
+            parse(&RE_NONVOLUNTARY_CTXT_SWITCHES),
+        )
+    }
+
+    /// Returns the usage according to the `smaps_rollup` of the process with the PID `pid`, either freshly read or
+    /// from `SMAPS_ROLLUP_CACHE` if `statm` hasn't changed since it has been read
+    fn smaps_rollup_usage(
+        proc_path: &Path,
+        pid: libc::pid_t,
+        starttime: u64,
+        statm: &str,
+    ) -> SmapsRollupUsage {
+        let now = unix_as_millis();
+
+        if let Some(cached) = SMAPS_ROLLUP_CACHE.read().unwrap().get(&pid) {
+            if cached.starttime == starttime
+                && cached.statm == statm
+                && now.saturating_sub(cached.timestamp) < SMAPS_ROLLUP_MAX_AGE_MILLIS
+            {
+                return cached.usage;
+            }
+        }
+
+        let smaps_rollup = std::fs::read_to_string(proc_path.join("smaps_rollup"))
+            .ok()
+            .map(Self::parse_smaps_rollup)
+            .unwrap_or_default();
+
+        let usage = SmapsRollupUsage {
+            proportional: smaps_rollup.get("Pss").copied(),
+            unique: smaps_rollup
+                .get("Private_Clean")
+                .zip(smaps_rollup.get("Private_Dirty"))
+                .map(|(clean, dirty)| clean.saturating_add(*dirty)),
+            shared: smaps_rollup
+                .get("Shared_Clean")
+                .zip(smaps_rollup.get("Shared_Dirty"))
+                .map(|(clean, dirty)| clean.saturating_add(*dirty)),
+            anonymous: smaps_rollup.get("Anonymous").copied(),
+        };
+
+        SMAPS_ROLLUP_CACHE.write().unwrap().insert(
+            pid,
+            CachedSmapsRollup {
+                starttime,
+                statm: statm.to_string(),
+                timestamp: now,
+                usage,
+            },
+        );
+
+        usage
+    }
+
+    /// Parses the contents of `/proc/<pid>/smaps_rollup` into a map of the field names and their values in bytes
+    fn parse_smaps_rollup<S: AsRef<str>>(smaps_rollup: S) -> HashMap<String, usize> {
+        smaps_rollup
+            .as_ref()
+            .lines()
+            .filter_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                let kibibytes = value
+                    .trim()
+                    .strip_suffix("kB")?
+                    .trim()
+                    .parse::<usize>()
+                    .ok()?;
+                Some((key.to_string(), kibibytes.saturating_mul(1024)))
+            })
+            .collect()
+    }
+
+    fn io_priority(pid: libc::pid_t) -> IoPriority {
+        unsafe { syscalls::syscall!(syscalls::Sysno::ioprio_get, IOPRIO_WHO_PROCESS, pid) }
+            .map(|ioprio| IoPriority::from_raw(ioprio as i32))
+            .unwrap_or_default()
+    }
+
+    fn threads(proc_path: &Path) -> Vec<ThreadData> {
+        std::fs::read_dir(proc_path.join("task"))
+            .map(|read_dir| {
+                read_dir
+                    .flatten()
+                    .filter_map(|entry| ThreadData::try_from_path(&entry.path()).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn thread_delays(proc_path: &Path) -> Vec<ThreadDelays> {
+        std::fs::read_dir(proc_path.join("task"))
+            .map(|read_dir| {
+                read_dir
+                    .flatten()
+                    .filter_map(|entry| ThreadDelays::read(&entry.path()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     fn gpu_usage_stats(proc_path: &Path, pid: i32) -> BTreeMap<PciSlot, GpuUsageStats> {
         let nvidia_stats = Self::nvidia_gpu_stats_all(pid);
         let mut other_stats = Self::other_gpu_usage_stats(proc_path, pid).unwrap_or_default();
@@ -654,3 +1398,223 @@ pub fn unix_as_millis() -> u64 {
         .unwrap()
         .as_millis() as u64
 }
+
+/// Parses the run-queue wait time in nanoseconds from `/proc/<pid>/schedstat`, which consists of the time spent on
+/// the CPU, the time spent waiting on a run-queue and the number of timeslices run
+fn parse_schedstat<S: AsRef<str>>(schedstat: S) -> Option<u64> {
+    schedstat.as_ref().split_whitespace().nth(1)?.parse().ok()
+}
+
+/// Parses whether a cgroup is frozen from its `cgroup.events` file, which consists of lines like `frozen 1`
+fn parse_cgroup_frozen<S: AsRef<str>>(events: S) -> bool {
+    events
+        .as_ref()
+        .lines()
+        .any(|line| line.trim() == "frozen 1")
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use crate::{
+        parse_cgroup_frozen, parse_schedstat, IoPriority, IoPriorityClass, Niceness, ProcessData,
+        ProcessDataOptions, ProcessState, SchedulingPolicy, ThreadData, ThreadDelays,
+        STAT_DELAYACCT_BLKIO_TICKS, STAT_MAJOR_FAULTS, STAT_MINOR_FAULTS, STAT_NUM_THREADS,
+        STAT_PARENT_PID, STAT_STARTTIME, STAT_STATE, STAT_SYSTEM_CPU_TIME, STAT_USER_CPU_TIME,
+    };
+
+    const THREAD_STAT: &str = "1234 (worker) thread) S 1000 1000 1000 0 -1 4194368 2450 0 0 0 \
+        512 128 0 0 20 5 24 0 16432 1200000000 3100 18446744073709551615 1 1 0 0 0 0 0 4096 \
+        17663 0 0 0 17 7 0 0 42 0 0 0 0 0 0 0 0 0 0";
+
+    #[test]
+    fn thread_from_stat() {
+        let parsed = ThreadData::from_stat(1234, "worker) thread".into(), THREAD_STAT).unwrap();
+
+        let expected = ThreadData {
+            tid: 1234,
+            comm: "worker) thread".into(),
+            state: ProcessState::Sleeping,
+            user_cpu_time: 512,
+            system_cpu_time: 128,
+            last_cpu: 7,
+            niceness: Niceness::try_new(5).unwrap(),
+        };
+
+        assert_eq!(expected, parsed)
+    }
+
+    #[test]
+    fn thread_delays_from_files() {
+        let delays = ThreadDelays::from_files(
+            1234,
+            Some(THREAD_STAT),
+            Some("1234567890 81234567 4711\n"),
+            true,
+        );
+
+        assert_eq!(
+            ThreadDelays {
+                tid: 1234,
+                io_delay: Some(42),
+                run_queue_wait: Some(81_234_567),
+            },
+            delays
+        );
+
+        // one counter being unavailable must not affect the other one
+        let delays = ThreadDelays::from_files(1234, Some(THREAD_STAT), None, false);
+
+        assert_eq!(None, delays.io_delay);
+        assert_eq!(None, delays.run_queue_wait);
+
+        let delays = ThreadDelays::from_files(1234, None, Some("1 2 3"), true);
+
+        assert_eq!(None, delays.io_delay);
+        assert_eq!(Some(2), delays.run_queue_wait);
+    }
+
+    #[test]
+    fn schedstat_parsing() {
+        assert_eq!(
+            Some(81_234_567),
+            parse_schedstat("1234567890 81234567 4711\n")
+        );
+        assert_eq!(None, parse_schedstat("1234567890"));
+        assert_eq!(None, parse_schedstat(""));
+    }
+
+    #[test]
+    fn process_stat_fields() {
+        let stat = "4711 (Web Content) (2)) R 4700 4700 4700 0 -1 4194560 183207 0 12 0 \
+            9120 2204 0 0 20 0 31 0 892340 3100000000 61230 18446744073709551615 1 1 0 0 0 0 0 \
+            4096 17663 0 0 0 17 3 0 0 9 0 0 0 0 0 0 0 0 0 0";
+
+        let stat = ProcessData::split_stat(stat).unwrap();
+
+        assert_eq!(Some(&"R"), stat.get(STAT_STATE));
+        assert_eq!(Some(&"4700"), stat.get(STAT_PARENT_PID));
+        assert_eq!(Some(&"183207"), stat.get(STAT_MINOR_FAULTS));
+        assert_eq!(Some(&"12"), stat.get(STAT_MAJOR_FAULTS));
+        assert_eq!(Some(&"9120"), stat.get(STAT_USER_CPU_TIME));
+        assert_eq!(Some(&"2204"), stat.get(STAT_SYSTEM_CPU_TIME));
+        assert_eq!(Some(&"31"), stat.get(STAT_NUM_THREADS));
+        assert_eq!(Some(&"892340"), stat.get(STAT_STARTTIME));
+        assert_eq!(Some(&"9"), stat.get(STAT_DELAYACCT_BLKIO_TICKS));
+    }
+
+    #[test]
+    fn context_switches_from_status() {
+        let status = concat!(
+            "Name:\tfirefox\n",
+            "State:\tS (sleeping)\n",
+            "Threads:\t98\n",
+            "Cpus_allowed:\tffff\n",
+            "voluntary_ctxt_switches:\t150432\n",
+            "nonvoluntary_ctxt_switches:\t2871\n",
+        );
+
+        assert_eq!((150_432, 2871), ProcessData::context_switches(status));
+
+        // kernel threads without the fields as well as the order of the lines shouldn't matter
+        assert_eq!(
+            (0, 12),
+            ProcessData::context_switches("nonvoluntary_ctxt_switches:\t12\n")
+        );
+        assert_eq!((0, 0), ProcessData::context_switches("Name:\tkthreadd\n"));
+    }
+
+    #[test]
+    fn smaps_rollup_parsing() {
+        let smaps_rollup = concat!(
+            "55d1c3a4e000-7ffd5b5f7000 ---p 00000000 00:00 0                          [rollup]\n",
+            "Rss:               15872 kB\n",
+            "Pss:                6120 kB\n",
+            "Pss_Anon:           3508 kB\n",
+            "Shared_Clean:       9400 kB\n",
+            "Shared_Dirty:          0 kB\n",
+            "Private_Clean:      2964 kB\n",
+            "Private_Dirty:      3508 kB\n",
+            "Anonymous:          3508 kB\n",
+            "THPeligible:           0\n",
+        );
+
+        let parsed = ProcessData::parse_smaps_rollup(smaps_rollup);
+
+        assert_eq!(Some(&(6120 * 1024)), parsed.get("Pss"));
+        assert_eq!(Some(&(3508 * 1024)), parsed.get("Anonymous"));
+        assert_eq!(Some(&0), parsed.get("Shared_Dirty"));
+        assert_eq!(None, parsed.get("THPeligible"));
+        assert_eq!(
+            None,
+            parsed.get("55d1c3a4e000-7ffd5b5f7000 ---p 00000000 00")
+        );
+    }
+
+    #[test]
+    fn process_state_from_char() {
+        assert_eq!(ProcessState::DiskSleep, ProcessState::from('D'));
+        assert_eq!(ProcessState::Zombie, ProcessState::from('Z'));
+        assert_eq!(ProcessState::Unknown, ProcessState::from('?'));
+    }
+
+    #[test]
+    fn io_priority_raw_roundtrip() {
+        let io_priority = IoPriority::from_raw((2 << 13) | 4);
+
+        assert_eq!(IoPriorityClass::BestEffort, io_priority.class);
+        assert_eq!(4, io_priority.level);
+        assert_eq!((2 << 13) | 4, io_priority.to_raw());
+        assert_eq!(IoPriority::default(), IoPriority::from_raw(0));
+        assert_eq!(
+            0,
+            IoPriority {
+                class: IoPriorityClass::None,
+                level: 4
+            }
+            .to_raw()
+        );
+        assert_eq!(Some(IoPriorityClass::Idle), IoPriorityClass::from_raw(3));
+        assert_eq!(None, IoPriorityClass::from_raw(4));
+    }
+
+    #[test]
+    fn scheduling_policy_from_raw() {
+        assert_eq!(SchedulingPolicy::Batch, SchedulingPolicy::from_raw(3));
+        assert_eq!(SchedulingPolicy::Deadline, SchedulingPolicy::from_raw(6));
+        assert_eq!(None, SchedulingPolicy::Deadline.to_raw());
+        assert!(SchedulingPolicy::RoundRobin.is_realtime());
+    }
+
+    #[test]
+    fn data_options_request_roundtrip() {
+        assert_eq!("", ProcessDataOptions::default().to_request());
+        assert_eq!(
+            ProcessDataOptions::default(),
+            ProcessDataOptions::from_request("")
+        );
+        assert_eq!(
+            ProcessDataOptions::all(),
+            ProcessDataOptions::from_request(ProcessDataOptions::all().to_request())
+        );
+        assert_eq!(
+            ProcessDataOptions::all(),
+            ProcessDataOptions::from_request(" thread-delays listening-ports  something-newer\n")
+        );
+        assert_eq!(
+            ProcessDataOptions {
+                listening_ports: false,
+                thread_delays: true,
+            },
+            ProcessDataOptions::from_request("thread-delays")
+        );
+    }
+
+    #[test]
+    fn cgroup_events_parsing() {
+        assert!(parse_cgroup_frozen("populated 1\nfrozen 1\n"));
+        assert!(!parse_cgroup_frozen("populated 1\nfrozen 0\n"));
+        assert!(!parse_cgroup_frozen(""));
+    }
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+
+/// Names of the resource limits as they appear in `/proc/<pid>/limits`, indexed by their `RLIMIT_*` constant.
+pub const RESOURCE_NAMES: [&str; 16] = [
+    "Max cpu time",
+    "Max file size",
+    "Max data size",
+    "Max stack size",
+    "Max core file size",
+    "Max resident set",
+    "Max processes",
+    "Max open files",
+    "Max locked memory",
+    "Max address space",
+    "Max file locks",
+    "Max pending signals",
+    "Max msgqueue size",
+    "Max nice priority",
+    "Max realtime priority",
+    "Max realtime timeout",
+];
+
+/// A resource limit of a process, see man getrlimit(2).
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceLimit {
+    /// The `RLIMIT_*` constant of this resource
+    pub resource: u32,
+    /// `None` if unlimited
+    pub soft: Option<u64>,
+    /// `None` if unlimited
+    pub hard: Option<u64>,
+}
+
+impl ResourceLimit {
+    pub fn name(&self) -> &'static str {
+        RESOURCE_NAMES
+            .get(self.resource as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Parses the contents of `/proc/<pid>/limits`. Limits with unknown names are skipped.
+pub fn parse_limits<S: AsRef<str>>(limits: S) -> Vec<ResourceLimit> {
+    limits
+        .as_ref()
+        .lines()
+        .filter_map(|line| {
+            let (resource, name) = RESOURCE_NAMES
+                .iter()
+                .enumerate()
+                .find(|(_, name)| line.starts_with(**name))?;
+
+            let mut values = line[name.len()..].split_whitespace();
+
+            Some(ResourceLimit {
+                resource: resource as u32,
+                soft: parse_limit_value(values.next()?)?,
+                hard: parse_limit_value(values.next()?)?,
+            })
+        })
+        .collect()
+}
+
+/// Parses a single limit value, returns `Some(None)` if it's unlimited and `None` if it couldn't be parsed
+fn parse_limit_value(value: &str) -> Option<Option<u64>> {
+    if value == "unlimited" {
+        Some(None)
+    } else {
+        value.parse().ok().map(Some)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{parse_limits, ResourceLimit};
+
+    const LIMITS: &str = "Limit                     Soft Limit           Hard Limit           Units     \n\
+                          Max cpu time              unlimited            unlimited            seconds   \n\
+                          Max file size             unlimited            unlimited            bytes     \n\
+                          Max stack size            8388608              unlimited            bytes     \n\
+                          Max processes             127616               127616               processes \n\
+                          Max open files            1024                 524288               files     \n\
+                          Max realtime priority     0                    0                    \n\
+                          Max realtime timeout      unlimited            unlimited            us        \n";
+
+    #[test]
+    fn limits_parsing() {
+        let limits = parse_limits(LIMITS);
+
+        assert_eq!(
+            limits,
+            vec![
+                ResourceLimit {
+                    resource: 0,
+                    soft: None,
+                    hard: None
+                },
+                ResourceLimit {
+                    resource: 1,
+                    soft: None,
+                    hard: None
+                },
+                ResourceLimit {
+                    resource: 3,
+                    soft: Some(8388608),
+                    hard: None
+                },
+                ResourceLimit {
+                    resource: 6,
+                    soft: Some(127616),
+                    hard: Some(127616)
+                },
+                ResourceLimit {
+                    resource: 7,
+                    soft: Some(1024),
+                    hard: Some(524288)
+                },
+                ResourceLimit {
+                    resource: 14,
+                    soft: Some(0),
+                    hard: Some(0)
+                },
+                ResourceLimit {
+                    resource: 15,
+                    soft: None,
+                    hard: None
+                },
+            ]
+        );
+
+        assert_eq!(limits[4].name(), "Max open files");
+    }
+}
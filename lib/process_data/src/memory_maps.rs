@@ -0,0 +1,230 @@
+use std::ops::AddAssign;
+
+use serde::{Deserialize, Serialize};
+
+/// What a memory mapping is used for, derived from its path
+#[derive(
+    Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum MappingCategory {
+    Heap,
+    Stack,
+    #[default]
+    Anonymous,
+    File,
+    SharedLibrary,
+    /// Mappings provided by the kernel like `[vdso]`
+    Other,
+}
+
+impl MappingCategory {
+    fn from_path(path: Option<&str>) -> Self {
+        let Some(path) = path else {
+            return MappingCategory::Anonymous;
+        };
+
+        if path == "[heap]" {
+            MappingCategory::Heap
+        } else if path.starts_with("[stack") {
+            MappingCategory::Stack
+        } else if path.starts_with("[anon") {
+            // named anonymous mappings like [anon:name] or [anon_shmem:name]
+            MappingCategory::Anonymous
+        } else if path.starts_with('/') {
+            let file_name = path
+                .trim_end_matches(" (deleted)")
+                .rsplit('/')
+                .next()
+                .unwrap_or_default();
+
+            if file_name.ends_with(".so") || file_name.contains(".so.") {
+                MappingCategory::SharedLibrary
+            } else {
+                MappingCategory::File
+            }
+        } else {
+            MappingCategory::Other
+        }
+    }
+}
+
+/// Memory usage of one or more mappings in bytes
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MappingUsage {
+    pub rss: usize,
+    pub pss: usize,
+    pub swap: usize,
+    /// Shared dirty + private dirty
+    pub dirty: usize,
+}
+
+impl AddAssign for MappingUsage {
+    fn add_assign(&mut self, rhs: Self) {
+        self.rss += rhs.rss;
+        self.pss += rhs.pss;
+        self.swap += rhs.swap;
+        self.dirty += rhs.dirty;
+    }
+}
+
+/// A memory mapping of a process as it appears in `/proc/<pid>/smaps`, see man proc_pid_smaps(5)
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryMapping {
+    pub start: u64,
+    pub end: u64,
+    /// E.g. `r-xp`
+    pub permissions: String,
+    pub offset: u64,
+    /// The file backing this mapping or a pseudo-path like `[heap]`, `None` for anonymous mappings
+    pub path: Option<String>,
+    pub category: MappingCategory,
+    pub usage: MappingUsage,
+}
+
+/// Parses the contents of `/proc/<pid>/smaps`
+pub fn parse_smaps<S: AsRef<str>>(smaps: S) -> Vec<MemoryMapping> {
+    let mut mappings: Vec<MemoryMapping> = Vec::new();
+
+    for line in smaps.as_ref().lines() {
+        if let Some(mapping) = parse_mapping_header(line) {
+            mappings.push(mapping);
+            continue;
+        }
+
+        let Some(mapping) = mappings.last_mut() else {
+            continue;
+        };
+
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        // all the fields we're interested in are in kB
+        let Some(value) = value
+            .trim()
+            .strip_suffix(" kB")
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .map(|value| value.saturating_mul(1024))
+        else {
+            continue;
+        };
+
+        match key {
+            "Rss" => mapping.usage.rss = value,
+            "Pss" => mapping.usage.pss = value,
+            "Swap" => mapping.usage.swap = value,
+            "Shared_Dirty" | "Private_Dirty" => mapping.usage.dirty += value,
+            _ => {}
+        }
+    }
+
+    mappings
+}
+
+/// Parses a line like `7f3a1c000000-7f3a1c021000 r-xp 00000000 fd:01 1234 /usr/lib/libc.so.6`
+fn parse_mapping_header(line: &str) -> Option<MemoryMapping> {
+    let mut fields = line.split_whitespace();
+
+    let (start, end) = fields.next()?.split_once('-')?;
+    let start = u64::from_str_radix(start, 16).ok()?;
+    let end = u64::from_str_radix(end, 16).ok()?;
+
+    let permissions = fields.next()?.to_string();
+    let offset = u64::from_str_radix(fields.next()?, 16).ok()?;
+
+    // the first five fields are separated by single spaces, the path is padded and may contain spaces itself
+    let path = line
+        .splitn(6, ' ')
+        .nth(5)
+        .map(str::trim_start)
+        .filter(|path| !path.is_empty())
+        .map(str::to_string);
+
+    let category = MappingCategory::from_path(path.as_deref());
+
+    Some(MemoryMapping {
+        start,
+        end,
+        permissions,
+        offset,
+        path,
+        category,
+        usage: MappingUsage::default(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{parse_smaps, MappingCategory, MappingUsage};
+
+    const SMAPS: &str = "55d0c8a4e000-55d0c8a50000 r--p 00000000 fd:01 1234567                    /usr/bin/cat\n\
+                         Size:                  8 kB\n\
+                         Rss:                   8 kB\n\
+                         Pss:                   4 kB\n\
+                         Shared_Clean:          0 kB\n\
+                         Shared_Dirty:          0 kB\n\
+                         Private_Clean:         8 kB\n\
+                         Private_Dirty:         0 kB\n\
+                         Swap:                  0 kB\n\
+                         VmFlags: rd mr mw me sd\n\
+                         55d0c9a00000-55d0c9a21000 rw-p 00000000 00:00 0                          [heap]\n\
+                         Rss:                  12 kB\n\
+                         Pss:                  12 kB\n\
+                         Shared_Dirty:          4 kB\n\
+                         Private_Dirty:         8 kB\n\
+                         Swap:                 16 kB\n\
+                         7f3a1c000000-7f3a1c021000 rw-p 00000000 00:00 0 \n\
+                         Rss:                   4 kB\n\
+                         7f3a1d000000-7f3a1d028000 r-xp 00028000 fd:01 7654321                    /usr/lib/x86_64-linux-gnu/libc.so.6\n\
+                         Rss:                 160 kB\n\
+                         7f3a1e000000-7f3a1e001000 rw-s 00000000 00:01 1024                       /tmp/my file (deleted)\n\
+                         Rss:                   4 kB\n\
+                         7ffd2b5fe000-7ffd2b600000 r-xp 00000000 00:00 0                          [vdso]\n\
+                         Rss:                   4 kB\n";
+
+    #[test]
+    fn smaps_parsing() {
+        let mappings = parse_smaps(SMAPS);
+
+        assert_eq!(mappings.len(), 6);
+
+        assert_eq!(mappings[0].start, 0x55d0c8a4e000);
+        assert_eq!(mappings[0].end, 0x55d0c8a50000);
+        assert_eq!(mappings[0].permissions, "r--p");
+        assert_eq!(mappings[0].path.as_deref(), Some("/usr/bin/cat"));
+        assert_eq!(mappings[0].category, MappingCategory::File);
+        assert_eq!(
+            mappings[0].usage,
+            MappingUsage {
+                rss: 8 * 1024,
+                pss: 4 * 1024,
+                swap: 0,
+                dirty: 0
+            }
+        );
+
+        assert_eq!(mappings[1].category, MappingCategory::Heap);
+        assert_eq!(
+            mappings[1].usage,
+            MappingUsage {
+                rss: 12 * 1024,
+                pss: 12 * 1024,
+                swap: 16 * 1024,
+                dirty: 12 * 1024
+            }
+        );
+
+        assert_eq!(mappings[2].path, None);
+        assert_eq!(mappings[2].category, MappingCategory::Anonymous);
+
+        assert_eq!(mappings[3].offset, 0x28000);
+        assert_eq!(mappings[3].category, MappingCategory::SharedLibrary);
+
+        assert_eq!(mappings[4].path.as_deref(), Some("/tmp/my file (deleted)"));
+        assert_eq!(mappings[4].category, MappingCategory::File);
+
+        assert_eq!(mappings[5].category, MappingCategory::Other);
+    }
+}
@@ -0,0 +1,158 @@
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+use syscalls::{syscall, Sysno};
+
+use crate::STAT_STARTTIME;
+
+/// Exit code of the helper binaries if the process behind a PID doesn't have the expected start time anymore, i.e. the
+/// original process has exited and its PID has been reused by another process.
+pub const EXIT_STARTTIME_MISMATCH: i32 = 252;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PidfdError {
+    /// The process has a different start time than expected
+    StarttimeMismatch,
+    /// A syscall failed with the contained errno
+    Errno(i32),
+}
+
+impl PidfdError {
+    /// Returns the exit code the helper binaries should use for this error
+    pub fn exit_code(self) -> i32 {
+        match self {
+            PidfdError::StarttimeMismatch => EXIT_STARTTIME_MISMATCH,
+            PidfdError::Errno(errno) => errno,
+        }
+    }
+}
+
+/// A file descriptor referring to exactly one process, see man pidfd_open(2).
+#[derive(Debug)]
+pub struct Pidfd {
+    fd: OwnedFd,
+    pid: libc::pid_t,
+}
+
+impl Pidfd {
+    /// Opens a pidfd for `pid` without checking which process it refers to.
+    pub fn open(pid: libc::pid_t) -> Result<Self, PidfdError> {
+        let fd = unsafe { syscall!(Sysno::pidfd_open, pid, 0) }
+            .map_err(|errno| PidfdError::Errno(errno.into_raw()))?;
+
+        Ok(Self {
+            fd: unsafe { OwnedFd::from_raw_fd(fd as i32) },
+            pid,
+        })
+    }
+
+    /// Opens a pidfd for `pid` and checks that the process it refers to has been started at `starttime` (in clock
+    /// ticks after boot, see man proc(5)).
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the pidfd couldn't be opened or if the start time doesn't match.
+    pub fn open_verified(pid: libc::pid_t, starttime: u64) -> Result<Self, PidfdError> {
+        let pidfd = Self::open(pid)?;
+
+        // the pidfd can't change the process it refers to anymore, so if the process with this PID still has the
+        // expected start time *after* opening the pidfd, the pidfd refers to the expected process
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat"))
+            .map_err(|err| PidfdError::Errno(err.raw_os_error().unwrap_or(libc::ESRCH)))?;
+
+        if starttime_from_stat(&stat) == Some(starttime) {
+            Ok(pidfd)
+        } else {
+            Err(PidfdError::StarttimeMismatch)
+        }
+    }
+
+    /// Opens a pidfd for `pid` and verifies its start time if `starttime` is `Some`.
+    pub fn open_maybe_verified(
+        pid: libc::pid_t,
+        starttime: Option<u64>,
+    ) -> Result<Self, PidfdError> {
+        match starttime {
+            Some(starttime) => Self::open_verified(pid, starttime),
+            None => Self::open(pid),
+        }
+    }
+
+    pub fn pid(&self) -> libc::pid_t {
+        self.pid
+    }
+
+    /// Sends `signal` to the process this pidfd refers to, see man pidfd_send_signal(2).
+    pub fn send_signal(&self, signal: i32) -> Result<(), PidfdError> {
+        unsafe { syscall!(Sysno::pidfd_send_signal, self.fd.as_raw_fd(), signal, 0, 0) }
+            .map(|_| ())
+            .map_err(|errno| PidfdError::Errno(errno.into_raw()))
+    }
+}
+
+/// Parses an argument of the form `<PID>[:<STARTTIME>]` as passed to the helper binaries.
+pub fn parse_pid_arg<S: AsRef<str>>(arg: S) -> Option<(libc::pid_t, Option<u64>)> {
+    let arg = arg.as_ref().trim();
+
+    if let Some((pid, starttime)) = arg.split_once(':') {
+        Some((pid.parse().ok()?, Some(starttime.parse().ok()?)))
+    } else {
+        Some((arg.parse().ok()?, None))
+    }
+}
+
+/// Parses the output of the helper binaries in batch mode, which consists of one `<PID> <RESULT CODE>` line per
+/// entry.
+pub fn parse_batch_output<S: AsRef<str>>(output: S) -> Vec<(libc::pid_t, i32)> {
+    output
+        .as_ref()
+        .lines()
+        .filter_map(|line| {
+            let (pid, code) = line.trim().split_once(' ')?;
+            Some((pid.parse().ok()?, code.parse().ok()?))
+        })
+        .collect()
+}
+
+fn starttime_from_stat<S: AsRef<str>>(stat: S) -> Option<u64> {
+    stat.as_ref()
+        .rsplit_once(')')
+        .map(|(_, stat)| stat)?
+        .split(' ')
+        .skip(1)
+        .nth(STAT_STARTTIME)?
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{parse_batch_output, parse_pid_arg, starttime_from_stat};
+
+    #[test]
+    fn pid_arg_parsing() {
+        assert_eq!(parse_pid_arg("1234"), Some((1234, None)));
+        assert_eq!(parse_pid_arg("1234:5678"), Some((1234, Some(5678))));
+        assert_eq!(parse_pid_arg("1234:"), None);
+        assert_eq!(parse_pid_arg("abc"), None);
+    }
+
+    #[test]
+    fn batch_output_parsing() {
+        let output = "1234 0\n5678 252\n\n0 255\n";
+
+        assert_eq!(
+            parse_batch_output(output),
+            vec![(1234, 0), (5678, 252), (0, 255)]
+        );
+    }
+
+    #[test]
+    fn starttime_parsing() {
+        let stat = "1234 (a (weird) name) S 1 1234 1234 0 -1 4194560 1000 0 5 0 120 30 0 0 20 0 4 0 987654 \
+                    12345678 1000 18446744073709551615 1 1 0 0 0 0 0 4096 0 0 0 0 17 3 0 0 0 0 0";
+
+        assert_eq!(starttime_from_stat(stat), Some(987654));
+    }
+}
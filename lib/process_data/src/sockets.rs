@@ -0,0 +1,390 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Set in the flags of a Unix socket in `/proc/net/unix` if it's listening for connections
+const UNIX_ACCEPTCON: u32 = 1 << 16;
+
+/// The socket tables in `/proc/<pid>/net/` that contain internet sockets
+pub const INET_PROTOCOLS: [Protocol; 4] =
+    [Protocol::Tcp, Protocol::Tcp6, Protocol::Udp, Protocol::Udp6];
+
+#[derive(
+    Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum Protocol {
+    #[default]
+    Tcp,
+    Tcp6,
+    Udp,
+    Udp6,
+    Unix,
+}
+
+impl Protocol {
+    /// The name of the socket table of this protocol in `/proc/<pid>/net/`
+    pub fn table_name(self) -> &'static str {
+        match self {
+            Protocol::Tcp => "tcp",
+            Protocol::Tcp6 => "tcp6",
+            Protocol::Udp => "udp",
+            Protocol::Udp6 => "udp6",
+            Protocol::Unix => "unix",
+        }
+    }
+
+    pub fn is_tcp(self) -> bool {
+        matches!(self, Protocol::Tcp | Protocol::Tcp6)
+    }
+
+    pub fn is_udp(self) -> bool {
+        matches!(self, Protocol::Udp | Protocol::Udp6)
+    }
+}
+
+/// The state of a socket. TCP sockets use the TCP states, connectionless sockets are either `Connected` or
+/// `Unconnected` and listening Unix sockets are `Listen`.
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SocketState {
+    Established,
+    SynSent,
+    SynReceived,
+    FinWait1,
+    FinWait2,
+    TimeWait,
+    Close,
+    CloseWait,
+    LastAck,
+    Listen,
+    Closing,
+    Connected,
+    Unconnected,
+    #[default]
+    Unknown,
+}
+
+impl SocketState {
+    /// Converts the `st` field of `/proc/net/{tcp,tcp6}`, see `include/net/tcp_states.h` in the kernel
+    fn from_tcp(state: u8) -> Self {
+        match state {
+            0x01 => SocketState::Established,
+            0x02 => SocketState::SynSent,
+            0x03 => SocketState::SynReceived,
+            0x04 => SocketState::FinWait1,
+            0x05 => SocketState::FinWait2,
+            0x06 => SocketState::TimeWait,
+            0x07 => SocketState::Close,
+            0x08 => SocketState::CloseWait,
+            0x09 => SocketState::LastAck,
+            0x0A => SocketState::Listen,
+            0x0B => SocketState::Closing,
+            _ => SocketState::Unknown,
+        }
+    }
+
+    /// Converts the `st` field of `/proc/net/{udp,udp6}`, which reuses the TCP states
+    fn from_udp(state: u8) -> Self {
+        match state {
+            0x01 => SocketState::Connected,
+            0x07 => SocketState::Unconnected,
+            _ => SocketState::Unknown,
+        }
+    }
+
+    /// Converts the `St` and `Flags` fields of `/proc/net/unix`, see `enum socket_state` in the kernel
+    fn from_unix(state: u8, flags: u32) -> Self {
+        if flags & UNIX_ACCEPTCON != 0 {
+            return SocketState::Listen;
+        }
+
+        match state {
+            0x01 => SocketState::Unconnected,
+            0x03 => SocketState::Connected,
+            _ => SocketState::Unknown,
+        }
+    }
+}
+
+/// A socket as it appears in one of the socket tables in `/proc/<pid>/net/`
+#[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Socket {
+    pub inode: u64,
+    pub protocol: Protocol,
+    /// `None` for Unix sockets
+    pub local_address: Option<SocketAddr>,
+    /// `None` for Unix sockets
+    pub remote_address: Option<SocketAddr>,
+    /// The path a Unix socket is bound to, abstract sockets start with `@`. `None` for other sockets.
+    pub path: Option<String>,
+    pub state: SocketState,
+    /// Bytes in the send queue, `None` for Unix sockets
+    pub send_queue: Option<u64>,
+    /// Bytes in the receive queue, `None` for Unix sockets
+    pub receive_queue: Option<u64>,
+}
+
+impl Socket {
+    /// Returns the port this socket is listening on, i.e. TCP sockets in the `Listen` state and bound but unconnected
+    /// UDP sockets
+    pub fn listening_port(&self) -> Option<ListeningPort> {
+        let port = self.local_address?.port();
+
+        let listening = (self.protocol.is_tcp() && self.state == SocketState::Listen)
+            || (self.protocol.is_udp() && self.state == SocketState::Unconnected);
+
+        (listening && port != 0).then_some(ListeningPort {
+            protocol: self.protocol,
+            port,
+        })
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub struct ListeningPort {
+    pub protocol: Protocol,
+    pub port: u16,
+}
+
+/// Returns the inode of the socket a file descriptor link target like `socket:[12345]` refers to
+pub fn socket_inode<S: AsRef<str>>(target: S) -> Option<u64> {
+    target
+        .as_ref()
+        .strip_prefix("socket:[")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// Reads the socket tables of `protocols` in `net_path` (e.g. `/proc/net` or `/proc/<pid>/net`) and returns their
+/// sockets by inode. Since the socket tables only contain the sockets of the network namespace of the process the
+/// `net_path` belongs to, sockets of processes in other network namespaces will be missing.
+pub fn sockets<P: AsRef<Path>>(net_path: P, protocols: &[Protocol]) -> HashMap<u64, Socket> {
+    let net_path = net_path.as_ref();
+
+    protocols
+        .iter()
+        .flat_map(|protocol| {
+            let Ok(table) = std::fs::read_to_string(net_path.join(protocol.table_name())) else {
+                return Vec::new();
+            };
+
+            match protocol {
+                Protocol::Unix => parse_unix_sockets(table),
+                protocol => parse_inet_sockets(table, *protocol),
+            }
+        })
+        // inode 0 means that the socket isn't associated with a file anymore (e.g. in TIME-WAIT)
+        .filter(|socket| socket.inode != 0)
+        .map(|socket| (socket.inode, socket))
+        .collect()
+}
+
+/// Parses a socket table like `/proc/net/tcp`, see man proc_pid_net(5)
+pub fn parse_inet_sockets<S: AsRef<str>>(table: S, protocol: Protocol) -> Vec<Socket> {
+    table
+        .as_ref()
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+
+            let local_address = parse_inet_address(fields.get(1)?)?;
+            let remote_address = parse_inet_address(fields.get(2)?)?;
+            let state = u8::from_str_radix(fields.get(3)?, 16).ok()?;
+            let (send_queue, receive_queue) = fields.get(4)?.split_once(':')?;
+            let inode = fields.get(9)?.parse().ok()?;
+
+            Some(Socket {
+                inode,
+                protocol,
+                local_address: Some(local_address),
+                remote_address: Some(remote_address),
+                path: None,
+                state: if protocol.is_tcp() {
+                    SocketState::from_tcp(state)
+                } else {
+                    SocketState::from_udp(state)
+                },
+                send_queue: u64::from_str_radix(send_queue, 16).ok(),
+                receive_queue: u64::from_str_radix(receive_queue, 16).ok(),
+            })
+        })
+        .collect()
+}
+
+/// Parses `/proc/net/unix`, see man proc_pid_net(5)
+pub fn parse_unix_sockets<S: AsRef<str>>(table: S) -> Vec<Socket> {
+    table
+        .as_ref()
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+
+            let flags = u32::from_str_radix(fields.nth(3)?, 16).ok()?;
+            let state = u8::from_str_radix(fields.nth(1)?, 16).ok()?;
+            let inode = fields.next()?.parse().ok()?;
+            let path = fields.collect::<Vec<_>>().join(" ");
+
+            Some(Socket {
+                inode,
+                protocol: Protocol::Unix,
+                local_address: None,
+                remote_address: None,
+                path: Some(path),
+                state: SocketState::from_unix(state, flags),
+                send_queue: None,
+                receive_queue: None,
+            })
+        })
+        .collect()
+}
+
+/// Parses an address like `0100007F:1F90`. The address consists of one (IPv4) or four (IPv6) 32-bit words in
+/// network byte order that are printed as if they were in host byte order, the port is in host byte order.
+fn parse_inet_address(address: &str) -> Option<SocketAddr> {
+    let (ip, port) = address.split_once(':')?;
+
+    let port = u16::from_str_radix(port, 16).ok()?;
+
+    let words = (0..ip.len())
+        .step_by(8)
+        .map(|i| {
+            ip.get(i..i + 8)
+                .and_then(|word| u32::from_str_radix(word, 16).ok())
+                .map(u32::to_ne_bytes)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let ip = match words.as_slice() {
+        [word] => IpAddr::V4(Ipv4Addr::from(*word)),
+        [_, _, _, _] => {
+            let mut octets = [0; 16];
+            for (chunk, word) in octets.chunks_exact_mut(4).zip(&words) {
+                chunk.copy_from_slice(word);
+            }
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return None,
+    };
+
+    Some(SocketAddr::new(ip, port))
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::SocketAddr;
+
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        parse_inet_sockets, parse_unix_sockets, socket_inode, ListeningPort, Protocol, Socket,
+        SocketState,
+    };
+
+    const TCP: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n   \
+                       0: 00000000:1F90 00000000:0000 0A 00000000:00000002 00:00000000 00000000  1000        0 45678 1 0000000000000000 100 0 0 10 0\n   \
+                       1: 0100007F:A2B4 0100007F:1F90 01 00000010:00000000 00:00000000 00000000  1000        0 45679 1 0000000000000000 20 4 30 10 -1\n";
+
+    const TCP6: &str = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode\n   \
+                        0: 00000000000000000000000001000000:0277 00000000000000000000000000000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 23456 1 0000000000000000 100 0 0 10 0\n";
+
+    const UNIX: &str = "Num       RefCount Protocol Flags    Type St Inode Path\n\
+                        0000000000000000: 00000002 00000000 00010000 0001 01 34567 /run/user/1000/bus\n\
+                        0000000000000000: 00000003 00000000 00000000 0001 03 34568\n";
+
+    #[test]
+    fn inet_sockets_parsing() {
+        let sockets = parse_inet_sockets(TCP, Protocol::Tcp);
+
+        assert_eq!(
+            sockets,
+            vec![
+                Socket {
+                    inode: 45678,
+                    protocol: Protocol::Tcp,
+                    local_address: Some("0.0.0.0:8080".parse::<SocketAddr>().unwrap()),
+                    remote_address: Some("0.0.0.0:0".parse::<SocketAddr>().unwrap()),
+                    path: None,
+                    state: SocketState::Listen,
+                    send_queue: Some(0),
+                    receive_queue: Some(2),
+                },
+                Socket {
+                    inode: 45679,
+                    protocol: Protocol::Tcp,
+                    local_address: Some("127.0.0.1:41652".parse::<SocketAddr>().unwrap()),
+                    remote_address: Some("127.0.0.1:8080".parse::<SocketAddr>().unwrap()),
+                    path: None,
+                    state: SocketState::Established,
+                    send_queue: Some(16),
+                    receive_queue: Some(0),
+                },
+            ]
+        );
+
+        assert_eq!(
+            sockets[0].listening_port(),
+            Some(ListeningPort {
+                protocol: Protocol::Tcp,
+                port: 8080
+            })
+        );
+        assert_eq!(sockets[1].listening_port(), None);
+    }
+
+    #[test]
+    fn inet6_sockets_parsing() {
+        let sockets = parse_inet_sockets(TCP6, Protocol::Tcp6);
+
+        assert_eq!(sockets.len(), 1);
+        assert_eq!(
+            sockets[0].local_address,
+            Some("[::1]:631".parse::<SocketAddr>().unwrap())
+        );
+    }
+
+    #[test]
+    fn unix_sockets_parsing() {
+        let sockets = parse_unix_sockets(UNIX);
+
+        assert_eq!(
+            sockets,
+            vec![
+                Socket {
+                    inode: 34567,
+                    protocol: Protocol::Unix,
+                    local_address: None,
+                    remote_address: None,
+                    path: Some(String::from("/run/user/1000/bus")),
+                    state: SocketState::Listen,
+                    send_queue: None,
+                    receive_queue: None,
+                },
+                Socket {
+                    inode: 34568,
+                    protocol: Protocol::Unix,
+                    local_address: None,
+                    remote_address: None,
+                    path: Some(String::new()),
+                    state: SocketState::Connected,
+                    send_queue: None,
+                    receive_queue: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn socket_inode_parsing() {
+        assert_eq!(socket_inode("socket:[12345]"), Some(12345));
+        assert_eq!(socket_inode("pipe:[12345]"), None);
+        assert_eq!(socket_inode("/dev/null"), None);
+    }
+}
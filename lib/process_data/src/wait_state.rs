@@ -0,0 +1,327 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Where in the kernel a process is currently waiting, see man proc_pid_wchan(5), proc_pid_syscall(5) and
+/// proc_pid_stack(5)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WaitState {
+    /// The kernel function the process is sleeping in, `None` if it isn't sleeping or we're not allowed to see it
+    pub wchan: Option<String>,
+    /// `None` if we're not allowed to read `/proc/<pid>/syscall`
+    pub syscall: Option<SyscallState>,
+    /// The kernel stack of the main thread with one function per frame (innermost first), `None` if we're not allowed
+    /// to read it, which requires CAP_SYS_ADMIN
+    pub stack: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SyscallState {
+    Running,
+    /// Blocked, but not inside of a system call (e.g. because of a page fault)
+    NoSyscall,
+    Syscall {
+        number: libc::c_long,
+        arguments: [u64; 6],
+    },
+}
+
+impl WaitState {
+    pub fn read<P: AsRef<Path>>(proc_path: P) -> Self {
+        let proc_path = proc_path.as_ref();
+
+        let read = |name: &str| std::fs::read_to_string(proc_path.join(name)).ok();
+
+        Self {
+            wchan: read("wchan").and_then(parse_wchan),
+            syscall: read("syscall").and_then(parse_syscall),
+            stack: read("stack").map(parse_stack),
+        }
+    }
+
+    /// Whether some of the information couldn't be gathered because of missing permissions
+    pub fn is_incomplete(&self) -> bool {
+        self.syscall.is_none() || self.stack.is_none()
+    }
+
+    /// Fills the information that couldn't be gathered with the one from `other`
+    pub fn complete_with(&mut self, other: WaitState) {
+        if self.wchan.is_none() {
+            self.wchan = other.wchan;
+        }
+
+        if self.syscall.is_none() {
+            self.syscall = other.syscall;
+        }
+
+        if self.stack.is_none() {
+            self.stack = other.stack;
+        }
+    }
+
+    /// Returns the lines `resources-kernel-stack` prints for this `WaitState` in batch mode, every line starts with a
+    /// tab so that they can't be confused with the `<PID> <RESULT CODE>` lines
+    pub fn batch_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        if let Some(wchan) = &self.wchan {
+            lines.push(format!("\twchan {wchan}"));
+        }
+
+        match self.syscall {
+            Some(SyscallState::Running) => lines.push(String::from("\tsyscall running")),
+            Some(SyscallState::NoSyscall) => lines.push(String::from("\tsyscall -1")),
+            Some(SyscallState::Syscall { number, arguments }) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| format!("{argument:#x}"))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                lines.push(format!("\tsyscall {number} {arguments}"));
+            }
+            None => {}
+        }
+
+        if let Some(stack) = &self.stack {
+            lines.push(String::from("\tstack"));
+            lines.extend(stack.iter().map(|frame| format!("\tframe {frame}")));
+        }
+
+        lines
+    }
+}
+
+/// Parses the output of `resources-kernel-stack` in batch mode, which consists of a `<PID> <RESULT CODE>` line per
+/// entry, followed by the `WaitState::batch_lines()` of that entry
+pub fn parse_batch_output<S: AsRef<str>>(output: S) -> Vec<(libc::pid_t, i32, WaitState)> {
+    let mut results: Vec<(libc::pid_t, i32, WaitState)> = Vec::new();
+
+    for line in output.as_ref().lines() {
+        let Some(line) = line.strip_prefix('\t') else {
+            if let Some((pid, code)) = crate::pidfd::parse_batch_output(line).first() {
+                results.push((*pid, *code, WaitState::default()));
+            }
+            continue;
+        };
+
+        let Some((_, _, wait_state)) = results.last_mut() else {
+            continue;
+        };
+
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+
+        match key {
+            "wchan" => wait_state.wchan = parse_wchan(value),
+            "syscall" => wait_state.syscall = parse_syscall(value),
+            "stack" => wait_state.stack = Some(Vec::new()),
+            "frame" => {
+                if let Some(stack) = wait_state.stack.as_mut() {
+                    stack.push(value.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    results
+}
+
+/// Returns the name of the system call with the number `number` for the more common blocking system calls
+pub fn syscall_name(number: libc::c_long) -> Option<&'static str> {
+    macro_rules! syscall_names {
+        ($($name:ident),* $(,)?) => {
+            $(
+                if number == libc::$name {
+                    return Some(stringify!($name).trim_start_matches("SYS_"));
+                }
+            )*
+        };
+    }
+
+    syscall_names!(
+        SYS_read,
+        SYS_write,
+        SYS_readv,
+        SYS_writev,
+        SYS_pread64,
+        SYS_pwrite64,
+        SYS_openat,
+        SYS_close,
+        SYS_ioctl,
+        SYS_fcntl,
+        SYS_flock,
+        SYS_fsync,
+        SYS_fdatasync,
+        SYS_msync,
+        SYS_ppoll,
+        SYS_pselect6,
+        SYS_epoll_pwait,
+        SYS_futex,
+        SYS_nanosleep,
+        SYS_clock_nanosleep,
+        SYS_wait4,
+        SYS_waitid,
+        SYS_accept4,
+        SYS_connect,
+        SYS_recvfrom,
+        SYS_sendto,
+        SYS_recvmsg,
+        SYS_sendmsg,
+        SYS_rt_sigsuspend,
+        SYS_rt_sigtimedwait,
+        SYS_io_uring_enter,
+    );
+
+    // newer architectures like aarch64 only have the variants above
+    #[cfg(target_arch = "x86_64")]
+    syscall_names!(
+        SYS_open,
+        SYS_poll,
+        SYS_select,
+        SYS_epoll_wait,
+        SYS_accept,
+        SYS_pause,
+    );
+
+    None
+}
+
+/// Parses the contents of `/proc/<pid>/wchan`, which is `0` if the process isn't sleeping or we're not allowed to see
+/// where
+fn parse_wchan<S: AsRef<str>>(wchan: S) -> Option<String> {
+    let wchan = wchan.as_ref().trim();
+    (!wchan.is_empty() && wchan != "0").then(|| wchan.to_string())
+}
+
+/// Parses the contents of `/proc/<pid>/syscall`, which is either `running`, `-1 <SP> <PC>` or
+/// `<NUMBER> <ARG1> … <ARG6> <SP> <PC>` with hexadecimal arguments
+fn parse_syscall<S: AsRef<str>>(syscall: S) -> Option<SyscallState> {
+    let mut fields = syscall.as_ref().split_whitespace();
+
+    let number = match fields.next()? {
+        "running" => return Some(SyscallState::Running),
+        number => number.parse::<libc::c_long>().ok()?,
+    };
+
+    if number < 0 {
+        return Some(SyscallState::NoSyscall);
+    }
+
+    let mut arguments = [0; 6];
+    for argument in &mut arguments {
+        let field = fields.next()?;
+        *argument = u64::from_str_radix(field.trim_start_matches("0x"), 16).ok()?;
+    }
+
+    Some(SyscallState::Syscall { number, arguments })
+}
+
+/// Parses the contents of `/proc/<pid>/stack`, whose lines look like `[<0>] do_select+0x5d0/0x8a0`
+fn parse_stack<S: AsRef<str>>(stack: S) -> Vec<String> {
+    stack
+        .as_ref()
+        .lines()
+        .map(|line| {
+            line.split_once("] ")
+                .map_or(line, |(_, function)| function)
+                .trim()
+                .to_string()
+        })
+        .filter(|function| !function.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        parse_batch_output, parse_stack, parse_syscall, parse_wchan, SyscallState, WaitState,
+    };
+
+    #[test]
+    fn wchan_parsing() {
+        assert_eq!(parse_wchan("do_select"), Some(String::from("do_select")));
+        assert_eq!(parse_wchan("0"), None);
+        assert_eq!(parse_wchan(""), None);
+    }
+
+    #[test]
+    fn syscall_parsing() {
+        assert_eq!(parse_syscall("running\n"), Some(SyscallState::Running));
+
+        assert_eq!(
+            parse_syscall("-1 0x7ffd2b5fd8e8 0x7f3a1d0a4b2d\n"),
+            Some(SyscallState::NoSyscall)
+        );
+
+        assert_eq!(
+            parse_syscall(
+                "202 0x7f3a1c000990 0x80 0x0 0x0 0x0 0xffffffff 0x7ffd2b5fd8e8 0x7f3a1d0a4b2d\n"
+            ),
+            Some(SyscallState::Syscall {
+                number: 202,
+                arguments: [0x7f3a1c000990, 0x80, 0, 0, 0, 0xffffffff]
+            })
+        );
+
+        assert_eq!(parse_syscall("7 0x1"), None);
+        assert_eq!(parse_syscall(""), None);
+    }
+
+    #[test]
+    fn stack_parsing() {
+        let stack = "[<0>] do_select+0x5d0/0x8a0\n\
+                     [<0>] core_sys_select+0x1d6/0x3a0\n\
+                     [<0>] do_syscall_64+0x82/0x160\n\
+                     [<0>] entry_SYSCALL_64_after_hwframe+0x76/0x7e\n";
+
+        assert_eq!(
+            parse_stack(stack),
+            vec![
+                "do_select+0x5d0/0x8a0",
+                "core_sys_select+0x1d6/0x3a0",
+                "do_syscall_64+0x82/0x160",
+                "entry_SYSCALL_64_after_hwframe+0x76/0x7e"
+            ]
+        );
+
+        assert_eq!(parse_stack(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn batch_output_roundtrip() {
+        let wait_state = WaitState {
+            wchan: Some(String::from("do_select")),
+            syscall: Some(SyscallState::Syscall {
+                number: 7,
+                arguments: [0x7ffd2b5fd8e8, 1, 0xffffffff, 0, 0, 0],
+            }),
+            stack: Some(vec![
+                String::from("do_select+0x5d0/0x8a0"),
+                String::from("do_syscall_64+0x82/0x160"),
+            ]),
+        };
+
+        let empty_stack = WaitState {
+            wchan: None,
+            syscall: Some(SyscallState::Running),
+            stack: Some(Vec::new()),
+        };
+
+        let mut output = vec![String::from("1234 0")];
+        output.extend(wait_state.batch_lines());
+        output.push(String::from("5678 0"));
+        output.extend(empty_stack.batch_lines());
+        output.push(String::from("42 3"));
+
+        assert_eq!(
+            parse_batch_output(output.join("\n")),
+            vec![
+                (1234, 0, wait_state),
+                (5678, 0, empty_stack),
+                (42, 3, WaitState::default())
+            ]
+        );
+    }
+}
@@ -195,6 +195,17 @@ impl Application {
         ));
         self.add_action(&action_information_app_process);
 
+        // Send Signal to Processes
+        let action_send_signal = gio::SimpleAction::new("send-signal", None);
+        action_send_signal.connect_activate(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_, _| {
+                this.main_window().shortcut_send_signal();
+            }
+        ));
+        self.add_action(&action_send_signal);
+
         // Show Process Options
         let action_process_options = gio::SimpleAction::new("process-options", None);
         action_process_options.connect_activate(clone!(
@@ -218,6 +229,7 @@ impl Application {
         self.set_accels_for_action("app.continue-app-process", &["<Control>N"]);
         self.set_accels_for_action("app.information-app-process", &["<Control>I"]);
         self.set_accels_for_action("app.process-options", &["<Control>O"]);
+        self.set_accels_for_action("app.send-signal", &["<Control>S"]);
     }
 
     fn setup_css(&self) {
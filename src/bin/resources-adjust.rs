@@ -4,48 +4,234 @@ use nix::{
     sched::{sched_setaffinity, CpuSet},
     unistd::Pid,
 };
+use process_data::{
+    pidfd::{parse_pid_arg, Pidfd},
+    IoPriority, IoPriorityClass,
+};
 
+/// Usage: `resources-adjust <TARGET> <NICE> <AFFINITY MASK> [<IO CLASS> <IO LEVEL> [<POLICY> <RT PRIORITY> [<OOM SCORE ADJ>]]]`
+///
+/// or: `resources-adjust --batch <TARGET>=<NICE>:<AFFINITY MASK>[:<IO CLASS>:<IO LEVEL>[:<POLICY>:<RT PRIORITY>[:<OOM SCORE ADJ>]]]...`
+///
+/// where `TARGET` is `<PID>[:<STARTTIME>][/<TID>[,<TID>...]]`. Without any `TID`s, the process and all of its
+/// threads are adjusted, otherwise only the given threads are. Every `TID` has to belong to the process, otherwise
+/// nothing is adjusted and `ESRCH` is returned.
+///
+/// `IO CLASS` and `IO LEVEL` are passed to ioprio_set(2), a class of 0 resets the I/O priority to the one derived from
+/// the niceness. `POLICY` is the raw value of a scheduling policy as used by sched_setscheduler(2) and `RT PRIORITY`
+/// must be between 1 and 99 for `SCHED_FIFO` and `SCHED_RR` and 0 for all other policies. `OOM SCORE ADJ` is written
+/// to `/proc/<PID>/oom_score_adj` and always applies to the whole process. Optional values that are empty are left
+/// unchanged.
+///
+/// If a `STARTTIME` (in clock ticks after boot) is given, the process will only be adjusted if its start time
+/// matches, otherwise `EXIT_STARTTIME_MISMATCH` is returned.
+///
+/// In batch mode, a line of the form `<PID> <RESULT CODE>` is printed to stdout for every entry in the order the
+/// entries were given in. The result codes are the same as the exit codes in single mode.
 fn main() {
-    if let Some(pid) = env::args().nth(1).and_then(|s| s.trim().parse().ok()) {
-        if let Some(nice) = env::args().nth(2).and_then(|s| s.trim().parse().ok()) {
-            if let Some(mask) = env::args().nth(3) {
-                let mut cpu_set = CpuSet::new();
+    let args: Vec<String> = env::args().skip(1).collect();
 
-                for (i, c) in mask.chars().enumerate() {
-                    if c == '1' {
-                        cpu_set.set(i).unwrap_or_default();
-                    }
-                }
+    if args.first().is_some_and(|arg| arg == "--batch") {
+        batch(&args[1..]);
+    }
 
-                adjust(pid, nice, &cpu_set);
+    if let Some((pid, starttime, tids)) = args.first().and_then(|arg| parse_target(arg)) {
+        if let Some(adjustment) = Adjustment::parse(&args[1..]) {
+            match adjust_process(pid, starttime, &tids, &adjustment) {
+                Ok(()) => std::process::exit(0),
+                Err(code) => std::process::exit(code),
+            }
+        }
+    }
+    std::process::exit(255);
+}
 
-                // find tasks that belong to this process
-                let tasks_path = PathBuf::from("/proc/").join(pid.to_string()).join("task");
-                for entry in std::fs::read_dir(tasks_path).unwrap().flatten() {
-                    let thread_id = entry.file_name().to_string_lossy().parse().unwrap();
+fn batch(entries: &[String]) -> ! {
+    if entries.is_empty() {
+        std::process::exit(255);
+    }
+
+    for entry in entries {
+        let parsed = entry
+            .split_once('=')
+            .and_then(|(target, arguments)| Some((parse_target(target)?, arguments)));
+
+        let (pid, code) = if let Some(((pid, starttime, tids), arguments)) = parsed {
+            let arguments: Vec<&str> = arguments.split(':').collect();
+            let code = Adjustment::parse(&arguments).map_or(255, |adjustment| {
+                adjust_process(pid, starttime, &tids, &adjustment).map_or_else(|code| code, |()| 0)
+            });
+            (pid, code)
+        } else {
+            (0, 255)
+        };
 
-                    adjust(thread_id, nice, &cpu_set);
-                }
+        println!("{pid} {code}");
+    }
+
+    std::process::exit(0);
+}
+
+/// Parses a target of the form `<PID>[:<STARTTIME>][/<TID>[,<TID>...]]`
+fn parse_target(arg: &str) -> Option<(libc::pid_t, Option<u64>, Vec<libc::pid_t>)> {
+    let (pid_arg, tids) = match arg.split_once('/') {
+        Some((pid_arg, tids)) => (
+            pid_arg,
+            tids.split(',')
+                .map(|tid| tid.trim().parse().ok())
+                .collect::<Option<Vec<_>>>()?,
+        ),
+        None => (arg, Vec::new()),
+    };
+
+    let (pid, starttime) = parse_pid_arg(pid_arg)?;
+
+    Some((pid, starttime, tids))
+}
+
+struct Adjustment {
+    nice: i32,
+    cpu_set: CpuSet,
+    io_priority: Option<IoPriority>,
+    scheduling: Option<(i32, i32)>,
+    oom_score_adj: Option<i32>,
+}
+
+impl Adjustment {
+    fn parse<S: AsRef<str>>(args: &[S]) -> Option<Self> {
+        let arg = |i: usize| {
+            args.get(i)
+                .map(|arg| arg.as_ref().trim())
+                .filter(|arg| !arg.is_empty())
+        };
+
+        let nice = arg(0)?.parse().ok()?;
+
+        let mut cpu_set = CpuSet::new();
+        for (i, c) in arg(1)?.chars().enumerate() {
+            if c == '1' {
+                cpu_set.set(i).unwrap_or_default();
+            }
+        }
 
-                std::process::exit(0)
+        let io_priority = match (arg(2), arg(3)) {
+            (Some(class), Some(level)) => {
+                let class = IoPriorityClass::from_raw(class.parse().ok()?)?;
+                let level: u8 = level.parse().ok().filter(|level| *level <= 7)?;
+                Some(IoPriority { class, level })
             }
+            (None, None) => None,
+            _ => return None,
+        };
+
+        let scheduling = match (arg(4), arg(5)) {
+            (Some(policy), Some(priority)) => Some((policy.parse().ok()?, priority.parse().ok()?)),
+            (None, None) => None,
+            _ => return None,
+        };
+
+        let oom_score_adj = match arg(6) {
+            Some(oom_score_adj) => Some(
+                oom_score_adj
+                    .parse()
+                    .ok()
+                    .filter(|oom_score_adj| (-1000..=1000).contains(oom_score_adj))?,
+            ),
+            None => None,
+        };
+
+        Some(Self {
+            nice,
+            cpu_set,
+            io_priority,
+            scheduling,
+            oom_score_adj,
+        })
+    }
+}
+
+fn adjust_process(
+    pid: i32,
+    starttime: Option<u64>,
+    tids: &[i32],
+    adjustment: &Adjustment,
+) -> Result<(), i32> {
+    // there are no pidfd-based variants of setpriority(), sched_setaffinity(), sched_setscheduler() and
+    // ioprio_set(), so verifying the process right before adjusting it is the best we can do
+    Pidfd::open_maybe_verified(pid, starttime).map_err(|error| error.exit_code())?;
+
+    let process_path = PathBuf::from("/proc/").join(pid.to_string());
+    let tasks_path = process_path.join("task");
+
+    // make sure that we don't adjust threads of other processes
+    if tids
+        .iter()
+        .any(|tid| !tasks_path.join(tid.to_string()).exists())
+    {
+        return Err(libc::ESRCH);
+    }
+
+    if let Some(oom_score_adj) = adjustment.oom_score_adj {
+        std::fs::write(
+            process_path.join("oom_score_adj"),
+            oom_score_adj.to_string(),
+        )
+        .map_err(|err| err.raw_os_error().unwrap_or(libc::EIO))?;
+    }
+
+    if !tids.is_empty() {
+        for tid in tids {
+            adjust(*tid, adjustment)?;
         }
+
+        return Ok(());
     }
-    std::process::exit(255);
+
+    adjust(pid, adjustment)?;
+
+    // find tasks that belong to this process
+    for entry in std::fs::read_dir(tasks_path)
+        .map_err(|err| err.raw_os_error().unwrap_or(libc::ESRCH))?
+        .flatten()
+    {
+        if let Ok(thread_id) = entry.file_name().to_string_lossy().parse() {
+            adjust(thread_id, adjustment)?;
+        }
+    }
+
+    Ok(())
 }
 
-fn adjust(id: i32, nice: i32, cpu_set: &CpuSet) {
-    unsafe {
-        libc::setpriority(libc::PRIO_PROCESS, id as u32, nice);
-    };
+fn adjust(id: i32, adjustment: &Adjustment) -> Result<(), i32> {
+    // the scheduling policy is set first since it's the most likely to be rejected (realtime policies require
+    // CAP_SYS_NICE), so that a rejected adjustment doesn't change the niceness. sched_setscheduler() leaves the
+    // niceness untouched, it only takes effect under SCHED_OTHER and SCHED_BATCH.
+    if let Some((policy, priority)) = adjustment.scheduling {
+        let param = libc::sched_param {
+            sched_priority: priority,
+        };
+        check(unsafe { libc::sched_setscheduler(id, policy, &param) })?;
+    }
 
-    let error = std::io::Error::last_os_error()
-        .raw_os_error()
-        .unwrap_or_default();
+    // errno is only meaningful if setpriority() actually failed, which matters in batch mode where it might still be
+    // set from an earlier entry
+    check(unsafe { libc::setpriority(libc::PRIO_PROCESS, id as u32, adjustment.nice) })?;
 
-    if error != 0 {
-        std::process::exit(error)
+    if let Some(io_priority) = adjustment.io_priority {
+        io_priority.apply(id)?;
     }
 
-    let _ = sched_setaffinity(Pid::from_raw(id), cpu_set);
+    let _ = sched_setaffinity(Pid::from_raw(id), &adjustment.cpu_set);
+
+    Ok(())
+}
+
+fn check(result: i32) -> Result<(), i32> {
+    if result == -1 {
+        Err(std::io::Error::last_os_error()
+            .raw_os_error()
+            .unwrap_or_default())
+    } else {
+        Ok(())
+    }
 }
@@ -0,0 +1,141 @@
+use std::{
+    env,
+    io::{BufRead, Write},
+    process::Command,
+};
+
+/// Helper binaries that may be run through this helper
+const ALLOWED_HELPERS: &[&str] = &[
+    "resources-kill",
+    "resources-adjust",
+    "resources-limit",
+    "resources-kernel-stack",
+];
+
+/// Usage: `resources-helper`
+///
+/// Long-running helper that is meant to be started through pkexec once per session so that the user only has to
+/// authenticate once. After starting, it prints `ready` and then reads requests of the form `<HELPER> <ENTRY>...`
+/// line by line from stdin, where `HELPER` is one of `ALLOWED_HELPERS`. Every request is executed by running the
+/// corresponding helper binary in batch mode, its output is written to stdout followed by an empty line.
+///
+/// The helper exits once stdin has been closed, i.e. when Resources exits.
+fn main() {
+    let Some(helper_dir) = env::current_exe()
+        .ok()
+        .and_then(|path| path.parent().map(ToOwned::to_owned))
+    else {
+        std::process::exit(255)
+    };
+
+    let result = serve(
+        std::io::stdin().lock(),
+        &mut std::io::stdout().lock(),
+        |helper, entries| {
+            Command::new(helper_dir.join(helper))
+                .arg("--batch")
+                .args(entries)
+                .output()
+                .map(|output| output.stdout)
+                .unwrap_or_default()
+        },
+    );
+
+    std::process::exit(result.map_or(255, |()| 0));
+}
+
+/// Returns the helper and the entries of the request `line` if the helper is one of `ALLOWED_HELPERS`
+fn parse_request(line: &str) -> Option<(&str, Vec<&str>)> {
+    let mut words = line.split_whitespace();
+
+    let helper = words
+        .next()
+        .filter(|helper| ALLOWED_HELPERS.contains(helper))?;
+
+    Some((helper, words.collect()))
+}
+
+/// Answers every request read from `input` with the output `run` returns for it, followed by an empty line that
+/// terminates the response. Requests for helpers that aren't allowed are answered with an empty response. Empty lines
+/// are removed from the output so that they can't terminate a response early.
+fn serve<R, W, F>(input: R, output: &mut W, mut run: F) -> std::io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+    F: FnMut(&str, &[&str]) -> Vec<u8>,
+{
+    writeln!(output, "ready")?;
+    output.flush()?;
+
+    for line in input.lines() {
+        let line = line?;
+
+        if let Some((helper, entries)) = parse_request(&line) {
+            for response_line in run(helper, &entries).split(|byte| *byte == b'\n') {
+                if !response_line.iter().all(u8::is_ascii_whitespace) {
+                    output.write_all(response_line)?;
+                    writeln!(output)?;
+                }
+            }
+        }
+
+        writeln!(output)?;
+        output.flush()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::{parse_request, serve};
+
+    #[test]
+    fn request_parsing() {
+        assert_eq!(
+            Some(("resources-kill", vec!["1234:5678=TERM", "4321=9"])),
+            parse_request("resources-kill 1234:5678=TERM  4321=9")
+        );
+        assert_eq!(
+            Some(("resources-adjust", vec![])),
+            parse_request("resources-adjust")
+        );
+        assert_eq!(None, parse_request("/usr/bin/rm -rf /"));
+        assert_eq!(None, parse_request("../resources-kill 1234=9"));
+        assert_eq!(None, parse_request(""));
+    }
+
+    #[test]
+    fn serving_requests() {
+        let input =
+            "resources-kill 1234=TERM 4321=KILL\nsh -c reboot\nresources-kernel-stack 1234\n";
+
+        let mut requests = Vec::new();
+        let mut output = Vec::new();
+
+        serve(input.as_bytes(), &mut output, |helper, entries| {
+            requests.push(format!("{helper} {}", entries.join(" ")));
+
+            match helper {
+                "resources-kill" => b"1234 0\n4321 1\n".to_vec(),
+                // a misbehaving helper must not be able to end the response early
+                _ => b"1234 0\n\n\twchan 0\n".to_vec(),
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                "resources-kill 1234=TERM 4321=KILL",
+                "resources-kernel-stack 1234"
+            ],
+            requests
+        );
+        assert_eq!(
+            "ready\n1234 0\n4321 1\n\n\n1234 0\n\twchan 0\n\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
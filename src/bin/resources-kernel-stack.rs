@@ -0,0 +1,75 @@
+use std::{env, path::PathBuf};
+
+use process_data::{
+    pidfd::{parse_pid_arg, Pidfd},
+    wait_state::WaitState,
+};
+
+/// Usage: `resources-kernel-stack <PID>[:<STARTTIME>]`
+///
+/// or: `resources-kernel-stack --batch <PID>[:<STARTTIME>]...`
+///
+/// Reads where in the kernel a process is waiting, i.e. its wait channel, its current system call and its kernel
+/// stack. Reading the latter requires CAP_SYS_ADMIN, which is why this is a separate helper that can be run through
+/// pkexec. The lines of `WaitState::batch_lines()` are printed to stdout.
+///
+/// If a `STARTTIME` (in clock ticks after boot) is given, the process is only inspected if its start time matches,
+/// otherwise `EXIT_STARTTIME_MISMATCH` is returned.
+///
+/// In batch mode, a line of the form `<PID> <RESULT CODE>` is printed to stdout for every entry in the order the
+/// entries were given in, followed by the lines of `WaitState::batch_lines()` if the result code is 0. The result
+/// codes are the same as the exit codes in single mode.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.first().is_some_and(|arg| arg == "--batch") {
+        batch(&args[1..]);
+    }
+
+    if let Some((pid, starttime)) = args.first().and_then(parse_pid_arg) {
+        match wait_state(pid, starttime) {
+            Ok(wait_state) => {
+                for line in wait_state.batch_lines() {
+                    println!("{line}");
+                }
+                std::process::exit(0);
+            }
+            Err(code) => std::process::exit(code),
+        }
+    }
+    std::process::exit(255);
+}
+
+fn batch(entries: &[String]) -> ! {
+    if entries.is_empty() {
+        std::process::exit(255);
+    }
+
+    for entry in entries {
+        let Some((pid, starttime)) = parse_pid_arg(entry) else {
+            println!("0 255");
+            continue;
+        };
+
+        match wait_state(pid, starttime) {
+            Ok(wait_state) => {
+                println!("{pid} 0");
+                for line in wait_state.batch_lines() {
+                    println!("{line}");
+                }
+            }
+            Err(code) => println!("{pid} {code}"),
+        }
+    }
+
+    std::process::exit(0);
+}
+
+fn wait_state(pid: i32, starttime: Option<u64>) -> Result<WaitState, i32> {
+    // procfs can't be read through a pidfd, so verifying the process right before reading is the best we can do
+    Pidfd::open_maybe_verified(pid, starttime).map_err(|error| error.exit_code())?;
+
+    Ok(WaitState::read(
+        PathBuf::from("/proc").join(pid.to_string()),
+    ))
+}
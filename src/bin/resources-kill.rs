@@ -1,26 +1,99 @@
-use std::env;
+use std::{env, str::FromStr};
 
-use nix::{sys::signal, unistd::Pid};
+use nix::{errno::Errno, sys::signal::Signal};
+use process_data::pidfd::{parse_pid_arg, Pidfd, PidfdError};
 
+/// Usage: `resources-kill <PID>[:<STARTTIME>] <SIGNAL>`
+///
+/// If a `STARTTIME` (in clock ticks after boot) is given, the process will only be signaled if its start time
+/// matches, otherwise the PID has been reused by another process in the meantime and `EXIT_STARTTIME_MISMATCH` is
+/// returned. Signals are sent using pidfds to avoid PID reuse races.
+///
+/// `SIGNAL` can either be a signal number or a signal name with or without the `SIG` prefix,
+/// e.g. `15`, `TERM`, `SIGTERM` or `RTMIN+2`. A `SIGNAL` of `0` doesn't send anything but only checks whether the
+/// process could be signaled.
+///
+/// or: `resources-kill --batch <PID>[:<STARTTIME>]=<SIGNAL>...`
+///
+/// In batch mode, the processes are signaled in the order they were given in and every entry can have its own
+/// signal. A line of the form `<PID> <RESULT CODE>` is printed to stdout for every entry, the result codes are the
+/// same as the exit codes in single mode.
 fn main() {
-    if let Some(pid) = env::args().nth(1).and_then(|s| s.trim().parse().ok()) {
+    if env::args().nth(1).is_some_and(|arg| arg == "--batch") {
+        batch(&env::args().skip(2).collect::<Vec<_>>());
+    }
+
+    if let Some((pid, starttime)) = env::args().nth(1).and_then(parse_pid_arg) {
         if let Some(arg) = env::args().nth(2) {
-            let signal = match arg.as_str() {
-                "STOP" => signal::Signal::SIGSTOP,
-                "CONT" => signal::Signal::SIGCONT,
-                "TERM" => signal::Signal::SIGTERM,
-                "KILL" => signal::Signal::SIGKILL,
-                _ => std::process::exit(254),
+            let Some(signal) = parse_signal(&arg) else {
+                std::process::exit(254)
             };
-            let result = signal::kill(Pid::from_raw(pid), Some(signal));
-            if let Err(errno) = result {
-                match errno {
-                    nix::errno::Errno::UnknownErrno => std::process::exit(253),
-                    _ => std::process::exit(errno as i32),
-                };
+
+            match signal_process(pid, starttime, signal) {
+                Ok(()) => std::process::exit(0),
+                Err(error) => std::process::exit(exit_code(error)),
             }
-            std::process::exit(0);
         }
     }
     std::process::exit(255);
 }
+
+fn batch(entries: &[String]) -> ! {
+    if entries.is_empty() {
+        std::process::exit(255);
+    }
+
+    for entry in entries {
+        let parsed = entry
+            .split_once('=')
+            .and_then(|(pid_arg, signal)| Some((parse_pid_arg(pid_arg)?, signal)));
+
+        let (pid, code) = if let Some(((pid, starttime), signal)) = parsed {
+            let code = parse_signal(signal).map_or(254, |signal| {
+                signal_process(pid, starttime, signal).map_or_else(exit_code, |()| 0)
+            });
+            (pid, code)
+        } else {
+            (0, 255)
+        };
+
+        println!("{pid} {code}");
+    }
+
+    std::process::exit(0);
+}
+
+fn signal_process(pid: i32, starttime: Option<u64>, signal: i32) -> Result<(), PidfdError> {
+    Pidfd::open_maybe_verified(pid, starttime).and_then(|pidfd| pidfd.send_signal(signal))
+}
+
+fn exit_code(error: PidfdError) -> i32 {
+    match error {
+        PidfdError::Errno(errno) if errno == Errno::UnknownErrno as i32 => 253,
+        error => error.exit_code(),
+    }
+}
+
+fn parse_signal(arg: &str) -> Option<i32> {
+    let arg = arg.trim().to_ascii_uppercase();
+
+    let signal = if let Ok(number) = arg.parse::<i32>() {
+        number
+    } else {
+        let name = arg.strip_prefix("SIG").unwrap_or(&arg);
+
+        if let Some(offset) = name.strip_prefix("RTMIN+") {
+            libc::SIGRTMIN() + offset.parse::<i32>().ok()?
+        } else if let Some(offset) = name.strip_prefix("RTMAX-") {
+            libc::SIGRTMAX() - offset.parse::<i32>().ok()?
+        } else if name == "RTMIN" {
+            libc::SIGRTMIN()
+        } else if name == "RTMAX" {
+            libc::SIGRTMAX()
+        } else {
+            Signal::from_str(&format!("SIG{name}")).ok()? as i32
+        }
+    };
+
+    (0..=libc::SIGRTMAX()).contains(&signal).then_some(signal)
+}
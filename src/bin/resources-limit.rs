@@ -0,0 +1,109 @@
+use std::env;
+
+use process_data::pidfd::{parse_pid_arg, Pidfd};
+
+const RLIM64_INFINITY: u64 = u64::MAX;
+
+/// Usage: `resources-limit <PID>[:<STARTTIME>] <RESOURCE> <SOFT LIMIT> <HARD LIMIT>`
+///
+/// or: `resources-limit --batch <PID>[:<STARTTIME>]=<RESOURCE>:<SOFT LIMIT>:<HARD LIMIT>...`
+///
+/// Sets a resource limit of a process using prlimit(2). `RESOURCE` is the value of the corresponding `RLIMIT_*`
+/// constant, the limits are either numbers or `unlimited`.
+///
+/// If a `STARTTIME` (in clock ticks after boot) is given, the limit will only be set if the process' start time
+/// matches, otherwise `EXIT_STARTTIME_MISMATCH` is returned.
+///
+/// In batch mode, a line of the form `<PID> <RESULT CODE>` is printed to stdout for every entry in the order the
+/// entries were given in. The result codes are the same as the exit codes in single mode.
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.first().is_some_and(|arg| arg == "--batch") {
+        batch(&args[1..]);
+    }
+
+    if let Some((pid, starttime)) = args.first().and_then(parse_pid_arg) {
+        if let Some((resource, soft, hard)) = parse_limit(&args[1..]) {
+            match set_limit(pid, starttime, resource, soft, hard) {
+                Ok(()) => std::process::exit(0),
+                Err(code) => std::process::exit(code),
+            }
+        }
+    }
+    std::process::exit(255);
+}
+
+fn batch(entries: &[String]) -> ! {
+    if entries.is_empty() {
+        std::process::exit(255);
+    }
+
+    for entry in entries {
+        let parsed = entry
+            .split_once('=')
+            .and_then(|(pid_arg, arguments)| Some((parse_pid_arg(pid_arg)?, arguments)));
+
+        let (pid, code) = if let Some(((pid, starttime), arguments)) = parsed {
+            let arguments: Vec<&str> = arguments.split(':').collect();
+            let code = parse_limit(&arguments).map_or(255, |(resource, soft, hard)| {
+                set_limit(pid, starttime, resource, soft, hard).map_or_else(|code| code, |()| 0)
+            });
+            (pid, code)
+        } else {
+            (0, 255)
+        };
+
+        println!("{pid} {code}");
+    }
+
+    std::process::exit(0);
+}
+
+fn parse_limit<S: AsRef<str>>(args: &[S]) -> Option<(i32, u64, u64)> {
+    let parse_value = |value: &S| match value.as_ref().trim() {
+        "unlimited" => Some(RLIM64_INFINITY),
+        value => value.parse().ok(),
+    };
+
+    let resource = args.first()?.as_ref().trim().parse().ok()?;
+    let soft = parse_value(args.get(1)?)?;
+    let hard = parse_value(args.get(2)?)?;
+
+    (soft <= hard).then_some((resource, soft, hard))
+}
+
+fn set_limit(
+    pid: i32,
+    starttime: Option<u64>,
+    resource: i32,
+    soft: u64,
+    hard: u64,
+) -> Result<(), i32> {
+    // there is no pidfd-based variant of prlimit(), so verifying the process right before setting the limit is the
+    // best we can do
+    Pidfd::open_maybe_verified(pid, starttime).map_err(|error| error.exit_code())?;
+
+    let limit = libc::rlimit64 {
+        rlim_cur: soft,
+        rlim_max: hard,
+    };
+
+    let result = unsafe {
+        libc::syscall(
+            libc::SYS_prlimit64,
+            pid,
+            resource,
+            &limit,
+            std::ptr::null_mut::<libc::rlimit64>(),
+        )
+    };
+
+    if result == -1 {
+        Err(std::io::Error::last_os_error()
+            .raw_os_error()
+            .unwrap_or_default())
+    } else {
+        Ok(())
+    }
+}
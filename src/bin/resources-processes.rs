@@ -1,7 +1,8 @@
 use anyhow::Result;
-use process_data::ProcessData;
+use process_data::{details::ProcessDetails, ProcessData, ProcessDataOptions};
 use ron::ser::PrettyConfig;
-use std::io::{Read, Write};
+use serde::Serialize;
+use std::io::{BufRead, Write};
 
 use clap::Parser;
 
@@ -17,32 +18,50 @@ struct Args {
     ron: bool,
 }
 
+/// Every line on stdin is a request. `details <PID>` requests the details of a single process (or `None` if they
+/// couldn't be gathered), every other line requests the data of all processes with the `ProcessDataOptions` it
+/// contains (see `ProcessDataOptions::to_request()`), an empty line requests it without any of them.
 fn main() -> Result<()> {
     let args = Args::parse();
 
     if args.once {
-        output(args.ron)?;
+        output(
+            &ProcessData::all_process_data(ProcessDataOptions::all())?,
+            args.ron,
+        )?;
         return Ok(());
     }
 
+    let mut stdin = std::io::stdin().lock();
+
     loop {
-        let mut buffer = [0; 1];
+        let mut line = String::new();
 
-        std::io::stdin().read_exact(&mut buffer)?;
+        if stdin.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
 
-        output(args.ron)?;
+        if let Some(pid) = line.trim().strip_prefix("details ") {
+            let details = pid
+                .trim()
+                .parse()
+                .ok()
+                .and_then(|pid| ProcessDetails::try_from_pid(pid).ok());
+            output(&details, args.ron)?;
+        } else {
+            let options = ProcessDataOptions::from_request(&line);
+            output(&ProcessData::all_process_data(options)?, args.ron)?;
+        }
     }
 }
 
-fn output(ron: bool) -> Result<()> {
-    let data = ProcessData::all_process_data()?;
-
+fn output<T: Serialize>(data: &T, ron: bool) -> Result<()> {
     let encoded = if ron {
-        ron::ser::to_string_pretty(&data, PrettyConfig::default())?
+        ron::ser::to_string_pretty(data, PrettyConfig::default())?
             .as_bytes()
             .to_vec()
     } else {
-        rmp_serde::to_vec(&data)?
+        rmp_serde::to_vec(data)?
     };
 
     let len_byte_array = encoded.len().to_le_bytes();
@@ -51,8 +51,12 @@ pub struct Args {
     #[arg(short = 'p', long, default_value_t = false)]
     pub disable_process_monitoring: bool,
 
+    /// Disable systemd unit monitoring
+    #[arg(short = 'u', long, default_value_t = false)]
+    pub disable_services_monitoring: bool,
+
     /// Open tab specified by ID.
-    /// Valid IDs are: "applications", "processes", "cpu", "memory", "gpu-$PCI_SLOT$",
+    /// Valid IDs are: "applications", "processes", "containers", "services", "cpu", "memory", "gpu-$PCI_SLOT$",
     /// "drive-$MODEL_NAME_OR_DEVICE_NAME$", "network-$INTERFACE_NAME$",
     /// "battery-$MANUFACTURER$-$MODEL_NAME$-$DEVICE_NAME$"
     #[arg(short = 't', long)]
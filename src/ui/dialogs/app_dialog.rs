@@ -51,6 +51,18 @@ mod imp {
         pub processes_amount: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub containerized: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub limits_group: TemplateChild<adw::PreferencesGroup>,
+        #[template_child]
+        pub cpu_limit: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_high_limit: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub memory_max_limit: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub drive_read_limit: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub drive_write_limit: TemplateChild<adw::ActionRow>,
     }
 
     #[glib::object_subclass]
@@ -190,5 +202,41 @@ impl ResAppDialog {
 
         imp.processes_amount
             .set_subtitle(&app.running_processes().to_string());
+
+        if let Some(limits) = app.limits() {
+            imp.limits_group.set_visible(true);
+
+            imp.cpu_limit
+                .set_subtitle(&limits.cpu_quota_usec.map_or_else(
+                    || i18n("None"),
+                    |usec| format!("{:.0} %", usec as f64 / 10_000.0),
+                ));
+
+            imp.memory_high_limit
+                .set_subtitle(&limits.memory_high.map_or_else(
+                    || i18n("None"),
+                    |bytes| convert_storage(bytes as f64, false),
+                ));
+
+            imp.memory_max_limit
+                .set_subtitle(&limits.memory_max.map_or_else(
+                    || i18n("None"),
+                    |bytes| convert_storage(bytes as f64, false),
+                ));
+
+            imp.drive_read_limit
+                .set_subtitle(&limits.io_read_bandwidth.map_or_else(
+                    || i18n("None"),
+                    |bytes_per_sec| convert_speed(bytes_per_sec as f64, false),
+                ));
+
+            imp.drive_write_limit
+                .set_subtitle(&limits.io_write_bandwidth.map_or_else(
+                    || i18n("None"),
+                    |bytes_per_sec| convert_speed(bytes_per_sec as f64, false),
+                ));
+        } else {
+            imp.limits_group.set_visible(false);
+        }
     }
 }
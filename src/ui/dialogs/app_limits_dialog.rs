@@ -0,0 +1,317 @@
+use crate::{
+    config::PROFILE,
+    i18n::{i18n, i18n_f},
+    ui::{pages::applications::application_entry::ApplicationEntry, window::Action},
+    utils::{memory::MemoryData, systemd::CgroupLimits, units::convert_storage, NUM_CPUS},
+};
+use adw::{prelude::*, subclass::prelude::*, ToastOverlay};
+use async_channel::Sender;
+use gtk::glib::{self, clone, MainContext};
+
+const MIB: u64 = 1024 * 1024;
+
+/// Microseconds of CPU time per second that correspond to one percent of a processor core
+const USEC_PER_PERCENT: u64 = 10_000;
+
+// values of the rows of limits that haven't been set yet
+const DEFAULT_CPU_PERCENT: u64 = 100;
+const DEFAULT_MEMORY_MIB: u64 = 2048;
+const DEFAULT_BANDWIDTH_MIB: u64 = 50;
+
+/// Memory limits that haven't been set yet start at this multiple of the app's current memory usage, so that enabling
+/// them doesn't immediately slow down or kill the app
+const DEFAULT_MEMORY_USAGE_FACTOR: u64 = 2;
+
+mod imp {
+    use std::cell::Cell;
+
+    use super::*;
+
+    use gtk::CompositeTemplate;
+
+    #[derive(Debug, CompositeTemplate, Default)]
+    #[template(resource = "/net/nokyan/Resources/ui/dialogs/app_limits_dialog.ui")]
+    pub struct ResAppLimitsDialog {
+        #[template_child]
+        pub name: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub apply_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub cpu_row: TemplateChild<adw::ExpanderRow>,
+        #[template_child]
+        pub cpu_value_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub memory_high_row: TemplateChild<adw::ExpanderRow>,
+        #[template_child]
+        pub memory_high_value_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub memory_max_row: TemplateChild<adw::ExpanderRow>,
+        #[template_child]
+        pub memory_max_value_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub read_row: TemplateChild<adw::ExpanderRow>,
+        #[template_child]
+        pub read_value_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub write_row: TemplateChild<adw::ExpanderRow>,
+        #[template_child]
+        pub write_value_row: TemplateChild<adw::SpinRow>,
+
+        /// The limits as they were shown when the dialog was opened, only the ones that differ from them are applied
+        pub initial_limits: Cell<CgroupLimits>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResAppLimitsDialog {
+        const NAME: &'static str = "ResAppLimitsDialog";
+        type Type = super::ResAppLimitsDialog;
+        type ParentType = adw::Dialog;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResAppLimitsDialog {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+        }
+    }
+
+    impl WidgetImpl for ResAppLimitsDialog {}
+    impl WindowImpl for ResAppLimitsDialog {}
+    impl AdwDialogImpl for ResAppLimitsDialog {}
+}
+
+glib::wrapper! {
+    pub struct ResAppLimitsDialog(ObjectSubclass<imp::ResAppLimitsDialog>)
+        @extends gtk::Widget, adw::Dialog;
+}
+
+impl Default for ResAppLimitsDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResAppLimitsDialog {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    pub fn init(
+        &self,
+        app: &ApplicationEntry,
+        sender: Sender<Action>,
+        toast_overlay: &ToastOverlay,
+    ) {
+        self.setup_widgets(app);
+        self.setup_signals(app, sender, toast_overlay);
+    }
+
+    /// Enables `row` if there is a `limit` and shows it in `value_row`, otherwise `value_row` is set to `default`
+    fn show_limit(
+        row: &adw::ExpanderRow,
+        value_row: &adw::SpinRow,
+        limit: Option<u64>,
+        default: u64,
+    ) {
+        row.set_enable_expansion(limit.is_some());
+        value_row.set_value(limit.unwrap_or(default) as f64);
+    }
+
+    /// Returns the limits that are currently set in the dialog, those whose rows aren't enabled are `None`
+    fn get_limits(&self) -> CgroupLimits {
+        let imp = self.imp();
+
+        let limit = |row: &adw::ExpanderRow, value_row: &adw::SpinRow, factor: u64| {
+            row.enables_expansion()
+                .then(|| (value_row.value() as u64).saturating_mul(factor))
+        };
+
+        CgroupLimits {
+            cpu_quota_usec: limit(&imp.cpu_row, &imp.cpu_value_row, USEC_PER_PERCENT),
+            memory_high: limit(&imp.memory_high_row, &imp.memory_high_value_row, MIB),
+            memory_max: limit(&imp.memory_max_row, &imp.memory_max_value_row, MIB),
+            io_read_bandwidth: limit(&imp.read_row, &imp.read_value_row, MIB),
+            io_write_bandwidth: limit(&imp.write_row, &imp.write_value_row, MIB),
+        }
+    }
+
+    pub fn setup_widgets(&self, app: &ApplicationEntry) {
+        let imp = self.imp();
+
+        imp.name.set_label(&app.name());
+
+        imp.cpu_value_row
+            .adjustment()
+            .set_upper((*NUM_CPUS * 100) as f64);
+
+        let total_memory_mib = MemoryData::new().map_or(f64::MAX, |memory_data| {
+            (memory_data.total_mem as u64 / MIB) as f64
+        });
+        imp.memory_high_value_row
+            .adjustment()
+            .set_upper(total_memory_mib);
+        imp.memory_max_value_row
+            .adjustment()
+            .set_upper(total_memory_mib);
+
+        let memory_usage = app.units_memory_usage();
+
+        // a hard limit below the current usage would get the app's processes killed right away
+        if let Some(memory_usage) = memory_usage {
+            imp.memory_max_value_row
+                .adjustment()
+                .set_lower(memory_usage.div_ceil(MIB).max(1) as f64);
+            imp.memory_max_value_row.set_subtitle(&i18n_f(
+                "The app currently uses {}",
+                &[&convert_storage(memory_usage as f64, false)],
+            ));
+        }
+
+        let default_memory_mib = memory_usage.map_or(DEFAULT_MEMORY_MIB, |memory_usage| {
+            memory_usage
+                .div_ceil(MIB)
+                .saturating_mul(DEFAULT_MEMORY_USAGE_FACTOR)
+        });
+
+        let limits = app.limits().unwrap_or_default();
+
+        Self::show_limit(
+            &imp.cpu_row,
+            &imp.cpu_value_row,
+            limits.cpu_quota_usec.map(|usec| usec / USEC_PER_PERCENT),
+            DEFAULT_CPU_PERCENT,
+        );
+
+        Self::show_limit(
+            &imp.memory_high_row,
+            &imp.memory_high_value_row,
+            limits.memory_high.map(|bytes| bytes / MIB),
+            default_memory_mib,
+        );
+
+        Self::show_limit(
+            &imp.memory_max_row,
+            &imp.memory_max_value_row,
+            limits.memory_max.map(|bytes| bytes / MIB),
+            default_memory_mib,
+        );
+
+        Self::show_limit(
+            &imp.read_row,
+            &imp.read_value_row,
+            limits
+                .io_read_bandwidth
+                .map(|bytes_per_sec| bytes_per_sec / MIB),
+            DEFAULT_BANDWIDTH_MIB,
+        );
+
+        Self::show_limit(
+            &imp.write_row,
+            &imp.write_value_row,
+            limits
+                .io_write_bandwidth
+                .map(|bytes_per_sec| bytes_per_sec / MIB),
+            DEFAULT_BANDWIDTH_MIB,
+        );
+
+        // compare against the limits as they're shown since they've been rounded to whole units
+        imp.initial_limits.set(self.get_limits());
+
+        self.update_apply_button();
+    }
+
+    /// The soft memory limit has no effect if it's above the hard one, so applying such limits is not allowed
+    fn update_apply_button(&self) {
+        let imp = self.imp();
+
+        let limits = self.get_limits();
+        let valid = match (limits.memory_high, limits.memory_max) {
+            (Some(memory_high), Some(memory_max)) => memory_high <= memory_max,
+            _ => true,
+        };
+
+        imp.apply_button.set_sensitive(valid);
+
+        if valid {
+            imp.memory_high_value_row.remove_css_class("error");
+            imp.memory_high_value_row.set_subtitle("");
+        } else {
+            imp.memory_high_value_row.add_css_class("error");
+            imp.memory_high_value_row
+                .set_subtitle(&i18n("Must not be higher than the hard limit"));
+        }
+    }
+
+    pub fn setup_signals(
+        &self,
+        app: &ApplicationEntry,
+        sender: Sender<Action>,
+        toast_overlay: &ToastOverlay,
+    ) {
+        let imp = self.imp();
+
+        for row in [&imp.memory_high_row, &imp.memory_max_row] {
+            row.connect_enable_expansion_notify(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_| this.update_apply_button()
+            ));
+        }
+
+        for value_row in [&imp.memory_high_value_row, &imp.memory_max_value_row] {
+            value_row.connect_value_notify(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_| this.update_apply_button()
+            ));
+        }
+
+        imp.apply_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            #[weak]
+            app,
+            #[weak]
+            toast_overlay,
+            #[strong]
+            sender,
+            move |_| {
+                let Some(id) = app.id() else {
+                    return;
+                };
+
+                let main_context = MainContext::default();
+                main_context.spawn_local(clone!(
+                    #[weak]
+                    this,
+                    #[strong]
+                    sender,
+                    async move {
+                        let _ = sender
+                            .send(Action::LimitApp(
+                                id.to_string(),
+                                this.imp().initial_limits.get(),
+                                this.get_limits(),
+                                toast_overlay.clone(),
+                            ))
+                            .await;
+                    }
+                ));
+            }
+        ));
+    }
+}
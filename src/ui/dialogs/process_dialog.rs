@@ -1,18 +1,45 @@
-use adw::{prelude::*, subclass::prelude::*};
-use gtk::glib::{self, GString};
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use adw::{prelude::*, subclass::prelude::*, ResponseAppearance, ToastOverlay};
+use async_channel::Sender;
+use gtk::glib::{self, clone, closure, GString, MainContext, Value};
+use gtk::{gio, ColumnView, ColumnViewColumn, NumericSorter, SortType, StringSorter, Widget};
+use log::warn;
+use process_data::details::{OpenFile, ProcessDetails};
+use process_data::limits::ResourceLimit;
+use process_data::memory_maps::MemoryMapping;
+use process_data::sockets::Socket;
+use process_data::wait_state::WaitState;
 
 use crate::config::PROFILE;
-use crate::i18n::i18n;
+use crate::i18n::{i18n, i18n_f};
+use crate::ui::pages::processes::environment_variable_entry::EnvironmentVariableEntry;
+use crate::ui::pages::processes::memory_map_entry::{
+    memory_map_tree, sync_memory_map_store, MemoryMapEntry,
+};
+use crate::ui::pages::processes::open_file_entry::OpenFileEntry;
 use crate::ui::pages::processes::process_entry::ProcessEntry;
-use crate::utils::units::{convert_speed, convert_storage, format_time};
+use crate::ui::pages::processes::socket_entry::SocketEntry;
+use crate::ui::pages::processes::thread_entry::ThreadEntry;
+use crate::ui::pages::syscall_label;
+use crate::ui::window::Action;
+use crate::utils::process::Process;
+use crate::utils::settings::SETTINGS;
+use crate::utils::units::{convert_rate, convert_speed, convert_storage, format_time};
+use crate::utils::NUM_CPUS;
 
 mod imp {
+    use std::{
+        cell::{Cell, RefCell},
+        sync::OnceLock,
+    };
 
     use super::*;
 
     use gtk::CompositeTemplate;
 
-    #[derive(Debug, CompositeTemplate, Default)]
+    #[derive(Debug, CompositeTemplate)]
     #[template(resource = "/net/nokyan/Resources/ui/dialogs/process_dialog.ui")]
     pub struct ResProcessDialog {
         #[template_child]
@@ -20,6 +47,10 @@ mod imp {
         #[template_child]
         pub cpu_usage: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub cpu_wait: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub io_wait: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub memory_usage: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub swap_usage: TemplateChild<adw::ActionRow>,
@@ -32,6 +63,20 @@ mod imp {
         #[template_child]
         pub drive_write_total: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub logical_read_speed: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub logical_read_total: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub logical_write_speed: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub logical_write_total: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub read_syscall_rate: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub write_syscall_rate: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub cancelled_write_total: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub gpu_usage: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub vram_usage: TemplateChild<adw::ActionRow>,
@@ -52,11 +97,140 @@ mod imp {
         #[template_child]
         pub commandline: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub executable: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub working_directory: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub root_directory: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub user: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub cgroup: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub containerized: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub kernel_stack_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub wait_channel: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub syscall: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub kernel_stack: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub threads_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub memory_maps_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub open_files_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub connections_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub environment_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub environment_search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub environment_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub limits_group: TemplateChild<adw::PreferencesGroup>,
+
+        pub threads_store: RefCell<gio::ListStore>,
+        pub threads_column_view: RefCell<gtk::ColumnView>,
+
+        pub memory_maps_store: RefCell<gio::ListStore>,
+        pub memory_maps_column_view: RefCell<gtk::ColumnView>,
+
+        pub open_files_store: RefCell<gio::ListStore>,
+        pub open_files_column_view: RefCell<gtk::ColumnView>,
+
+        pub connections_store: RefCell<gio::ListStore>,
+        pub connections_column_view: RefCell<gtk::ColumnView>,
+
+        pub environment_store: RefCell<gio::ListStore>,
+        pub environment_column_view: RefCell<gtk::ColumnView>,
+
+        pub limit_rows: RefCell<Vec<(adw::ActionRow, ResourceLimit)>>,
+
+        pub process_pid: Cell<libc::pid_t>,
+        pub process_starttime: Cell<u64>,
+        /// Wait state of the latest details
+        pub wait_state: RefCell<WaitState>,
+        /// Wait state gathered privileged when the user requested it, completes the wait states of later details
+        pub privileged_wait_state: RefCell<Option<WaitState>>,
+        /// Timestamp of the details that are currently shown, so that older ones arriving late are ignored
+        pub details_timestamp: Cell<u64>,
+
+        pub sender: OnceLock<Sender<Action>>,
+        pub toast_overlay: RefCell<Option<ToastOverlay>>,
+    }
+
+    impl Default for ResProcessDialog {
+        fn default() -> Self {
+            Self {
+                name: Default::default(),
+                cpu_usage: Default::default(),
+                cpu_wait: Default::default(),
+                io_wait: Default::default(),
+                memory_usage: Default::default(),
+                swap_usage: Default::default(),
+                drive_read_speed: Default::default(),
+                drive_read_total: Default::default(),
+                drive_write_speed: Default::default(),
+                drive_write_total: Default::default(),
+                logical_read_speed: Default::default(),
+                logical_read_total: Default::default(),
+                logical_write_speed: Default::default(),
+                logical_write_total: Default::default(),
+                read_syscall_rate: Default::default(),
+                write_syscall_rate: Default::default(),
+                cancelled_write_total: Default::default(),
+                gpu_usage: Default::default(),
+                vram_usage: Default::default(),
+                encoder_usage: Default::default(),
+                decoder_usage: Default::default(),
+                total_cpu_time: Default::default(),
+                user_cpu_time: Default::default(),
+                system_cpu_time: Default::default(),
+                pid: Default::default(),
+                running_since: Default::default(),
+                commandline: Default::default(),
+                executable: Default::default(),
+                working_directory: Default::default(),
+                root_directory: Default::default(),
+                user: Default::default(),
+                cgroup: Default::default(),
+                containerized: Default::default(),
+                kernel_stack_button: Default::default(),
+                wait_channel: Default::default(),
+                syscall: Default::default(),
+                kernel_stack: Default::default(),
+                threads_scrolled_window: Default::default(),
+                memory_maps_scrolled_window: Default::default(),
+                open_files_scrolled_window: Default::default(),
+                connections_scrolled_window: Default::default(),
+                environment_stack: Default::default(),
+                environment_search_entry: Default::default(),
+                environment_scrolled_window: Default::default(),
+                limits_group: Default::default(),
+                threads_store: gio::ListStore::new::<ThreadEntry>().into(),
+                threads_column_view: Default::default(),
+                memory_maps_store: gio::ListStore::new::<MemoryMapEntry>().into(),
+                memory_maps_column_view: Default::default(),
+                open_files_store: gio::ListStore::new::<OpenFileEntry>().into(),
+                open_files_column_view: Default::default(),
+                connections_store: gio::ListStore::new::<SocketEntry>().into(),
+                connections_column_view: Default::default(),
+                environment_store: gio::ListStore::new::<EnvironmentVariableEntry>().into(),
+                environment_column_view: Default::default(),
+                limit_rows: Default::default(),
+                process_pid: Default::default(),
+                process_starttime: Default::default(),
+                wait_state: Default::default(),
+                privileged_wait_state: Default::default(),
+                details_timestamp: Default::default(),
+                sender: Default::default(),
+                toast_overlay: Default::default(),
+            }
+        }
     }
 
     #[glib::object_subclass]
@@ -108,8 +282,42 @@ impl ResProcessDialog {
         glib::Object::new::<Self>()
     }
 
-    pub fn init<S: AsRef<str>>(&self, process: &ProcessEntry, user: S) {
+    pub fn init<S: AsRef<str>>(
+        &self,
+        process: &ProcessEntry,
+        user: S,
+        sender: Sender<Action>,
+        toast_overlay: &ToastOverlay,
+    ) {
+        let imp = self.imp();
+
+        let _ = imp.sender.set(sender);
+        *imp.toast_overlay.borrow_mut() = Some(toast_overlay.clone());
+        imp.process_pid.set(process.pid());
+        imp.process_starttime.set(process.starttime());
+
         self.setup_widgets(process, user.as_ref());
+        self.setup_signals();
+        self.setup_threads_column_view();
+        self.setup_memory_maps_column_view();
+        self.setup_open_files_column_view();
+        self.setup_connections_column_view();
+        self.setup_environment_column_view();
+
+        // don't leave the threads and everything else that's part of the details empty until the next refresh
+        let pid = process.pid();
+        let main_context = MainContext::default();
+        main_context.spawn_local(clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                if let Ok(Ok(Some(details))) =
+                    gio::spawn_blocking(move || Process::details(pid)).await
+                {
+                    this.update_details(&details);
+                }
+            }
+        ));
     }
 
     pub fn setup_widgets(&self, process: &ProcessEntry, user: &str) {
@@ -136,6 +344,13 @@ impl ResProcessDialog {
         imp.commandline.set_subtitle(&commandline_str);
         imp.commandline.set_tooltip_text(Some(&commandline_str));
 
+        let executable = process.executable().unwrap_or_else(|| i18n("N/A").into());
+        imp.executable.set_subtitle(&executable);
+        imp.executable.set_tooltip_text(Some(&executable));
+
+        imp.working_directory.set_subtitle(&i18n("N/A"));
+        imp.root_directory.set_subtitle(&i18n("N/A"));
+
         imp.cgroup
             .set_subtitle(&process.cgroup().unwrap_or_else(|| i18n("N/A").into()));
         imp.cgroup.set_tooltip_text(Some(
@@ -144,17 +359,78 @@ impl ResProcessDialog {
 
         imp.containerized.set_subtitle(&process.containerization());
 
+        self.show_wait_state();
+
         self.update(process);
     }
 
+    pub fn setup_signals(&self) {
+        let imp = self.imp();
+
+        imp.kernel_stack_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |button| {
+                let imp = this.imp();
+
+                // this asks the user to authenticate, so it's only done when requested and not on every refresh
+                button.set_sensitive(false);
+
+                let pid = imp.process_pid.get();
+                let starttime = imp.process_starttime.get();
+
+                let main_context = MainContext::default();
+                main_context.spawn_local(clone!(
+                    #[weak]
+                    this,
+                    #[weak]
+                    button,
+                    async move {
+                        match gio::spawn_blocking(move || {
+                            Process::privileged_wait_state(pid, starttime)
+                        })
+                        .await
+                        {
+                            Ok(Ok(wait_state)) => {
+                                *this.imp().privileged_wait_state.borrow_mut() = Some(wait_state);
+                                this.show_wait_state();
+                            }
+                            Ok(Err(err)) => {
+                                warn!("Unable to gather the wait state of {pid} privileged: {err}");
+                            }
+                            Err(_) => warn!("Unable to gather the wait state of {pid} privileged"),
+                        }
+
+                        button.set_sensitive(true);
+                    }
+                ));
+            }
+        ));
+    }
+
     pub fn update(&self, process: &ProcessEntry) {
         let imp = self.imp();
 
         imp.cpu_usage
             .set_subtitle(&format!("{:.1} %", process.cpu_usage() * 100.0));
 
+        let format_wait = |wait: f32| {
+            if wait == -1.0 {
+                i18n("N/A")
+            } else {
+                format!("{:.1} %", wait * 100.0)
+            }
+        };
+
+        imp.cpu_wait.set_subtitle(&format_wait(process.cpu_wait()));
+        imp.io_wait.set_subtitle(&format_wait(process.io_wait()));
+
         imp.memory_usage
-            .set_subtitle(&convert_storage(process.memory_usage() as f64, false));
+            .set_subtitle(&if process.memory_usage() == -1 {
+                i18n("N/A")
+            } else {
+                convert_storage(process.memory_usage() as f64, false)
+            });
 
         imp.swap_usage
             .set_subtitle(&convert_storage(process.swap_usage() as f64, false));
@@ -187,6 +463,45 @@ impl ResProcessDialog {
                 .set_subtitle(&convert_storage(process.write_total() as f64, false));
         }
 
+        let format_speed = |speed: f64| {
+            if speed == -1.0 {
+                i18n("N/A")
+            } else {
+                convert_speed(speed, false)
+            }
+        };
+
+        let format_total = |total: i64| {
+            if total == -1 {
+                i18n("N/A")
+            } else {
+                convert_storage(total as f64, false)
+            }
+        };
+
+        let format_rate = |rate: f64| {
+            if rate == -1.0 {
+                i18n("N/A")
+            } else {
+                convert_rate(rate)
+            }
+        };
+
+        imp.logical_read_speed
+            .set_subtitle(&format_speed(process.logical_read_speed()));
+        imp.logical_read_total
+            .set_subtitle(&format_total(process.logical_read_total()));
+        imp.logical_write_speed
+            .set_subtitle(&format_speed(process.logical_write_speed()));
+        imp.logical_write_total
+            .set_subtitle(&format_total(process.logical_write_total()));
+        imp.read_syscall_rate
+            .set_subtitle(&format_rate(process.read_syscall_rate()));
+        imp.write_syscall_rate
+            .set_subtitle(&format_rate(process.write_syscall_rate()));
+        imp.cancelled_write_total
+            .set_subtitle(&format_total(process.cancelled_write_total()));
+
         imp.gpu_usage
             .set_subtitle(&format!("{:.1} %", process.gpu_usage() * 100.0));
 
@@ -208,4 +523,969 @@ impl ResProcessDialog {
         imp.system_cpu_time
             .set_subtitle(&format_time(process.system_cpu_time()));
     }
+
+    fn update_threads(&self, details: &ProcessDetails) {
+        let imp = self.imp();
+
+        let store = imp.threads_store.borrow();
+
+        let mut already_existing_tids = HashSet::new();
+
+        // update the threads that we already know of
+        for thread_entry in store.iter::<ThreadEntry>().flatten() {
+            if let Some(thread) = details
+                .threads
+                .iter()
+                .find(|thread| thread.tid == thread_entry.tid())
+            {
+                thread_entry.update(thread, details.timestamp);
+                already_existing_tids.insert(thread.tid);
+            }
+        }
+
+        // remove threads that have exited in the meantime
+        store.retain(|object| {
+            already_existing_tids.contains(&object.downcast_ref::<ThreadEntry>().unwrap().tid())
+        });
+
+        let new_threads: Vec<ThreadEntry> = details
+            .threads
+            .iter()
+            .filter(|thread| !already_existing_tids.contains(&thread.tid))
+            .map(|thread| ThreadEntry::new(thread, details.timestamp))
+            .collect();
+        store.extend_from_slice(&new_threads);
+
+        if let Some(sorter) = imp.threads_column_view.borrow().sorter() {
+            sorter.changed(gtk::SorterChange::Different);
+        }
+    }
+
+    pub fn update_details(&self, details: &ProcessDetails) {
+        let imp = self.imp();
+
+        if details.timestamp <= imp.details_timestamp.get() {
+            return;
+        }
+        imp.details_timestamp.set(details.timestamp);
+
+        self.update_threads(details);
+        self.update_limits(&details.limits);
+        self.update_memory_maps(&details.memory_maps);
+        self.update_open_files(&details.open_files);
+        self.update_connections(&details.sockets);
+        self.update_environment(details.environment.as_deref());
+        *imp.wait_state.borrow_mut() = details.wait_state.clone();
+        self.show_wait_state();
+
+        let working_directory = details.cwd.clone().unwrap_or_else(|| i18n("N/A"));
+        imp.working_directory.set_subtitle(&working_directory);
+        imp.working_directory
+            .set_tooltip_text(Some(&working_directory));
+
+        let root_directory = details.root.clone().unwrap_or_else(|| i18n("N/A"));
+        imp.root_directory.set_subtitle(&root_directory);
+        imp.root_directory.set_tooltip_text(Some(&root_directory));
+    }
+
+    /// Shows the wait state of the latest details, completed with the one that has been gathered privileged (if any)
+    fn show_wait_state(&self) {
+        let imp = self.imp();
+
+        let mut wait_state = imp.wait_state.borrow().clone();
+
+        // the button also allows gathering the privileged wait state again since it's only a snapshot
+        imp.kernel_stack_button
+            .set_visible(wait_state.is_incomplete());
+
+        if let Some(privileged_wait_state) = imp.privileged_wait_state.borrow().clone() {
+            wait_state.complete_with(privileged_wait_state);
+        }
+
+        imp.wait_channel
+            .set_subtitle(wait_state.wchan.as_deref().unwrap_or(&i18n("N/A")));
+
+        imp.syscall.set_subtitle(
+            &wait_state
+                .syscall
+                .map_or_else(|| i18n("N/A"), syscall_label),
+        );
+
+        let kernel_stack = match &wait_state.stack {
+            Some(stack) if stack.is_empty() => i18n("Empty"),
+            Some(stack) => stack.join("\n"),
+            None => i18n("N/A"),
+        };
+        imp.kernel_stack.set_subtitle(&kernel_stack);
+    }
+
+    fn update_memory_maps(&self, memory_maps: &[MemoryMapping]) {
+        let imp = self.imp();
+
+        sync_memory_map_store(
+            &imp.memory_maps_store.borrow(),
+            &memory_map_tree(memory_maps),
+        );
+
+        if let Some(sorter) = imp.memory_maps_column_view.borrow().sorter() {
+            sorter.changed(gtk::SorterChange::Different);
+        }
+    }
+
+    fn update_open_files(&self, open_files: &[OpenFile]) {
+        let imp = self.imp();
+
+        let store = imp.open_files_store.borrow();
+
+        let mut already_existing_fds = HashSet::new();
+
+        // update the file descriptors that we already know of, an fd might have been reused for another file in
+        // the meantime, so the target is updated as well
+        for open_file_entry in store.iter::<OpenFileEntry>().flatten() {
+            if let Some(open_file) = open_files
+                .iter()
+                .find(|open_file| open_file.fd == open_file_entry.fd())
+            {
+                open_file_entry.update(open_file);
+                already_existing_fds.insert(open_file.fd);
+            }
+        }
+
+        // remove file descriptors that have been closed in the meantime
+        store.retain(|object| {
+            already_existing_fds.contains(&object.downcast_ref::<OpenFileEntry>().unwrap().fd())
+        });
+
+        let new_open_files: Vec<OpenFileEntry> = open_files
+            .iter()
+            .filter(|open_file| !already_existing_fds.contains(&open_file.fd))
+            .map(OpenFileEntry::new)
+            .collect();
+        store.extend_from_slice(&new_open_files);
+
+        if let Some(sorter) = imp.open_files_column_view.borrow().sorter() {
+            sorter.changed(gtk::SorterChange::Different);
+        }
+    }
+
+    fn update_connections(&self, sockets: &[Socket]) {
+        let imp = self.imp();
+
+        let store = imp.connections_store.borrow();
+
+        let mut already_existing_inodes = HashSet::new();
+
+        // update the sockets that we already know of
+        for socket_entry in store.iter::<SocketEntry>().flatten() {
+            if let Some(socket) = sockets
+                .iter()
+                .find(|socket| socket.inode == socket_entry.inode())
+            {
+                socket_entry.update(socket);
+                already_existing_inodes.insert(socket.inode);
+            }
+        }
+
+        // remove sockets that have been closed in the meantime
+        store.retain(|object| {
+            already_existing_inodes.contains(&object.downcast_ref::<SocketEntry>().unwrap().inode())
+        });
+
+        let new_sockets: Vec<SocketEntry> = sockets
+            .iter()
+            .filter(|socket| !already_existing_inodes.contains(&socket.inode))
+            .map(SocketEntry::new)
+            .collect();
+        store.extend_from_slice(&new_sockets);
+
+        if let Some(sorter) = imp.connections_column_view.borrow().sorter() {
+            sorter.changed(gtk::SorterChange::Different);
+        }
+    }
+
+    fn update_environment(&self, environment: Option<&[(String, String)]>) {
+        let imp = self.imp();
+
+        let Some(environment) = environment else {
+            imp.environment_stack.set_visible_child_name("unavailable");
+            return;
+        };
+
+        imp.environment_stack.set_visible_child_name("variables");
+
+        let store = imp.environment_store.borrow();
+
+        let mut already_existing_names = HashSet::new();
+
+        // the environment of a process rarely changes, so only values are updated and names are added or removed
+        for entry in store.iter::<EnvironmentVariableEntry>().flatten() {
+            if let Some((name, value)) = environment.iter().find(|(name, _)| *name == entry.name())
+            {
+                if *value != entry.value() {
+                    entry.set_value(value.as_str());
+                }
+                already_existing_names.insert(name.as_str());
+            }
+        }
+
+        store.retain(|object| {
+            already_existing_names.contains(
+                object
+                    .downcast_ref::<EnvironmentVariableEntry>()
+                    .unwrap()
+                    .name()
+                    .as_str(),
+            )
+        });
+
+        let mut new_entries = Vec::new();
+        for (name, value) in environment {
+            // a variable might appear more than once, only the first one counts
+            if already_existing_names.insert(name.as_str()) {
+                new_entries.push(EnvironmentVariableEntry::new(name, value));
+            }
+        }
+        store.extend_from_slice(&new_entries);
+    }
+
+    fn update_limits(&self, limits: &[ResourceLimit]) {
+        let imp = self.imp();
+
+        let mut limit_rows = imp.limit_rows.borrow_mut();
+
+        for limit in limits {
+            let subtitle = i18n_f(
+                "Soft: {} · Hard: {}",
+                &[
+                    &format_limit(limit.resource, limit.soft),
+                    &format_limit(limit.resource, limit.hard),
+                ],
+            );
+
+            if let Some((row, old_limit)) = limit_rows
+                .iter_mut()
+                .find(|(_, old_limit)| old_limit.resource == limit.resource)
+            {
+                if old_limit != limit {
+                    row.set_subtitle(&subtitle);
+                    *old_limit = *limit;
+                }
+                continue;
+            }
+
+            let edit_button = gtk::Button::builder()
+                .icon_name("document-edit-symbolic")
+                .tooltip_text(i18n("Change Limit"))
+                .valign(gtk::Align::Center)
+                .css_classes(["flat"])
+                .build();
+
+            let resource = limit.resource;
+            edit_button.connect_clicked(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_| this.open_limit_dialog(resource)
+            ));
+
+            let row = adw::ActionRow::builder()
+                .title(limit_label(limit.resource))
+                .subtitle(subtitle)
+                .css_classes(["property"])
+                .build();
+            row.add_suffix(&edit_button);
+
+            imp.limits_group.add(&row);
+
+            limit_rows.push((row, *limit));
+        }
+    }
+
+    fn open_limit_dialog(&self, resource: u32) {
+        let imp = self.imp();
+
+        let Some(limit) = imp
+            .limit_rows
+            .borrow()
+            .iter()
+            .find(|(_, limit)| limit.resource == resource)
+            .map(|(_, limit)| *limit)
+        else {
+            return;
+        };
+
+        let raw_value =
+            |value: Option<u64>| value.map(|value| value.to_string()).unwrap_or_default();
+
+        let soft_row = adw::EntryRow::builder()
+            .title(i18n("Soft Limit"))
+            .text(raw_value(limit.soft))
+            .input_purpose(gtk::InputPurpose::Digits)
+            .build();
+
+        let hard_row = adw::EntryRow::builder()
+            .title(i18n("Hard Limit"))
+            .text(raw_value(limit.hard))
+            .input_purpose(gtk::InputPurpose::Digits)
+            .build();
+
+        let list_box = gtk::ListBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .css_classes(["boxed-list"])
+            .build();
+        list_box.append(&soft_row);
+        list_box.append(&hard_row);
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(limit_label(resource))
+            .body(i18n(
+                "Leave a field empty to remove the limit. Raising the hard limit requires administrative privileges.",
+            ))
+            .extra_child(&list_box)
+            .build();
+
+        dialog.add_response("cancel", &i18n("Cancel"));
+        dialog.add_response("apply", &i18n("Apply"));
+        dialog.set_response_appearance("apply", ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("apply"));
+        dialog.set_close_response("cancel");
+
+        for entry_row in [&soft_row, &hard_row] {
+            entry_row.connect_changed(clone!(
+                #[weak]
+                dialog,
+                #[weak]
+                soft_row,
+                #[weak]
+                hard_row,
+                move |_| {
+                    dialog.set_response_enabled(
+                        "apply",
+                        parse_limit_entries(&soft_row.text(), &hard_row.text()).is_some(),
+                    );
+                }
+            ));
+        }
+
+        dialog.connect_response(
+            None,
+            clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[weak]
+                soft_row,
+                #[weak]
+                hard_row,
+                move |_, response| {
+                    if response != "apply" {
+                        return;
+                    }
+
+                    let Some((soft, hard)) =
+                        parse_limit_entries(&soft_row.text(), &hard_row.text())
+                    else {
+                        return;
+                    };
+
+                    let main_context = MainContext::default();
+                    main_context.spawn_local(clone!(
+                        #[weak]
+                        this,
+                        async move {
+                            let imp = this.imp();
+
+                            let (Some(sender), Some(toast_overlay)) =
+                                (imp.sender.get(), imp.toast_overlay.borrow().clone())
+                            else {
+                                return;
+                            };
+
+                            let _ = sender
+                                .send(Action::SetProcessLimit(
+                                    imp.process_pid.get(),
+                                    ResourceLimit {
+                                        resource,
+                                        soft,
+                                        hard,
+                                    },
+                                    imp.name.label().to_string(),
+                                    toast_overlay,
+                                ))
+                                .await;
+                        }
+                    ));
+                }
+            ),
+        );
+
+        dialog.present(Some(self));
+    }
+
+    fn setup_threads_column_view(&self) {
+        let imp = self.imp();
+
+        *imp.threads_column_view.borrow_mut() = gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = imp.threads_column_view.borrow();
+
+        self.add_column::<ThreadEntry, _>(
+            &column_view,
+            &i18n("Thread"),
+            "name",
+            string_sorter::<ThreadEntry>("name"),
+            |value| value.get::<String>().unwrap_or_default(),
+        )
+        .set_expand(true);
+
+        self.add_column::<ThreadEntry, _>(
+            &column_view,
+            &i18n("Thread ID"),
+            "tid",
+            numeric_sorter::<ThreadEntry>("tid"),
+            |value| value.get::<i32>().unwrap_or_default().to_string(),
+        );
+
+        self.add_column::<ThreadEntry, _>(
+            &column_view,
+            &i18n("State"),
+            "state",
+            string_sorter::<ThreadEntry>("state"),
+            |value| value.get::<String>().unwrap_or_default(),
+        );
+
+        let cpu_column = self.add_column::<ThreadEntry, _>(
+            &column_view,
+            &i18n("Processor"),
+            "cpu_usage",
+            numeric_sorter::<ThreadEntry>("cpu_usage"),
+            |value| {
+                let mut percentage = value.get::<f32>().unwrap_or_default() * 100.0;
+                if !SETTINGS.normalize_cpu_usage() {
+                    percentage *= *NUM_CPUS as f32;
+                }
+
+                format!("{percentage:.1} %")
+            },
+        );
+
+        self.add_column::<ThreadEntry, _>(
+            &column_view,
+            &i18n("User CPU Time"),
+            "user_cpu_time",
+            numeric_sorter::<ThreadEntry>("user_cpu_time"),
+            |value| format_time(value.get::<f64>().unwrap_or_default()),
+        );
+
+        self.add_column::<ThreadEntry, _>(
+            &column_view,
+            &i18n("System CPU Time"),
+            "system_cpu_time",
+            numeric_sorter::<ThreadEntry>("system_cpu_time"),
+            |value| format_time(value.get::<f64>().unwrap_or_default()),
+        );
+
+        self.add_column::<ThreadEntry, _>(
+            &column_view,
+            &i18n("Last CPU"),
+            "last_cpu",
+            numeric_sorter::<ThreadEntry>("last_cpu"),
+            |value| (value.get::<u32>().unwrap_or_default() + 1).to_string(),
+        );
+
+        self.add_column::<ThreadEntry, _>(
+            &column_view,
+            &i18n("Niceness"),
+            "niceness",
+            numeric_sorter::<ThreadEntry>("niceness"),
+            |value| value.get::<i8>().unwrap_or_default().to_string(),
+        );
+
+        let store = imp.threads_store.borrow();
+
+        let sort_model = gtk::SortListModel::new(Some(store.clone()), column_view.sorter());
+
+        let selection_model = gtk::NoSelection::new(Some(sort_model));
+
+        column_view.set_model(Some(&selection_model));
+
+        column_view.sort_by_column(Some(&cpu_column), SortType::Descending);
+
+        column_view.add_css_class("resources-columnview");
+
+        imp.threads_scrolled_window.set_child(Some(&*column_view));
+    }
+
+    fn setup_memory_maps_column_view(&self) {
+        let imp = self.imp();
+
+        *imp.memory_maps_column_view.borrow_mut() =
+            gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = imp.memory_maps_column_view.borrow();
+
+        self.add_memory_maps_column(
+            &column_view,
+            &i18n("Mapping"),
+            "name",
+            string_sorter::<MemoryMapEntry>("name"),
+            |value| value.get::<String>().unwrap_or_default(),
+        )
+        .set_expand(true);
+
+        self.add_memory_maps_column(
+            &column_view,
+            &i18n("Permissions"),
+            "permissions",
+            string_sorter::<MemoryMapEntry>("permissions"),
+            |value| value.get::<String>().unwrap_or_default(),
+        );
+
+        let rss_column = self.add_memory_maps_column(
+            &column_view,
+            &i18n("RSS"),
+            "rss",
+            numeric_sorter::<MemoryMapEntry>("rss"),
+            |value| convert_storage(value.get::<u64>().unwrap_or_default() as f64, false),
+        );
+
+        for (title, property) in [
+            (i18n("PSS"), "pss"),
+            (i18n("Swap"), "swap"),
+            (i18n("Dirty"), "dirty"),
+        ] {
+            self.add_memory_maps_column(
+                &column_view,
+                &title,
+                property,
+                numeric_sorter::<MemoryMapEntry>(property),
+                |value| convert_storage(value.get::<u64>().unwrap_or_default() as f64, false),
+            );
+        }
+
+        let store = imp.memory_maps_store.borrow();
+
+        let tree_model = gtk::TreeListModel::new(store.clone(), false, false, |object| {
+            object
+                .downcast_ref::<MemoryMapEntry>()
+                .filter(|entry| entry.has_children())
+                .map(|entry| entry.children().upcast::<gio::ListModel>())
+        });
+
+        let sort_model = gtk::SortListModel::new(
+            Some(tree_model),
+            Some(gtk::TreeListRowSorter::new(column_view.sorter())),
+        );
+
+        let selection_model = gtk::NoSelection::new(Some(sort_model));
+
+        column_view.set_model(Some(&selection_model));
+
+        column_view.sort_by_column(Some(&rss_column), SortType::Descending);
+
+        column_view.add_css_class("resources-columnview");
+
+        imp.memory_maps_scrolled_window
+            .set_child(Some(&*column_view));
+    }
+
+    /// Like `add_column` but for the rows of a `gtk::TreeListModel` of `MemoryMapEntry`s. The first column that is
+    /// added contains the expanders.
+    fn add_memory_maps_column<F: Fn(&Value) -> String + 'static>(
+        &self,
+        column_view: &ColumnView,
+        title: &str,
+        property: &'static str,
+        sorter: gtk::Sorter,
+        format: F,
+    ) -> ColumnViewColumn {
+        let format = Rc::new(format);
+
+        let with_expander = column_view.columns().n_items() == 0;
+
+        let col_factory = gtk::SignalListItemFactory::new();
+
+        let col = gtk::ColumnViewColumn::new(Some(title), Some(col_factory.clone()));
+
+        col.set_resizable(true);
+
+        col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+            row.set_min_chars(5);
+
+            if with_expander {
+                let expander = gtk::TreeExpander::new();
+                expander.set_child(Some(&row));
+
+                item.set_child(Some(&expander));
+
+                item.property_expression("item")
+                    .bind(&expander, "list-row", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<MemoryMapEntry>("has_children")
+                    .chain_closure::<bool>(closure!(
+                        |_: Option<glib::Object>, has_children: bool| { !has_children }
+                    ))
+                    .bind(&expander, "hide-expander", Widget::NONE);
+            } else {
+                item.set_child(Some(&row));
+            }
+
+            let format = format.clone();
+            gtk::ClosureExpression::with_callback(
+                [item
+                    .property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<MemoryMapEntry>(property)],
+                move |values| format(&values[1]),
+            )
+            .bind(&row, "text", Widget::NONE);
+        });
+
+        col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Widget>);
+        });
+
+        col.set_sorter(Some(&sorter));
+
+        column_view.append_column(&col);
+
+        col
+    }
+
+    fn setup_environment_column_view(&self) {
+        let imp = self.imp();
+
+        *imp.environment_column_view.borrow_mut() =
+            gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = imp.environment_column_view.borrow();
+
+        let name_column = self.add_column::<EnvironmentVariableEntry, _>(
+            &column_view,
+            &i18n("Variable"),
+            "name",
+            string_sorter::<EnvironmentVariableEntry>("name"),
+            |value| value.get::<String>().unwrap_or_default(),
+        );
+
+        self.add_column::<EnvironmentVariableEntry, _>(
+            &column_view,
+            &i18n("Value"),
+            "value",
+            string_sorter::<EnvironmentVariableEntry>("value"),
+            |value| value.get::<String>().unwrap_or_default(),
+        )
+        .set_expand(true);
+
+        let filter = gtk::CustomFilter::new(clone!(
+            #[weak(rename_to = this)]
+            self,
+            #[upgrade_or]
+            true,
+            move |object| {
+                let Some(entry) = object.downcast_ref::<EnvironmentVariableEntry>() else {
+                    return false;
+                };
+
+                let search_string = this.imp().environment_search_entry.text().to_lowercase();
+
+                entry.name().to_lowercase().contains(&search_string)
+                    || entry.value().to_lowercase().contains(&search_string)
+            }
+        ));
+
+        imp.environment_search_entry.connect_search_changed(clone!(
+            #[weak]
+            filter,
+            move |_| filter.changed(gtk::FilterChange::Different)
+        ));
+
+        let store = imp.environment_store.borrow();
+
+        let filter_model = gtk::FilterListModel::new(Some(store.clone()), Some(filter.clone()));
+
+        let sort_model = gtk::SortListModel::new(Some(filter_model), column_view.sorter());
+
+        let selection_model = gtk::NoSelection::new(Some(sort_model));
+
+        column_view.set_model(Some(&selection_model));
+
+        column_view.sort_by_column(Some(&name_column), SortType::Ascending);
+
+        column_view.add_css_class("resources-columnview");
+
+        imp.environment_scrolled_window
+            .set_child(Some(&*column_view));
+    }
+
+    fn setup_open_files_column_view(&self) {
+        let imp = self.imp();
+
+        *imp.open_files_column_view.borrow_mut() =
+            gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = imp.open_files_column_view.borrow();
+
+        let fd_column = self.add_column::<OpenFileEntry, _>(
+            &column_view,
+            &i18n("FD"),
+            "fd",
+            numeric_sorter::<OpenFileEntry>("fd"),
+            |value| value.get::<i32>().unwrap_or_default().to_string(),
+        );
+
+        self.add_column::<OpenFileEntry, _>(
+            &column_view,
+            &i18n("Type"),
+            "kind",
+            string_sorter::<OpenFileEntry>("kind"),
+            |value| value.get::<String>().unwrap_or_default(),
+        );
+
+        self.add_column::<OpenFileEntry, _>(
+            &column_view,
+            &i18n("Target"),
+            "target",
+            string_sorter::<OpenFileEntry>("target"),
+            |value| value.get::<String>().unwrap_or_default(),
+        )
+        .set_expand(true);
+
+        self.add_column::<OpenFileEntry, _>(
+            &column_view,
+            &i18n("Flags"),
+            "flags",
+            string_sorter::<OpenFileEntry>("flags"),
+            |value| value.get::<String>().unwrap_or_default(),
+        );
+
+        self.add_column::<OpenFileEntry, _>(
+            &column_view,
+            &i18n("Position"),
+            "pos",
+            numeric_sorter::<OpenFileEntry>("pos"),
+            |value| match value.get::<i64>().unwrap_or(-1) {
+                -1 => i18n("N/A"),
+                pos => pos.to_string(),
+            },
+        );
+
+        let store = imp.open_files_store.borrow();
+
+        let sort_model = gtk::SortListModel::new(Some(store.clone()), column_view.sorter());
+
+        let selection_model = gtk::NoSelection::new(Some(sort_model));
+
+        column_view.set_model(Some(&selection_model));
+
+        column_view.sort_by_column(Some(&fd_column), SortType::Ascending);
+
+        column_view.add_css_class("resources-columnview");
+
+        imp.open_files_scrolled_window
+            .set_child(Some(&*column_view));
+    }
+
+    fn setup_connections_column_view(&self) {
+        let imp = self.imp();
+
+        *imp.connections_column_view.borrow_mut() =
+            gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = imp.connections_column_view.borrow();
+
+        let protocol_column = self.add_column::<SocketEntry, _>(
+            &column_view,
+            &i18n("Protocol"),
+            "protocol",
+            string_sorter::<SocketEntry>("protocol"),
+            |value| value.get::<String>().unwrap_or_default(),
+        );
+
+        self.add_column::<SocketEntry, _>(
+            &column_view,
+            &i18n("Local Address"),
+            "local_address",
+            string_sorter::<SocketEntry>("local_address"),
+            |value| value.get::<String>().unwrap_or_default(),
+        )
+        .set_expand(true);
+
+        self.add_column::<SocketEntry, _>(
+            &column_view,
+            &i18n("Remote Address"),
+            "remote_address",
+            string_sorter::<SocketEntry>("remote_address"),
+            |value| value.get::<String>().unwrap_or_default(),
+        )
+        .set_expand(true);
+
+        self.add_column::<SocketEntry, _>(
+            &column_view,
+            &i18n("State"),
+            "state",
+            string_sorter::<SocketEntry>("state"),
+            |value| value.get::<String>().unwrap_or_default(),
+        );
+
+        for (title, property) in [
+            (i18n("Send Queue"), "send_queue"),
+            (i18n("Receive Queue"), "receive_queue"),
+        ] {
+            self.add_column::<SocketEntry, _>(
+                &column_view,
+                &title,
+                property,
+                numeric_sorter::<SocketEntry>(property),
+                |value| match value.get::<i64>().unwrap_or(-1) {
+                    -1 => i18n("N/A"),
+                    queue => convert_storage(queue as f64, false),
+                },
+            );
+        }
+
+        let store = imp.connections_store.borrow();
+
+        let sort_model = gtk::SortListModel::new(Some(store.clone()), column_view.sorter());
+
+        let selection_model = gtk::NoSelection::new(Some(sort_model));
+
+        column_view.set_model(Some(&selection_model));
+
+        column_view.sort_by_column(Some(&protocol_column), SortType::Ascending);
+
+        column_view.add_css_class("resources-columnview");
+
+        imp.connections_scrolled_window
+            .set_child(Some(&*column_view));
+    }
+
+    fn add_column<T: IsA<glib::Object>, F: Fn(&Value) -> String + 'static>(
+        &self,
+        column_view: &ColumnView,
+        title: &str,
+        property: &'static str,
+        sorter: gtk::Sorter,
+        format: F,
+    ) -> ColumnViewColumn {
+        let format = Rc::new(format);
+
+        let col_factory = gtk::SignalListItemFactory::new();
+
+        let col = gtk::ColumnViewColumn::new(Some(title), Some(col_factory.clone()));
+
+        col.set_resizable(true);
+
+        col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+            let row = gtk::Inscription::new(None);
+            row.set_min_chars(5);
+
+            item.set_child(Some(&row));
+
+            let format = format.clone();
+            gtk::ClosureExpression::with_callback(
+                [item
+                    .property_expression("item")
+                    .chain_property::<T>(property)],
+                move |values| format(&values[1]),
+            )
+            .bind(&row, "text", Widget::NONE);
+        });
+
+        col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        col.set_sorter(Some(&sorter));
+
+        column_view.append_column(&col);
+
+        col
+    }
+}
+
+fn string_sorter<T: StaticType>(property: &str) -> gtk::Sorter {
+    StringSorter::builder()
+        .ignore_case(true)
+        .expression(gtk::PropertyExpression::new(
+            T::static_type(),
+            None::<&gtk::Expression>,
+            property,
+        ))
+        .build()
+        .upcast()
+}
+
+fn numeric_sorter<T: StaticType>(property: &str) -> gtk::Sorter {
+    NumericSorter::builder()
+        .sort_order(SortType::Ascending)
+        .expression(gtk::PropertyExpression::new(
+            T::static_type(),
+            None::<&gtk::Expression>,
+            property,
+        ))
+        .build()
+        .upcast()
+}
+
+/// Parses the soft and hard limit entered by the user, empty values mean unlimited. Returns `None` if one of them
+/// isn't valid or the soft limit is larger than the hard limit.
+fn parse_limit_entries(soft: &str, hard: &str) -> Option<(Option<u64>, Option<u64>)> {
+    let parse = |value: &str| {
+        let value = value.trim();
+        if value.is_empty() {
+            Some(None)
+        } else {
+            value.parse().ok().map(Some)
+        }
+    };
+
+    let (soft, hard) = (parse(soft)?, parse(hard)?);
+
+    match (soft, hard) {
+        (Some(soft_value), Some(hard_value)) if soft_value > hard_value => None,
+        (None, Some(_)) => None,
+        _ => Some((soft, hard)),
+    }
+}
+
+fn limit_label(resource: u32) -> String {
+    match resource {
+        0 => i18n("CPU Time"),
+        1 => i18n("File Size"),
+        2 => i18n("Data Segment Size"),
+        3 => i18n("Stack Size"),
+        4 => i18n("Core File Size"),
+        5 => i18n("Resident Set Size"),
+        6 => i18n("Processes"),
+        7 => i18n("Open Files"),
+        8 => i18n("Locked Memory"),
+        9 => i18n("Address Space"),
+        10 => i18n("File Locks"),
+        11 => i18n("Pending Signals"),
+        12 => i18n("Message Queue Size"),
+        13 => i18n("Nice Priority"),
+        14 => i18n("Real-Time Priority"),
+        15 => i18n("Real-Time Timeout"),
+        _ => i18n("Unknown"),
+    }
+}
+
+fn format_limit(resource: u32, value: Option<u64>) -> String {
+    let Some(value) = value else {
+        return i18n("Unlimited");
+    };
+
+    match resource {
+        // RLIMIT_CPU is in seconds
+        0 => format_time(value as f64),
+        // RLIMIT_FSIZE, RLIMIT_DATA, RLIMIT_STACK, RLIMIT_CORE, RLIMIT_RSS, RLIMIT_MEMLOCK, RLIMIT_AS and
+        // RLIMIT_MSGQUEUE are in bytes
+        1..=5 | 8 | 9 | 12 => convert_storage(value as f64, false),
+        // RLIMIT_RTTIME is in microseconds
+        15 => format_time(value as f64 / 1_000_000.0),
+        _ => value.to_string(),
+    }
 }
@@ -5,12 +5,35 @@ use crate::{
         pages::{processes::process_entry::ProcessEntry, NICE_TO_LABEL},
         window::Action,
     },
-    utils::settings::SETTINGS,
+    utils::{
+        process::{Process, ProcessAdjustment},
+        settings::SETTINGS,
+    },
 };
 use adw::{prelude::*, subclass::prelude::*, ToastOverlay};
 use async_channel::Sender;
-use gtk::glib::{self, clone, MainContext};
-use process_data::Niceness;
+use gtk::{
+    gio,
+    glib::{self, clone, MainContext},
+};
+use process_data::{IoPriority, IoPriorityClass, Niceness, SchedulingPolicy, ThreadData};
+
+/// Scheduling policies in the order they appear in `policy_row`
+const POLICIES: [SchedulingPolicy; 5] = [
+    SchedulingPolicy::Other,
+    SchedulingPolicy::Batch,
+    SchedulingPolicy::Idle,
+    SchedulingPolicy::Fifo,
+    SchedulingPolicy::RoundRobin,
+];
+
+/// I/O priority classes in the order they appear in `io_class_row`
+const IO_CLASSES: [IoPriorityClass; 4] = [
+    IoPriorityClass::None,
+    IoPriorityClass::RealTime,
+    IoPriorityClass::BestEffort,
+    IoPriorityClass::Idle,
+];
 
 mod imp {
 
@@ -32,13 +55,30 @@ mod imp {
         #[template_child]
         pub priority_row: TemplateChild<adw::ComboRow>,
         #[template_child]
+        pub policy_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub realtime_priority_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub io_class_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub io_level_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub oom_score_adj_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
         pub affinity_row: TemplateChild<adw::ExpanderRow>,
         #[template_child]
         pub select_all_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub threads_row: TemplateChild<adw::ExpanderRow>,
 
         pub cpu_rows: RefCell<Vec<adw::SwitchRow>>,
 
+        pub thread_rows: RefCell<Vec<(libc::pid_t, adw::SwitchRow)>>,
+
         pub pid: Cell<libc::pid_t>,
+
+        /// Scheduling policy of the process if it can't be selected in `policy_row`, e.g. `SCHED_DEADLINE`
+        pub unsupported_policy: Cell<Option<SchedulingPolicy>>,
     }
 
     #[glib::object_subclass]
@@ -98,6 +138,21 @@ impl ResProcessOptionsDialog {
     ) {
         self.setup_widgets(process);
         self.setup_signals(process, sender, toast_overlay);
+
+        // don't leave the list of threads empty until the next refresh
+        let pid = process.pid();
+        let main_context = MainContext::default();
+        main_context.spawn_local(clone!(
+            #[weak(rename_to = this)]
+            self,
+            async move {
+                if let Ok(Ok(Some(details))) =
+                    gio::spawn_blocking(move || Process::details(pid)).await
+                {
+                    this.update_threads(&details.threads);
+                }
+            }
+        ));
     }
 
     fn get_current_niceness(&self) -> Niceness {
@@ -117,6 +172,166 @@ impl ResProcessOptionsDialog {
         }
     }
 
+    fn get_current_scheduling_policy(&self) -> SchedulingPolicy {
+        let imp = self.imp();
+
+        imp.unsupported_policy.get().unwrap_or_else(|| {
+            POLICIES
+                .get(imp.policy_row.selected() as usize)
+                .copied()
+                .unwrap_or_default()
+        })
+    }
+
+    fn get_current_io_priority(&self) -> IoPriority {
+        let imp = self.imp();
+
+        IoPriority {
+            class: IO_CLASSES
+                .get(imp.io_class_row.selected() as usize)
+                .copied()
+                .unwrap_or_default(),
+            level: imp.io_level_row.value() as u8,
+        }
+    }
+
+    fn get_selected_threads(&self) -> Vec<libc::pid_t> {
+        let imp = self.imp();
+
+        if !imp.threads_row.enables_expansion() {
+            return Vec::new();
+        }
+
+        imp.thread_rows
+            .borrow()
+            .iter()
+            .filter(|(_, switch_row)| switch_row.is_active())
+            .map(|(tid, _)| *tid)
+            .collect()
+    }
+
+    /// Returns the I/O priority and the scheduling policy with its realtime priority that are set in the dialog. They
+    /// are `None` if they haven't been changed since setting them again can require additional privileges even if
+    /// they stay the same, e.g. for realtime policies. If individual threads are targeted, they are always included
+    /// since the values shown are those of the process, which the threads' might differ from.
+    fn get_changed_priorities(
+        &self,
+        process: &ProcessEntry,
+        targets_threads: bool,
+    ) -> (Option<IoPriority>, Option<(SchedulingPolicy, u32)>) {
+        let imp = self.imp();
+
+        let io_priority = self.get_current_io_priority();
+        let io_priority_changed = io_priority != process.io_priority_value();
+
+        let policy = self.get_current_scheduling_policy();
+        let realtime_priority = imp.realtime_priority_row.value() as u32;
+        let scheduling_changed = policy != process.scheduling_policy_value()
+            || (policy.is_realtime() && realtime_priority != process.realtime_priority());
+
+        (
+            (io_priority_changed || targets_threads).then_some(io_priority),
+            (scheduling_changed || targets_threads).then_some((policy, realtime_priority)),
+        )
+    }
+
+    /// Returns the adjustment for the current state of the dialog. The OOM score adjustment is only included if it
+    /// has been changed.
+    fn get_adjustment(&self, process: &ProcessEntry) -> ProcessAdjustment {
+        let imp = self.imp();
+
+        let affinity: Vec<_> = imp
+            .cpu_rows
+            .borrow()
+            .iter()
+            .map(|switch_row| switch_row.is_active())
+            .collect();
+
+        let threads = self.get_selected_threads();
+
+        let (io_priority, scheduling) = self.get_changed_priorities(process, !threads.is_empty());
+
+        let oom_score_adj = imp.oom_score_adj_row.value() as i32;
+
+        ProcessAdjustment {
+            niceness: self.get_current_niceness(),
+            affinity,
+            io_priority,
+            scheduling,
+            oom_score_adj: (oom_score_adj != process.oom_score_adj()).then_some(oom_score_adj),
+            threads,
+        }
+    }
+
+    fn update_apply_button(&self) {
+        let imp = self.imp();
+
+        // at least one CPU and, if threads are targeted, at least one thread have to be selected
+        let any_cpu = imp
+            .cpu_rows
+            .borrow()
+            .iter()
+            .any(|switch_row| switch_row.is_active());
+
+        let any_thread =
+            !imp.threads_row.enables_expansion() || !self.get_selected_threads().is_empty();
+
+        imp.apply_button.set_sensitive(any_cpu && any_thread);
+    }
+
+    /// Adds rows for threads that have been started and removes the rows of threads that have exited since the last
+    /// update while keeping the selection of the remaining ones
+    pub fn update_threads(&self, threads: &[ThreadData]) {
+        let imp = self.imp();
+
+        let mut thread_rows = imp.thread_rows.borrow_mut();
+
+        thread_rows.retain(|(tid, switch_row)| {
+            let alive = threads.iter().any(|thread| thread.tid == *tid);
+            if !alive {
+                imp.threads_row.remove(switch_row);
+            }
+            alive
+        });
+
+        for thread in threads {
+            if thread_rows.iter().any(|(tid, _)| *tid == thread.tid) {
+                continue;
+            }
+
+            let switch_row = adw::SwitchRow::builder()
+                .title(glib::markup_escape_text(&thread.comm))
+                .subtitle(i18n_f("TID: {}", &[&thread.tid.to_string()]))
+                .build();
+
+            switch_row.connect_active_notify(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_| this.update_apply_button()
+            ));
+
+            imp.threads_row.add_row(&switch_row);
+
+            thread_rows.push((thread.tid, switch_row));
+        }
+
+        std::mem::drop(thread_rows);
+
+        self.update_apply_button();
+    }
+
+    fn update_row_visibility(&self) {
+        let imp = self.imp();
+
+        imp.realtime_priority_row
+            .set_visible(self.get_current_scheduling_policy().is_realtime());
+
+        imp.io_level_row.set_visible(matches!(
+            self.get_current_io_priority().class,
+            IoPriorityClass::RealTime | IoPriorityClass::BestEffort
+        ));
+    }
+
     pub fn setup_widgets(&self, process: &ProcessEntry) {
         let imp = self.imp();
 
@@ -136,6 +351,32 @@ impl ResProcessOptionsDialog {
             imp.nice_row.set_visible(false);
         }
 
+        let policy = process.scheduling_policy_value();
+        if let Some(position) = POLICIES.iter().position(|p| *p == policy) {
+            imp.policy_row.set_selected(position as u32);
+        } else {
+            imp.unsupported_policy.set(Some(policy));
+            imp.policy_row.set_sensitive(false);
+            imp.policy_row.set_subtitle(&process.scheduling_policy());
+        }
+
+        imp.realtime_priority_row
+            .set_value(process.realtime_priority().max(1) as f64);
+
+        let io_priority = process.io_priority_value();
+        imp.io_class_row.set_selected(
+            IO_CLASSES
+                .iter()
+                .position(|class| *class == io_priority.class)
+                .unwrap_or_default() as u32,
+        );
+        imp.io_level_row.set_value(io_priority.level as f64);
+
+        imp.oom_score_adj_row
+            .set_value(process.oom_score_adj() as f64);
+
+        self.update_row_visibility();
+
         for (i, affinity) in process.affinity().iter().enumerate() {
             let switch_row = adw::SwitchRow::builder()
                 .title(i18n_f("CPU {}", &[&(i + 1).to_string()]))
@@ -145,17 +386,7 @@ impl ResProcessOptionsDialog {
             switch_row.connect_active_notify(clone!(
                 #[weak(rename_to = this)]
                 self,
-                move |_| {
-                    let imp = this.imp();
-
-                    // if all switch rows are disabled, disable the apply button
-                    let setting = imp
-                        .cpu_rows
-                        .borrow()
-                        .iter()
-                        .any(|switch_row| switch_row.is_active());
-                    imp.apply_button.set_sensitive(setting);
-                }
+                move |_| this.update_apply_button()
             ));
 
             imp.affinity_row.add_row(&switch_row);
@@ -174,6 +405,24 @@ impl ResProcessOptionsDialog {
     ) {
         let imp = self.imp();
 
+        imp.policy_row.connect_selected_notify(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.update_row_visibility()
+        ));
+
+        imp.io_class_row.connect_selected_notify(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.update_row_visibility()
+        ));
+
+        imp.threads_row.connect_enable_expansion_notify(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| this.update_apply_button()
+        ));
+
         imp.select_all_button.connect_clicked(clone!(
             #[weak(rename_to = this)]
             self,
@@ -205,20 +454,10 @@ impl ResProcessOptionsDialog {
                     #[strong]
                     sender,
                     async move {
-                        let imp = this.imp();
-
-                        let affinity: Vec<_> = imp
-                            .cpu_rows
-                            .borrow()
-                            .iter()
-                            .map(|switch_row| switch_row.is_active())
-                            .collect();
-
                         let _ = sender
                             .send(Action::AdjustProcess(
                                 process.pid(),
-                                this.get_current_niceness(),
-                                affinity,
+                                this.get_adjustment(&process),
                                 process.name().to_string(),
                                 toast_overlay.clone(),
                             ))
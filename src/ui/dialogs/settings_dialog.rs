@@ -3,7 +3,10 @@ use gtk::glib;
 
 use crate::{
     config::PROFILE,
-    utils::settings::{Base, RefreshSpeed, SidebarMeterType, TemperatureUnit, SETTINGS},
+    utils::{
+        privileged_helper,
+        settings::{Base, MemoryMetric, RefreshSpeed, SidebarMeterType, TemperatureUnit, SETTINGS},
+    },
 };
 
 mod imp {
@@ -65,6 +68,10 @@ mod imp {
         #[template_child]
         pub processes_niceness: TemplateChild<adw::SwitchRow>,
         #[template_child]
+        pub processes_memory_metric_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub persistent_privileged_helper_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
         pub processes_show_id_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub processes_show_user_row: TemplateChild<adw::SwitchRow>,
@@ -98,6 +105,40 @@ mod imp {
         pub processes_show_priority_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub processes_show_swap_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_state_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_threads_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_voluntary_context_switches_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_involuntary_context_switches_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_minor_faults_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_major_faults_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_io_priority_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_scheduling_policy_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_oom_score_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_fd_count_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_listening_ports_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_logical_read_speed_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_logical_write_speed_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_read_syscall_rate_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_write_syscall_rate_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_cpu_wait_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_io_wait_row: TemplateChild<adw::SwitchRow>,
 
         #[template_child]
         pub show_virtual_drives_row: TemplateChild<adw::SwitchRow>,
@@ -210,6 +251,10 @@ impl ResSettingsDialog {
 
         imp.processes_niceness
             .set_active(SETTINGS.detailed_priority());
+        imp.processes_memory_metric_row
+            .set_selected((SETTINGS.processes_memory_metric() as u8) as u32);
+        imp.persistent_privileged_helper_row
+            .set_active(SETTINGS.persistent_privileged_helper());
         imp.processes_show_id_row
             .set_active(SETTINGS.processes_show_id());
         imp.processes_show_user_row
@@ -244,6 +289,40 @@ impl ResSettingsDialog {
             .set_active(SETTINGS.processes_show_system_cpu_time());
         imp.processes_show_swap_row
             .set_active(SETTINGS.processes_show_swap());
+        imp.processes_show_state_row
+            .set_active(SETTINGS.processes_show_state());
+        imp.processes_show_threads_row
+            .set_active(SETTINGS.processes_show_threads());
+        imp.processes_show_voluntary_context_switches_row
+            .set_active(SETTINGS.processes_show_voluntary_context_switches());
+        imp.processes_show_involuntary_context_switches_row
+            .set_active(SETTINGS.processes_show_involuntary_context_switches());
+        imp.processes_show_minor_faults_row
+            .set_active(SETTINGS.processes_show_minor_faults());
+        imp.processes_show_major_faults_row
+            .set_active(SETTINGS.processes_show_major_faults());
+        imp.processes_show_io_priority_row
+            .set_active(SETTINGS.processes_show_io_priority());
+        imp.processes_show_scheduling_policy_row
+            .set_active(SETTINGS.processes_show_scheduling_policy());
+        imp.processes_show_oom_score_row
+            .set_active(SETTINGS.processes_show_oom_score());
+        imp.processes_show_fd_count_row
+            .set_active(SETTINGS.processes_show_fd_count());
+        imp.processes_show_listening_ports_row
+            .set_active(SETTINGS.processes_show_listening_ports());
+        imp.processes_show_logical_read_speed_row
+            .set_active(SETTINGS.processes_show_logical_read_speed());
+        imp.processes_show_logical_write_speed_row
+            .set_active(SETTINGS.processes_show_logical_write_speed());
+        imp.processes_show_read_syscall_rate_row
+            .set_active(SETTINGS.processes_show_read_syscall_rate());
+        imp.processes_show_write_syscall_rate_row
+            .set_active(SETTINGS.processes_show_write_syscall_rate());
+        imp.processes_show_cpu_wait_row
+            .set_active(SETTINGS.processes_show_cpu_wait());
+        imp.processes_show_io_wait_row
+            .set_active(SETTINGS.processes_show_io_wait());
 
         imp.show_virtual_drives_row
             .set_active(SETTINGS.show_virtual_drives());
@@ -372,6 +451,21 @@ impl ResSettingsDialog {
             let _ = SETTINGS.set_detailed_priority(switch_row.is_active());
         });
 
+        imp.processes_memory_metric_row
+            .connect_selected_item_notify(|combo_row| {
+                if let Some(metric) = MemoryMetric::from_repr(combo_row.selected() as u8) {
+                    let _ = SETTINGS.set_processes_memory_metric(metric);
+                }
+            });
+
+        imp.persistent_privileged_helper_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_persistent_privileged_helper(switch_row.is_active());
+                if !switch_row.is_active() {
+                    privileged_helper::stop();
+                }
+            });
+
         imp.processes_show_id_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_processes_show_id(switch_row.is_active());
@@ -457,6 +551,93 @@ impl ResSettingsDialog {
                 let _ = SETTINGS.set_processes_show_swap(switch_row.is_active());
             });
 
+        imp.processes_show_state_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_state(switch_row.is_active());
+            });
+
+        imp.processes_show_threads_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_threads(switch_row.is_active());
+            });
+
+        imp.processes_show_voluntary_context_switches_row
+            .connect_active_notify(|switch_row| {
+                let _ =
+                    SETTINGS.set_processes_show_voluntary_context_switches(switch_row.is_active());
+            });
+
+        imp.processes_show_involuntary_context_switches_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS
+                    .set_processes_show_involuntary_context_switches(switch_row.is_active());
+            });
+
+        imp.processes_show_minor_faults_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_minor_faults(switch_row.is_active());
+            });
+
+        imp.processes_show_major_faults_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_major_faults(switch_row.is_active());
+            });
+
+        imp.processes_show_io_priority_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_io_priority(switch_row.is_active());
+            });
+
+        imp.processes_show_scheduling_policy_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_scheduling_policy(switch_row.is_active());
+            });
+
+        imp.processes_show_oom_score_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_oom_score(switch_row.is_active());
+            });
+
+        imp.processes_show_fd_count_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_fd_count(switch_row.is_active());
+            });
+
+        imp.processes_show_listening_ports_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_listening_ports(switch_row.is_active());
+            });
+
+        imp.processes_show_logical_read_speed_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_logical_read_speed(switch_row.is_active());
+            });
+
+        imp.processes_show_logical_write_speed_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_logical_write_speed(switch_row.is_active());
+            });
+
+        imp.processes_show_read_syscall_rate_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_read_syscall_rate(switch_row.is_active());
+            });
+
+        imp.processes_show_write_syscall_rate_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_write_syscall_rate(switch_row.is_active());
+            });
+
+        imp.processes_show_cpu_wait_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_cpu_wait(switch_row.is_active());
+            });
+
+        imp.processes_show_io_wait_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_io_wait(switch_row.is_active());
+            });
+
         imp.show_virtual_drives_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_show_virtual_drives(switch_row.is_active());
@@ -1,9 +1,14 @@
-use gtk::glib::{self};
-use process_data::Containerization;
+use gtk::{
+    glib::{self},
+    subclass::prelude::ObjectSubclassIsExt,
+};
 
 use crate::{
-    i18n::i18n,
-    utils::app::{App, AppsContext},
+    ui::pages::containerization_label,
+    utils::{
+        app::{App, AppsContext},
+        systemd::{CgroupLimits, UnitCgroup},
+    },
 };
 
 mod imp {
@@ -78,6 +83,16 @@ mod imp {
         #[property(get, set)]
         running_processes: Cell<u32>,
 
+        #[property(get, set)]
+        frozen: Cell<bool>,
+
+        /// The limits of the app's systemd unit, `None` if the app isn't running in a unit of its own
+        pub limits: Cell<Option<CgroupLimits>>,
+
+        /// The highest memory usage among the app's systemd units, a hard memory limit below it would get the app's
+        /// processes killed
+        pub units_memory_usage: Cell<Option<u64>>,
+
         // TODO: Make this properly dynamic, don't use a variable that's never read
         #[property(get = Self::symbolic)]
         #[allow(dead_code)]
@@ -106,6 +121,9 @@ mod imp {
                 running_since: Cell::new(None),
                 containerization: Cell::new(glib::GString::default()),
                 running_processes: Cell::new(0),
+                frozen: Cell::new(false),
+                limits: Cell::new(None),
+                units_memory_usage: Cell::new(None),
             }
         }
     }
@@ -179,18 +197,15 @@ glib::wrapper! {
 
 impl ApplicationEntry {
     pub fn new(app: &App, apps_context: &AppsContext) -> Self {
-        let containerization = match app.containerization {
-            Containerization::None => i18n("No"),
-            Containerization::Flatpak => i18n("Yes (Flatpak)"),
-            Containerization::Snap => i18n("Yes (Snap)"),
-        };
-
         let this: Self = glib::Object::builder()
             .property("name", &app.display_name)
             .property("icon", &app.icon)
             .property("id", &app.id)
             .property("description", &app.description)
-            .property("containerization", containerization)
+            .property(
+                "containerization",
+                containerization_label(app.containerization),
+            )
             .property("running_since", app.running_since(apps_context).ok())
             .build();
         this.update(app, apps_context);
@@ -210,5 +225,22 @@ impl ApplicationEntry {
         self.set_dec_usage(app.dec_usage(apps_context));
         self.set_gpu_mem_usage(app.gpu_mem_usage(apps_context));
         self.set_running_processes(app.running_processes() as u32);
+        self.set_frozen(app.is_frozen(apps_context));
+
+        let units = app.units(apps_context);
+
+        // limits are applied to all of the app's units, so the first one is representative
+        self.imp().limits.set(units.first().map(UnitCgroup::limits));
+        self.imp()
+            .units_memory_usage
+            .set(units.iter().filter_map(UnitCgroup::memory_usage).max());
+    }
+
+    pub fn limits(&self) -> Option<CgroupLimits> {
+        self.imp().limits.get()
+    }
+
+    pub fn units_memory_usage(&self) -> Option<u64> {
+        self.imp().units_memory_usage.get()
     }
 }
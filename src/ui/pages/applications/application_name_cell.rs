@@ -1,6 +1,8 @@
 use adw::{glib::property::PropertySet, prelude::*, subclass::prelude::*};
 use gtk::{gio::Icon, glib};
 
+use crate::ui::pages::frozen_state_label;
+
 mod imp {
     use std::cell::{Cell, RefCell};
 
@@ -20,6 +22,8 @@ mod imp {
         pub image: TemplateChild<gtk::Image>,
         #[template_child]
         pub inscription: TemplateChild<gtk::Inscription>,
+        #[template_child]
+        pub state_badge: TemplateChild<gtk::Label>,
 
         #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
         name: Cell<glib::GString>,
@@ -29,6 +33,8 @@ mod imp {
         icon: RefCell<Icon>,
         #[property(get, set = Self::set_symbolic)]
         symbolic: Cell<bool>,
+        #[property(get, set = Self::set_frozen)]
+        frozen: Cell<bool>,
     }
 
     impl Default for ResApplicationNameCell {
@@ -36,10 +42,12 @@ mod imp {
             Self {
                 image: Default::default(),
                 inscription: Default::default(),
+                state_badge: Default::default(),
                 name: Default::default(),
                 tooltip: Default::default(),
                 icon: RefCell::new(ThemedIcon::new("generic-process").into()),
                 symbolic: Default::default(),
+                frozen: Default::default(),
             }
         }
     }
@@ -106,6 +114,12 @@ mod imp {
                 self.image.set_pixel_size(32);
             }
         }
+
+        pub fn set_frozen(&self, frozen: bool) {
+            self.frozen.set(frozen);
+            self.state_badge.set_label(&frozen_state_label());
+            self.state_badge.set_visible(frozen);
+        }
     }
 
     #[glib::object_subclass]
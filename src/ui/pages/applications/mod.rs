@@ -15,6 +15,7 @@ use gtk::{
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
 use crate::ui::dialogs::app_dialog::ResAppDialog;
+use crate::ui::dialogs::app_limits_dialog::ResAppLimitsDialog;
 use crate::ui::window::{Action, MainWindow};
 use crate::utils::app::AppsContext;
 use crate::utils::process::ProcessAction;
@@ -203,6 +204,18 @@ mod imp {
                 },
             );
 
+            klass.install_action(
+                "applications.context-limit-app",
+                None,
+                move |res_applications, _, _| {
+                    if let Some(application_entry) =
+                        res_applications.imp().popped_over_app.borrow().as_ref()
+                    {
+                        res_applications.open_limits_dialog(application_entry);
+                    }
+                },
+            );
+
             klass.install_action(
                 "applications.context-information",
                 None,
@@ -333,6 +346,11 @@ impl ResApplications {
                     let imp = this.imp();
                     let popover_menu = &imp.popover_menu;
 
+                    this.action_set_enabled(
+                        "applications.context-limit-app",
+                        entry.limits().is_some(),
+                    );
+
                     *imp.popped_over_app.borrow_mut() = Some(entry);
 
                     let position = widget
@@ -565,6 +583,16 @@ impl ResApplications {
         ));
     }
 
+    pub fn open_limits_dialog(&self, app: &ApplicationEntry) {
+        let imp = self.imp();
+
+        let dialog = ResAppLimitsDialog::new();
+
+        dialog.init(app, imp.sender.get().unwrap().clone(), &imp.toast_overlay);
+
+        dialog.present(Some(&MainWindow::default()));
+    }
+
     fn search_filter(&self, obj: &Object) -> bool {
         let imp = self.imp();
         let item = obj.downcast_ref::<ApplicationEntry>().unwrap();
@@ -779,6 +807,10 @@ impl ResApplications {
                     .chain_property::<ApplicationEntry>("symbolic")
                     .bind(&row, "symbolic", Widget::NONE);
 
+                item.property_expression("item")
+                    .chain_property::<ApplicationEntry>("frozen")
+                    .bind(&row, "frozen", Widget::NONE);
+
                 this.add_gestures(item);
             }
         ));
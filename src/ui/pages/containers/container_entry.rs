@@ -0,0 +1,128 @@
+use gtk::glib::{self};
+
+use crate::{
+    ui::pages::container_runtime_label,
+    utils::{app::AppsContext, container::RunningContainer},
+};
+
+mod imp {
+    use std::cell::Cell;
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use super::*;
+
+    #[derive(Properties)]
+    #[properties(wrapper_type = super::ContainerEntry)]
+    pub struct ContainerEntry {
+        /// See `container_key()`
+        #[property(get = Self::key, set = Self::set_key, type = glib::GString)]
+        key: Cell<glib::GString>,
+
+        #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
+        name: Cell<glib::GString>,
+
+        #[property(get = Self::id, set = Self::set_id, type = glib::GString)]
+        id: Cell<glib::GString>,
+
+        #[property(get = Self::runtime, set = Self::set_runtime, type = glib::GString)]
+        runtime: Cell<glib::GString>,
+
+        #[property(get, set)]
+        cpu_usage: Cell<f32>,
+
+        #[property(get, set)]
+        memory_usage: Cell<u64>,
+
+        #[property(get, set)]
+        read_speed: Cell<f64>,
+
+        #[property(get, set)]
+        write_speed: Cell<f64>,
+
+        #[property(get, set)]
+        running_processes: Cell<u32>,
+
+        #[property(get, set)]
+        paused: Cell<bool>,
+    }
+
+    impl Default for ContainerEntry {
+        fn default() -> Self {
+            Self {
+                key: Cell::new(glib::GString::default()),
+                name: Cell::new(glib::GString::default()),
+                id: Cell::new(glib::GString::default()),
+                runtime: Cell::new(glib::GString::default()),
+                cpu_usage: Cell::new(0.0),
+                memory_usage: Cell::new(0),
+                read_speed: Cell::new(0.0),
+                write_speed: Cell::new(0.0),
+                running_processes: Cell::new(0),
+                paused: Cell::new(false),
+            }
+        }
+    }
+
+    impl ContainerEntry {
+        gstring_getter_setter!(key, name, id, runtime);
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ContainerEntry {
+        const NAME: &'static str = "ContainerEntry";
+        type Type = super::ContainerEntry;
+    }
+
+    impl ObjectImpl for ContainerEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct ContainerEntry(ObjectSubclass<imp::ContainerEntry>);
+}
+
+impl ContainerEntry {
+    pub fn new(container: &RunningContainer, apps_context: &AppsContext) -> Self {
+        let this: Self = glib::Object::builder()
+            .property("key", container.key())
+            .property("id", &container.container.id)
+            .property(
+                "runtime",
+                container_runtime_label(container.container.runtime),
+            )
+            .build();
+        this.update(container, apps_context);
+        this
+    }
+
+    pub fn update(&self, container: &RunningContainer, apps_context: &AppsContext) {
+        // the name might only become known once a process that allows us to look into the container shows up
+        self.set_name(container.container.display_name());
+        self.set_cpu_usage(container.cpu_time_ratio(apps_context));
+        self.set_memory_usage(container.memory_usage(apps_context) as u64);
+        self.set_read_speed(container.read_speed(apps_context));
+        self.set_write_speed(container.write_speed(apps_context));
+        self.set_running_processes(container.running_processes() as u32);
+        self.set_paused(container.is_paused(apps_context));
+    }
+}
@@ -0,0 +1,1062 @@
+pub mod container_entry;
+
+use std::collections::HashSet;
+
+use adw::ResponseAppearance;
+use adw::{prelude::*, subclass::prelude::*};
+use async_channel::Sender;
+use gtk::glib::{self, clone, closure, MainContext, Object};
+use gtk::{
+    gio, ColumnView, ColumnViewColumn, EventControllerKey, FilterChange, ListItem, NumericSorter,
+    SortType, StringSorter, Widget,
+};
+
+use crate::config::PROFILE;
+use crate::i18n::{i18n, i18n_f};
+use crate::ui::window::{Action, MainWindow};
+use crate::utils::app::AppsContext;
+use crate::utils::process::ProcessAction;
+use crate::utils::settings::SETTINGS;
+use crate::utils::units::{convert_speed, convert_storage};
+use crate::utils::NUM_CPUS;
+
+use self::container_entry::ContainerEntry;
+
+pub const TAB_ID: &str = "containers";
+
+mod imp {
+    use std::{
+        cell::{Cell, RefCell},
+        sync::OnceLock,
+    };
+
+    use crate::ui::{pages::CONTAINERS_PRIMARY_ORD, window::Action};
+
+    use super::*;
+
+    use gtk::{
+        gio::{Icon, ThemedIcon},
+        glib::{ParamSpec, Properties, Value},
+        ColumnViewColumn, CompositeTemplate,
+    };
+
+    #[derive(CompositeTemplate, Properties)]
+    #[template(resource = "/net/nokyan/Resources/ui/pages/containers.ui")]
+    #[properties(wrapper_type = super::ResContainers)]
+    pub struct ResContainers {
+        #[template_child]
+        pub toast_overlay: TemplateChild<adw::ToastOverlay>,
+        #[template_child]
+        pub popover_menu: TemplateChild<gtk::PopoverMenu>,
+        #[template_child]
+        pub search_revealer: TemplateChild<gtk::Revealer>,
+        #[template_child]
+        pub search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub containers_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub containers_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub search_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub stop_container_button: TemplateChild<adw::SplitButton>,
+
+        pub store: RefCell<gio::ListStore>,
+        pub selection_model: RefCell<gtk::SingleSelection>,
+        pub filter_model: RefCell<gtk::FilterListModel>,
+        pub sort_model: RefCell<gtk::SortListModel>,
+        pub column_view: RefCell<gtk::ColumnView>,
+
+        pub sender: OnceLock<Sender<Action>>,
+
+        pub popped_over_container: RefCell<Option<ContainerEntry>>,
+
+        pub columns: RefCell<Vec<ColumnViewColumn>>,
+
+        #[property(get)]
+        uses_progress_bar: Cell<bool>,
+
+        #[property(get)]
+        icon: RefCell<Icon>,
+
+        #[property(get = Self::tab_name, type = glib::GString)]
+        tab_name: Cell<glib::GString>,
+
+        #[property(get = Self::tab_detail_string, type = glib::GString)]
+        tab_detail_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_usage_string, set = Self::set_tab_usage_string, type = glib::GString)]
+        tab_usage_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_id, type = glib::GString)]
+        tab_id: Cell<glib::GString>,
+
+        #[property(get)]
+        graph_locked_max_y: Cell<bool>,
+
+        #[property(get)]
+        primary_ord: Cell<u32>,
+
+        #[property(get)]
+        secondary_ord: Cell<u32>,
+    }
+
+    impl ResContainers {
+        gstring_getter_setter!(tab_name, tab_detail_string, tab_usage_string, tab_id);
+    }
+
+    impl Default for ResContainers {
+        fn default() -> Self {
+            Self {
+                toast_overlay: Default::default(),
+                popover_menu: Default::default(),
+                search_revealer: Default::default(),
+                search_entry: Default::default(),
+                containers_stack: Default::default(),
+                containers_scrolled_window: Default::default(),
+                search_button: Default::default(),
+                stop_container_button: Default::default(),
+                store: gio::ListStore::new::<ContainerEntry>().into(),
+                selection_model: Default::default(),
+                filter_model: Default::default(),
+                sort_model: Default::default(),
+                column_view: Default::default(),
+                sender: Default::default(),
+                popped_over_container: Default::default(),
+                columns: Default::default(),
+                uses_progress_bar: Cell::new(false),
+                icon: RefCell::new(ThemedIcon::new("docker-bridge-symbolic").into()),
+                tab_name: Cell::from(glib::GString::from(i18n("Containers"))),
+                tab_detail_string: Cell::new(glib::GString::new()),
+                tab_usage_string: Cell::new(glib::GString::new()),
+                tab_id: Cell::new(glib::GString::from(TAB_ID)),
+                graph_locked_max_y: Cell::new(true),
+                primary_ord: Cell::new(CONTAINERS_PRIMARY_ORD),
+                secondary_ord: Default::default(),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResContainers {
+        const NAME: &'static str = "ResContainers";
+        type Type = super::ResContainers;
+        type ParentType = adw::Bin;
+
+        fn class_init(klass: &mut Self::Class) {
+            for (name, action) in [
+                ("containers.context-stop-container", ProcessAction::TERM),
+                ("containers.context-kill-container", ProcessAction::KILL),
+                ("containers.context-pause-container", ProcessAction::STOP),
+                ("containers.context-resume-container", ProcessAction::CONT),
+            ] {
+                klass.install_action(name, None, move |res_containers, _, _| {
+                    if let Some(container_entry) =
+                        res_containers.imp().popped_over_container.borrow().as_ref()
+                    {
+                        res_containers.open_container_action_dialog(container_entry, action);
+                    }
+                });
+            }
+
+            for (name, action) in [
+                ("containers.kill-container", ProcessAction::KILL),
+                ("containers.pause-container", ProcessAction::STOP),
+                ("containers.resume-container", ProcessAction::CONT),
+            ] {
+                klass.install_action(name, None, move |res_containers, _, _| {
+                    if let Some(container) = res_containers.get_selected_container_entry() {
+                        res_containers.open_container_action_dialog(&container, action);
+                    }
+                });
+            }
+
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResContainers {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+
+    impl WidgetImpl for ResContainers {}
+    impl BinImpl for ResContainers {}
+}
+
+glib::wrapper! {
+    pub struct ResContainers(ObjectSubclass<imp::ResContainers>)
+        @extends gtk::Widget, adw::Bin;
+}
+
+impl Default for ResContainers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResContainers {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    pub fn toggle_search(&self) {
+        let imp = self.imp();
+        imp.search_button.set_active(!imp.search_button.is_active());
+    }
+
+    pub fn close_search(&self) {
+        let imp = self.imp();
+        imp.search_button.set_active(false);
+    }
+
+    pub fn init(&self, sender: Sender<Action>) {
+        let imp = self.imp();
+        imp.sender.set(sender).unwrap();
+
+        self.setup_widgets();
+        self.setup_signals();
+    }
+
+    fn add_gestures(&self, item: &ListItem) {
+        let widget = item.child().unwrap();
+
+        let secondary_click = gtk::GestureClick::new();
+        secondary_click.set_button(3);
+        secondary_click.connect_released(clone!(
+            #[weak]
+            widget,
+            #[weak]
+            item,
+            #[weak(rename_to = this)]
+            self,
+            move |_, _, x, y| {
+                if let Some(entry) = item.item().and_downcast::<ContainerEntry>() {
+                    let imp = this.imp();
+                    let popover_menu = &imp.popover_menu;
+
+                    this.action_set_enabled("containers.context-pause-container", !entry.paused());
+                    this.action_set_enabled("containers.context-resume-container", entry.paused());
+
+                    *imp.popped_over_container.borrow_mut() = Some(entry);
+
+                    let position = widget
+                        .compute_point(&this, &gtk::graphene::Point::new(x as _, y as _))
+                        .unwrap();
+
+                    popover_menu.set_pointing_to(Some(&gtk::gdk::Rectangle::new(
+                        position.x().round() as i32,
+                        position.y().round() as i32,
+                        1,
+                        1,
+                    )));
+
+                    popover_menu.popup();
+                }
+            }
+        ));
+
+        widget.add_controller(secondary_click);
+    }
+
+    pub fn setup_widgets(&self) {
+        let imp = self.imp();
+
+        imp.popover_menu.set_parent(self);
+
+        *imp.column_view.borrow_mut() = gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = imp.column_view.borrow();
+
+        let mut columns = imp.columns.borrow_mut();
+
+        columns.push(self.add_name_column(&column_view));
+        columns.push(self.add_runtime_column(&column_view));
+        columns.push(self.add_state_column(&column_view));
+        columns.push(self.add_processes_column(&column_view));
+        columns.push(self.add_memory_column(&column_view));
+        columns.push(self.add_cpu_column(&column_view));
+        columns.push(self.add_read_speed_column(&column_view));
+        columns.push(self.add_write_speed_column(&column_view));
+
+        let store = gio::ListStore::new::<ContainerEntry>();
+
+        let filter_model = gtk::FilterListModel::new(
+            Some(store.clone()),
+            Some(gtk::CustomFilter::new(clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |obj| this.search_filter(obj)
+            ))),
+        );
+
+        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
+
+        let selection_model = gtk::SingleSelection::new(Some(sort_model.clone()));
+        selection_model.set_can_unselect(true);
+        selection_model.set_autoselect(false);
+
+        column_view.set_model(Some(&selection_model));
+
+        column_view.sort_by_column(
+            columns
+                .get(SETTINGS.containers_sort_by() as usize)
+                .or_else(|| columns.first()),
+            SETTINGS.containers_sort_by_ascending(),
+        );
+
+        column_view.add_css_class("resources-columnview");
+
+        *imp.store.borrow_mut() = store;
+        *imp.selection_model.borrow_mut() = selection_model;
+        *imp.sort_model.borrow_mut() = sort_model;
+        *imp.filter_model.borrow_mut() = filter_model;
+
+        imp.containers_scrolled_window
+            .set_child(Some(&*column_view));
+
+        imp.containers_stack.set_visible_child_name("empty");
+    }
+
+    pub fn setup_signals(&self) {
+        let imp = self.imp();
+
+        imp.selection_model
+            .borrow()
+            .connect_selection_changed(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_, _, _| {
+                    this.update_action_sensitivity();
+                }
+            ));
+
+        imp.search_button.connect_toggled(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |button| {
+                let imp = this.imp();
+                imp.search_revealer.set_reveal_child(button.is_active());
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+                if button.is_active() {
+                    imp.search_entry.grab_focus();
+                }
+            }
+        ));
+
+        imp.search_entry.connect_search_changed(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                let imp = this.imp();
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+            }
+        ));
+
+        let event_controller = EventControllerKey::new();
+        event_controller.connect_key_released(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_, key, _, _| {
+                if key.name().unwrap_or_default() == "Escape" {
+                    this.close_search();
+                }
+            }
+        ));
+        imp.search_entry.add_controller(event_controller);
+
+        imp.stop_container_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                if let Some(container) = this.get_selected_container_entry() {
+                    this.open_container_action_dialog(&container, ProcessAction::TERM);
+                }
+            }
+        ));
+
+        if let Some(column_view_sorter) = imp.column_view.borrow().sorter() {
+            column_view_sorter.connect_changed(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |sorter, _| {
+                    if let Some(sorter) = sorter.downcast_ref::<gtk::ColumnViewSorter>() {
+                        let current_column = sorter
+                            .primary_sort_column()
+                            .map(|column| column.as_ptr() as usize)
+                            .unwrap_or_default();
+
+                        let current_column_number = this
+                            .imp()
+                            .columns
+                            .borrow()
+                            .iter()
+                            .enumerate()
+                            .find(|(_, column)| column.as_ptr() as usize == current_column)
+                            .map_or(0, |(i, _)| i as u32); // 0 corresponds to the name column
+
+                        if SETTINGS.containers_sort_by() != current_column_number {
+                            let _ = SETTINGS.set_containers_sort_by(current_column_number);
+                        }
+
+                        if SETTINGS.containers_sort_by_ascending() != sorter.primary_sort_order() {
+                            let _ = SETTINGS
+                                .set_containers_sort_by_ascending(sorter.primary_sort_order());
+                        }
+                    }
+                }
+            ));
+        }
+    }
+
+    /// Only allows pausing running and resuming paused containers
+    fn update_action_sensitivity(&self) {
+        let imp = self.imp();
+
+        let selected = self.get_selected_container_entry();
+
+        imp.stop_container_button.set_sensitive(selected.is_some());
+
+        let paused = selected.as_ref().map(ContainerEntry::paused);
+        self.action_set_enabled("containers.kill-container", selected.is_some());
+        self.action_set_enabled("containers.pause-container", paused == Some(false));
+        self.action_set_enabled("containers.resume-container", paused == Some(true));
+    }
+
+    fn search_filter(&self, obj: &Object) -> bool {
+        let imp = self.imp();
+        let item = obj.downcast_ref::<ContainerEntry>().unwrap();
+        let search_string = imp.search_entry.text().to_string().to_lowercase();
+        !imp.search_revealer.reveals_child()
+            || item.name().to_lowercase().contains(&search_string)
+            || item.id().to_lowercase().contains(&search_string)
+            || item.runtime().to_lowercase().contains(&search_string)
+    }
+
+    pub fn get_selected_container_entry(&self) -> Option<ContainerEntry> {
+        self.imp()
+            .selection_model
+            .borrow()
+            .selected_item()
+            .and_then(|object| object.downcast::<ContainerEntry>().ok())
+    }
+
+    pub fn refresh_containers_list(&self, apps_context: &AppsContext) {
+        let imp = self.imp();
+
+        let store = imp.store.borrow_mut();
+
+        let mut already_existing_keys = HashSet::new();
+
+        // update the entries of containers that are still running
+        for entry in store.iter::<ContainerEntry>().flatten() {
+            if let Some(container) = apps_context.get_container(&entry.key()) {
+                entry.update(container, apps_context);
+                already_existing_keys.insert(entry.key().to_string());
+            }
+        }
+
+        // remove containers that recently have stopped running
+        store.retain(|object| {
+            already_existing_keys.contains(
+                object
+                    .downcast_ref::<ContainerEntry>()
+                    .unwrap()
+                    .key()
+                    .as_str(),
+            )
+        });
+
+        if imp
+            .popped_over_container
+            .borrow()
+            .as_ref()
+            .is_some_and(|entry| !already_existing_keys.contains(entry.key().as_str()))
+        {
+            *imp.popped_over_container.borrow_mut() = None;
+        }
+
+        // add the newly started containers to the store
+        let items: Vec<ContainerEntry> = apps_context
+            .containers_iter()
+            .filter(|container| !already_existing_keys.contains(&container.key()))
+            .map(|new_item| ContainerEntry::new(new_item, apps_context))
+            .collect();
+        store.extend_from_slice(&items);
+
+        if let Some(sorter) = imp.column_view.borrow().sorter() {
+            sorter.changed(gtk::SorterChange::Different);
+        }
+
+        imp.containers_stack
+            .set_visible_child_name(if store.n_items() == 0 {
+                "empty"
+            } else {
+                "containers"
+            });
+
+        self.set_tab_usage_string(i18n_f(
+            "Running Containers: {}",
+            &[&store.n_items().to_string()],
+        ));
+
+        std::mem::drop(store);
+
+        // containers might have been paused or resumed in the meantime
+        self.update_action_sensitivity();
+    }
+
+    pub fn open_container_action_dialog(&self, container: &ContainerEntry, action: ProcessAction) {
+        // Nothing too bad can happen on Resume so dont show the dialog
+        if action == ProcessAction::CONT {
+            let main_context = MainContext::default();
+            main_context.spawn_local(clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[weak]
+                container,
+                async move {
+                    let imp = this.imp();
+                    let _ = imp
+                        .sender
+                        .get()
+                        .unwrap()
+                        .send(Action::ManipulateContainer(
+                            action,
+                            container.key().to_string(),
+                            imp.toast_overlay.get(),
+                        ))
+                        .await;
+                }
+            ));
+            return;
+        }
+
+        // Confirmation dialog & warning
+        let dialog = adw::AlertDialog::builder()
+            .heading(get_action_name(action, &container.name()))
+            .body(get_action_warning(action))
+            .build();
+
+        dialog.add_response("yes", &get_action_description(action));
+        dialog.set_response_appearance("yes", ResponseAppearance::Destructive);
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.set_default_response(Some("no"));
+        dialog.set_close_response("no");
+
+        // Called when "yes" or "no" were clicked
+        dialog.connect_response(
+            None,
+            clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[weak]
+                container,
+                move |_, response| {
+                    if response == "yes" {
+                        let main_context = MainContext::default();
+                        main_context.spawn_local(clone!(
+                            #[weak]
+                            this,
+                            #[strong]
+                            container,
+                            async move {
+                                let imp = this.imp();
+                                let _ = imp
+                                    .sender
+                                    .get()
+                                    .unwrap()
+                                    .send(Action::ManipulateContainer(
+                                        action,
+                                        container.key().to_string(),
+                                        imp.toast_overlay.get(),
+                                    ))
+                                    .await;
+                            }
+                        ));
+                    }
+                }
+            ),
+        );
+
+        dialog.present(Some(&MainWindow::default()));
+    }
+
+    fn add_name_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let name_col_factory = gtk::SignalListItemFactory::new();
+
+        let name_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Container")), Some(name_col_factory.clone()));
+
+        name_col.set_resizable(true);
+
+        name_col.set_expand(true);
+
+        name_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(12);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("name")
+                    .bind(&row, "text", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("id")
+                    .bind(&row, "tooltip-text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        name_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let name_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "name",
+            ))
+            .build();
+
+        name_col.set_sorter(Some(&name_col_sorter));
+
+        column_view.append_column(&name_col);
+
+        name_col
+    }
+
+    fn add_runtime_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let runtime_col_factory = gtk::SignalListItemFactory::new();
+
+        let runtime_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Runtime")), Some(runtime_col_factory.clone()));
+
+        runtime_col.set_resizable(true);
+
+        runtime_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("runtime")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        runtime_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let runtime_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "runtime",
+            ))
+            .build();
+
+        runtime_col.set_sorter(Some(&runtime_col_sorter));
+
+        column_view.append_column(&runtime_col);
+
+        runtime_col
+    }
+
+    fn add_state_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let state_col_factory = gtk::SignalListItemFactory::new();
+
+        let state_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("State")), Some(state_col_factory.clone()));
+
+        state_col.set_resizable(true);
+
+        state_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("paused")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, paused: bool| {
+                        if paused {
+                            i18n("Paused")
+                        } else {
+                            i18n("Running")
+                        }
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        state_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let state_col_sorter = gtk::CustomSorter::new(|a, b| {
+            let a = a.downcast_ref::<ContainerEntry>().unwrap().paused();
+            let b = b.downcast_ref::<ContainerEntry>().unwrap().paused();
+            a.cmp(&b).into()
+        });
+
+        state_col.set_sorter(Some(&state_col_sorter));
+
+        column_view.append_column(&state_col);
+
+        state_col
+    }
+
+    fn add_processes_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let processes_col_factory = gtk::SignalListItemFactory::new();
+
+        let processes_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Processes")),
+            Some(processes_col_factory.clone()),
+        );
+
+        processes_col.set_resizable(true);
+
+        processes_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(5);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("running_processes")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, running_processes: u32| {
+                            running_processes.to_string()
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        processes_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let processes_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "running_processes",
+            ))
+            .build();
+
+        processes_col.set_sorter(Some(&processes_col_sorter));
+
+        column_view.append_column(&processes_col);
+
+        processes_col
+    }
+
+    fn add_memory_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let memory_col_factory = gtk::SignalListItemFactory::new();
+
+        let memory_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Memory")), Some(memory_col_factory.clone()));
+
+        memory_col.set_resizable(true);
+
+        memory_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("memory_usage")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: u64| {
+                        convert_storage(memory_usage as f64, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        memory_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let memory_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "memory_usage",
+            ))
+            .build();
+
+        memory_col.set_sorter(Some(&memory_col_sorter));
+
+        column_view.append_column(&memory_col);
+
+        memory_col
+    }
+
+    fn add_cpu_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let cpu_col_factory = gtk::SignalListItemFactory::new();
+
+        let cpu_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Processor")), Some(cpu_col_factory.clone()));
+
+        cpu_col.set_resizable(true);
+
+        cpu_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("cpu_usage")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
+                        let mut percentage = cpu_usage * 100.0;
+                        if !SETTINGS.normalize_cpu_usage() {
+                            percentage *= *NUM_CPUS as f32;
+                        }
+
+                        format!("{percentage:.1} %")
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        cpu_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let cpu_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "cpu_usage",
+            ))
+            .build();
+
+        cpu_col.set_sorter(Some(&cpu_col_sorter));
+
+        column_view.append_column(&cpu_col);
+
+        cpu_col
+    }
+
+    fn add_read_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let read_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let read_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Read")),
+            Some(read_speed_col_factory.clone()),
+        );
+
+        read_speed_col.set_resizable(true);
+
+        read_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("read_speed")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, read_speed: f64| {
+                        convert_speed(read_speed, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        read_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let read_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "read_speed",
+            ))
+            .build();
+
+        read_speed_col.set_sorter(Some(&read_speed_col_sorter));
+
+        column_view.append_column(&read_speed_col);
+
+        read_speed_col
+    }
+
+    fn add_write_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let write_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let write_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Write")),
+            Some(write_speed_col_factory.clone()),
+        );
+
+        write_speed_col.set_resizable(true);
+
+        write_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<ContainerEntry>("write_speed")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, write_speed: f64| {
+                        convert_speed(write_speed, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        write_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let write_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ContainerEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_speed",
+            ))
+            .build();
+
+        write_speed_col.set_sorter(Some(&write_speed_col_sorter));
+
+        column_view.append_column(&write_speed_col);
+
+        write_speed_col
+    }
+}
+
+fn get_action_name(action: ProcessAction, name: &str) -> String {
+    match action {
+        ProcessAction::TERM => i18n_f("Stop {}?", &[name]),
+        ProcessAction::STOP => i18n_f("Pause {}?", &[name]),
+        ProcessAction::KILL => i18n_f("Kill {}?", &[name]),
+        ProcessAction::CONT => i18n_f("Resume {}?", &[name]),
+    }
+}
+
+fn get_action_warning(action: ProcessAction) -> String {
+    match action {
+            ProcessAction::TERM => i18n("All processes of the container will be asked to end. Depending on its restart policy, the container might be restarted by its runtime."),
+            ProcessAction::STOP => i18n("Pausing a container halts all of its processes, which might cause connections to it to time out."),
+            ProcessAction::KILL => i18n("Killing a container can come with serious risks such as losing data. Use with caution."),
+            ProcessAction::CONT => String::new(),
+        }
+}
+
+fn get_action_description(action: ProcessAction) -> String {
+    match action {
+        ProcessAction::TERM => i18n("Stop Container"),
+        ProcessAction::STOP => i18n("Pause Container"),
+        ProcessAction::KILL => i18n("Kill Container"),
+        ProcessAction::CONT => i18n("Resume Container"),
+    }
+}
@@ -1,11 +1,19 @@
 use std::{collections::HashMap, sync::LazyLock};
 
-use process_data::Niceness;
+use process_data::{
+    details::FileKind,
+    memory_maps::MappingCategory,
+    sockets::{ListeningPort, Protocol, SocketState},
+    wait_state::{syscall_name, SyscallState},
+    Containerization, IoPriority, IoPriorityClass, Niceness, ProcessState, SchedulingPolicy,
+};
 
-use crate::i18n::pi18n;
+use crate::i18n::{i18n, i18n_f, pi18n};
+use crate::utils::systemd::UnitKind;
 
 pub mod applications;
 pub mod battery;
+pub mod containers;
 pub mod cpu;
 pub mod drive;
 pub mod gpu;
@@ -13,16 +21,19 @@ pub mod memory;
 pub mod network;
 pub mod npu;
 pub mod processes;
+pub mod services;
 
 const APPLICATIONS_PRIMARY_ORD: u32 = 0;
 const PROCESSES_PRIMARY_ORD: u32 = 1;
-const CPU_PRIMARY_ORD: u32 = 2;
-const MEMORY_PRIMARY_ORD: u32 = 3;
-const GPU_PRIMARY_ORD: u32 = 4;
-const NPU_PRIMARY_ORD: u32 = 5;
-const DRIVE_PRIMARY_ORD: u32 = 6;
-const NETWORK_PRIMARY_ORD: u32 = 7;
-const BATTERY_PRIMARY_ORD: u32 = 8;
+const CONTAINERS_PRIMARY_ORD: u32 = 2;
+const SERVICES_PRIMARY_ORD: u32 = 3;
+const CPU_PRIMARY_ORD: u32 = 4;
+const MEMORY_PRIMARY_ORD: u32 = 5;
+const GPU_PRIMARY_ORD: u32 = 6;
+const NPU_PRIMARY_ORD: u32 = 7;
+const DRIVE_PRIMARY_ORD: u32 = 8;
+const NETWORK_PRIMARY_ORD: u32 = 9;
+const BATTERY_PRIMARY_ORD: u32 = 10;
 
 pub static NICE_TO_LABEL: LazyLock<HashMap<Niceness, (String, u32)>> = LazyLock::new(|| {
     let mut hash_map = HashMap::new();
@@ -64,3 +75,230 @@ pub static NICE_TO_LABEL: LazyLock<HashMap<Niceness, (String, u32)>> = LazyLock:
 
     hash_map
 });
+
+pub fn process_state_label(state: ProcessState) -> String {
+    match state {
+        ProcessState::Running => pi18n("process state", "Running"),
+        ProcessState::Sleeping => pi18n("process state", "Sleeping"),
+        ProcessState::DiskSleep => pi18n("process state", "Waiting for I/O"),
+        ProcessState::Zombie => pi18n("process state", "Zombie"),
+        ProcessState::Stopped => pi18n("process state", "Stopped"),
+        ProcessState::TracingStop => pi18n("process state", "Traced"),
+        ProcessState::Dead => pi18n("process state", "Dead"),
+        ProcessState::Idle => pi18n("process state", "Idle"),
+        ProcessState::Unknown => pi18n("process state", "Unknown"),
+    }
+}
+
+/// The kernel reports processes that are frozen by the cgroup freezer as sleeping, so they get a state of their own
+pub fn frozen_state_label() -> String {
+    pi18n("process state", "Frozen")
+}
+
+pub fn io_priority_class_label(class: IoPriorityClass) -> String {
+    match class {
+        IoPriorityClass::None => pi18n("I/O priority class", "Default"),
+        IoPriorityClass::RealTime => pi18n("I/O priority class", "Real-Time"),
+        IoPriorityClass::BestEffort => pi18n("I/O priority class", "Best Effort"),
+        IoPriorityClass::Idle => pi18n("I/O priority class", "Idle"),
+    }
+}
+
+pub fn io_priority_label(io_priority: IoPriority) -> String {
+    match io_priority.class {
+        // the level has no meaning for these classes
+        IoPriorityClass::None | IoPriorityClass::Idle => io_priority_class_label(io_priority.class),
+        class => i18n_f(
+            "{} ({})",
+            &[
+                &io_priority_class_label(class),
+                &io_priority.level.to_string(),
+            ],
+        ),
+    }
+}
+
+pub fn scheduling_policy_label(policy: SchedulingPolicy, realtime_priority: u32) -> String {
+    let label = match policy {
+        SchedulingPolicy::Other => pi18n("scheduling policy", "Normal"),
+        SchedulingPolicy::Fifo => pi18n("scheduling policy", "FIFO"),
+        SchedulingPolicy::RoundRobin => pi18n("scheduling policy", "Round Robin"),
+        SchedulingPolicy::Batch => pi18n("scheduling policy", "Batch"),
+        SchedulingPolicy::Idle => pi18n("scheduling policy", "Idle"),
+        SchedulingPolicy::Deadline => pi18n("scheduling policy", "Deadline"),
+        SchedulingPolicy::Unknown => pi18n("scheduling policy", "Unknown"),
+    };
+
+    if policy.is_realtime() {
+        i18n_f("{} ({})", &[&label, &realtime_priority.to_string()])
+    } else {
+        label
+    }
+}
+
+pub fn containerization_label(containerization: Containerization) -> String {
+    match containerization {
+        Containerization::None => i18n("No"),
+        Containerization::Flatpak => i18n("Yes (Flatpak)"),
+        Containerization::Snap => i18n("Yes (Snap)"),
+        Containerization::Docker => i18n("Yes (Docker)"),
+        Containerization::Podman => i18n("Yes (Podman)"),
+        Containerization::Lxc => i18n("Yes (LXC)"),
+        Containerization::Nspawn => i18n("Yes (systemd-nspawn)"),
+        Containerization::Toolbox => i18n("Yes (Toolbox)"),
+        Containerization::Distrobox => i18n("Yes (Distrobox)"),
+    }
+}
+
+/// Returns the name of the runtime of a container, empty for processes that aren't running in one
+pub fn container_runtime_label(containerization: Containerization) -> &'static str {
+    match containerization {
+        Containerization::None | Containerization::Flatpak | Containerization::Snap => "",
+        Containerization::Docker => "Docker",
+        Containerization::Podman => "Podman",
+        Containerization::Lxc => "LXC",
+        Containerization::Nspawn => "systemd-nspawn",
+        Containerization::Toolbox => "Toolbox",
+        Containerization::Distrobox => "Distrobox",
+    }
+}
+
+pub fn unit_kind_label(kind: UnitKind) -> String {
+    match kind {
+        UnitKind::Service => pi18n("systemd unit type", "Service"),
+        UnitKind::Slice => pi18n("systemd unit type", "Slice"),
+        UnitKind::Scope => pi18n("systemd unit type", "Scope"),
+    }
+}
+
+pub fn file_kind_label(kind: FileKind) -> String {
+    match kind {
+        FileKind::File => pi18n("file type", "File"),
+        FileKind::Directory => pi18n("file type", "Directory"),
+        FileKind::CharDevice => pi18n("file type", "Character Device"),
+        FileKind::BlockDevice => pi18n("file type", "Block Device"),
+        FileKind::Socket => pi18n("file type", "Socket"),
+        FileKind::Pipe => pi18n("file type", "Pipe"),
+        FileKind::AnonInode => pi18n("file type", "Anonymous Inode"),
+        FileKind::Other => pi18n("file type", "Other"),
+    }
+}
+
+/// Formats the flags a file has been opened with, e.g. `O_RDWR | O_CLOEXEC`
+pub fn open_flags_label(flags: u32) -> String {
+    const FLAGS: [(libc::c_int, &str); 9] = [
+        (libc::O_CREAT, "O_CREAT"),
+        (libc::O_EXCL, "O_EXCL"),
+        (libc::O_NOCTTY, "O_NOCTTY"),
+        (libc::O_APPEND, "O_APPEND"),
+        (libc::O_NONBLOCK, "O_NONBLOCK"),
+        (libc::O_DSYNC, "O_DSYNC"),
+        (libc::O_DIRECT, "O_DIRECT"),
+        (libc::O_PATH, "O_PATH"),
+        (libc::O_CLOEXEC, "O_CLOEXEC"),
+    ];
+
+    let flags = flags as libc::c_int;
+
+    let access_mode = match flags & libc::O_ACCMODE {
+        libc::O_WRONLY => "O_WRONLY",
+        libc::O_RDWR => "O_RDWR",
+        _ => "O_RDONLY",
+    };
+
+    std::iter::once(access_mode)
+        .chain(
+            FLAGS
+                .iter()
+                .filter(|(flag, _)| flags & flag == *flag)
+                .map(|(_, name)| *name),
+        )
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+pub fn protocol_label(protocol: Protocol) -> &'static str {
+    match protocol {
+        Protocol::Tcp => "TCP",
+        Protocol::Tcp6 => "TCPv6",
+        Protocol::Udp => "UDP",
+        Protocol::Udp6 => "UDPv6",
+        Protocol::Unix => "Unix",
+    }
+}
+
+pub fn socket_state_label(state: SocketState) -> String {
+    match state {
+        SocketState::Established => pi18n("socket state", "Established"),
+        SocketState::SynSent => pi18n("socket state", "SYN Sent"),
+        SocketState::SynReceived => pi18n("socket state", "SYN Received"),
+        SocketState::FinWait1 => pi18n("socket state", "FIN Wait 1"),
+        SocketState::FinWait2 => pi18n("socket state", "FIN Wait 2"),
+        SocketState::TimeWait => pi18n("socket state", "Time Wait"),
+        SocketState::Close => pi18n("socket state", "Closed"),
+        SocketState::CloseWait => pi18n("socket state", "Close Wait"),
+        SocketState::LastAck => pi18n("socket state", "Last ACK"),
+        SocketState::Listen => pi18n("socket state", "Listening"),
+        SocketState::Closing => pi18n("socket state", "Closing"),
+        SocketState::Connected => pi18n("socket state", "Connected"),
+        SocketState::Unconnected => pi18n("socket state", "Unconnected"),
+        SocketState::Unknown => pi18n("socket state", "Unknown"),
+    }
+}
+
+/// Formats listening ports like `8080/tcp, 53/udp`, IPv4 and IPv6 sockets on the same port are only listed once
+pub fn listening_ports_label(listening_ports: &[ListeningPort]) -> String {
+    let mut ports: Vec<(u16, &str)> = listening_ports
+        .iter()
+        .map(|listening_port| {
+            let protocol = if listening_port.protocol.is_tcp() {
+                "tcp"
+            } else {
+                "udp"
+            };
+            (listening_port.port, protocol)
+        })
+        .collect();
+
+    ports.sort_unstable();
+    ports.dedup();
+
+    ports
+        .into_iter()
+        .map(|(port, protocol)| format!("{port}/{protocol}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub fn mapping_category_label(category: MappingCategory) -> String {
+    match category {
+        MappingCategory::Heap => pi18n("memory mapping category", "Heap"),
+        MappingCategory::Stack => pi18n("memory mapping category", "Stack"),
+        MappingCategory::Anonymous => pi18n("memory mapping category", "Anonymous"),
+        MappingCategory::File => pi18n("memory mapping category", "File-Backed"),
+        MappingCategory::SharedLibrary => pi18n("memory mapping category", "Shared Libraries"),
+        MappingCategory::Other => pi18n("memory mapping category", "Other"),
+    }
+}
+
+/// Formats the system call a process is currently in, e.g. `read(0x3, 0x7ffd2b5fd8e8, 0x1000, 0x0, 0x0, 0x0)`
+pub fn syscall_label(syscall: SyscallState) -> String {
+    match syscall {
+        SyscallState::Running => pi18n("system call", "Running"),
+        SyscallState::NoSyscall => pi18n("system call", "None"),
+        SyscallState::Syscall { number, arguments } => {
+            let name = syscall_name(number).map_or_else(
+                || i18n_f("System Call {}", &[&number.to_string()]),
+                ToString::to_string,
+            );
+
+            let arguments = arguments
+                .iter()
+                .map(|argument| format!("{argument:#x}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            format!("{name}({arguments})")
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use gtk::glib;
+
+mod imp {
+    use std::cell::Cell;
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use super::*;
+
+    #[derive(Properties, Default)]
+    #[properties(wrapper_type = super::EnvironmentVariableEntry)]
+    pub struct EnvironmentVariableEntry {
+        #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
+        name: Cell<glib::GString>,
+
+        #[property(get = Self::value, set = Self::set_value, type = glib::GString)]
+        value: Cell<glib::GString>,
+    }
+
+    impl EnvironmentVariableEntry {
+        gstring_getter_setter!(name, value);
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for EnvironmentVariableEntry {
+        const NAME: &'static str = "EnvironmentVariableEntry";
+        type Type = super::EnvironmentVariableEntry;
+    }
+
+    impl ObjectImpl for EnvironmentVariableEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct EnvironmentVariableEntry(ObjectSubclass<imp::EnvironmentVariableEntry>);
+}
+
+impl EnvironmentVariableEntry {
+    pub fn new(name: &str, value: &str) -> Self {
+        glib::Object::builder()
+            .property("name", name)
+            .property("value", value)
+            .build()
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::{BTreeMap, HashSet};
+
+use gtk::{gio, glib, prelude::*, subclass::prelude::ObjectSubclassIsExt};
+use process_data::memory_maps::{MappingCategory, MappingUsage, MemoryMapping};
+
+use crate::{i18n::i18n, ui::pages::mapping_category_label};
+
+mod imp {
+    use std::cell::{Cell, RefCell};
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use super::*;
+
+    #[derive(Properties)]
+    #[properties(wrapper_type = super::MemoryMapEntry)]
+    pub struct MemoryMapEntry {
+        /// Identifies the entry among its siblings
+        #[property(get = Self::key, set = Self::set_key, type = glib::GString)]
+        key: Cell<glib::GString>,
+
+        #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
+        name: Cell<glib::GString>,
+
+        #[property(get = Self::permissions, set = Self::set_permissions, type = glib::GString)]
+        permissions: Cell<glib::GString>,
+
+        #[property(get, set)]
+        rss: Cell<u64>,
+
+        #[property(get, set)]
+        pss: Cell<u64>,
+
+        #[property(get, set)]
+        swap: Cell<u64>,
+
+        #[property(get, set)]
+        dirty: Cell<u64>,
+
+        #[property(get, set)]
+        has_children: Cell<bool>,
+
+        pub children: RefCell<gio::ListStore>,
+    }
+
+    impl Default for MemoryMapEntry {
+        fn default() -> Self {
+            Self {
+                key: Cell::new(glib::GString::default()),
+                name: Cell::new(glib::GString::default()),
+                permissions: Cell::new(glib::GString::default()),
+                rss: Cell::new(0),
+                pss: Cell::new(0),
+                swap: Cell::new(0),
+                dirty: Cell::new(0),
+                has_children: Cell::new(false),
+                children: RefCell::new(gio::ListStore::new::<super::MemoryMapEntry>()),
+            }
+        }
+    }
+
+    impl MemoryMapEntry {
+        gstring_getter_setter!(key, name, permissions);
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for MemoryMapEntry {
+        const NAME: &'static str = "MemoryMapEntry";
+        type Type = super::MemoryMapEntry;
+    }
+
+    impl ObjectImpl for MemoryMapEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct MemoryMapEntry(ObjectSubclass<imp::MemoryMapEntry>);
+}
+
+impl MemoryMapEntry {
+    pub fn new(node: &MemoryMapNode) -> Self {
+        let this: Self = glib::Object::builder()
+            .property("key", node.key.as_str())
+            .build();
+        this.update(node);
+        this
+    }
+
+    pub fn update(&self, node: &MemoryMapNode) {
+        self.set_name(node.name.as_str());
+        self.set_permissions(node.permissions.as_str());
+        self.set_rss(node.usage.rss as u64);
+        self.set_pss(node.usage.pss as u64);
+        self.set_swap(node.usage.swap as u64);
+        self.set_dirty(node.usage.dirty as u64);
+        self.set_has_children(!node.children.is_empty());
+
+        sync_memory_map_store(&self.children(), &node.children);
+    }
+
+    pub fn children(&self) -> gio::ListStore {
+        self.imp().children.borrow().clone()
+    }
+}
+
+/// A node of the memory map tree. The tree consists of the mapping categories at the top, the files (or pseudo-paths
+/// like `[heap]`) within these categories below and the individual mappings of these files at the bottom.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryMapNode {
+    pub key: String,
+    pub name: String,
+    pub permissions: String,
+    pub usage: MappingUsage,
+    pub children: Vec<MemoryMapNode>,
+}
+
+impl MemoryMapNode {
+    fn with_children(key: String, name: String, children: Vec<MemoryMapNode>) -> Self {
+        let mut usage = MappingUsage::default();
+        for child in &children {
+            usage += child.usage;
+        }
+
+        Self {
+            key,
+            name,
+            permissions: String::new(),
+            usage,
+            children,
+        }
+    }
+}
+
+/// Groups `mappings` by category and file, the usages of the groups are the sums of their mappings
+pub fn memory_map_tree(mappings: &[MemoryMapping]) -> Vec<MemoryMapNode> {
+    let mut groups: BTreeMap<MappingCategory, BTreeMap<Option<&str>, Vec<MemoryMapNode>>> =
+        BTreeMap::new();
+
+    for mapping in mappings {
+        let node = MemoryMapNode {
+            key: format!("{:x}", mapping.start),
+            name: format!("{:x}–{:x}", mapping.start, mapping.end),
+            permissions: mapping.permissions.clone(),
+            usage: mapping.usage,
+            children: Vec::new(),
+        };
+
+        groups
+            .entry(mapping.category)
+            .or_default()
+            .entry(mapping.path.as_deref())
+            .or_default()
+            .push(node);
+    }
+
+    groups
+        .into_iter()
+        .map(|(category, files)| {
+            let file_nodes = files
+                .into_iter()
+                .map(|(path, mapping_nodes)| {
+                    MemoryMapNode::with_children(
+                        path.unwrap_or_default().to_string(),
+                        path.map_or_else(|| i18n("Anonymous"), ToString::to_string),
+                        mapping_nodes,
+                    )
+                })
+                .collect();
+
+            MemoryMapNode::with_children(
+                format!("{category:?}"),
+                mapping_category_label(category),
+                file_nodes,
+            )
+        })
+        .collect()
+}
+
+/// Updates the entries in `store` to match `nodes`, keeping the entries (and thus the expansion state of their rows)
+/// that are still present
+pub fn sync_memory_map_store(store: &gio::ListStore, nodes: &[MemoryMapNode]) {
+    let mut already_existing_keys = HashSet::new();
+
+    for entry in store.iter::<MemoryMapEntry>().flatten() {
+        if let Some(node) = nodes.iter().find(|node| node.key == entry.key()) {
+            entry.update(node);
+            already_existing_keys.insert(node.key.as_str());
+        }
+    }
+
+    store.retain(|object| {
+        already_existing_keys.contains(
+            object
+                .downcast_ref::<MemoryMapEntry>()
+                .unwrap()
+                .key()
+                .as_str(),
+        )
+    });
+
+    let new_entries: Vec<MemoryMapEntry> = nodes
+        .iter()
+        .filter(|node| !already_existing_keys.contains(node.key.as_str()))
+        .map(MemoryMapEntry::new)
+        .collect();
+    store.extend_from_slice(&new_entries);
+}
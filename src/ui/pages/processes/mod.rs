@@ -1,7 +1,12 @@
+pub mod environment_variable_entry;
+pub mod memory_map_entry;
+pub mod open_file_entry;
 pub mod process_entry;
 mod process_name_cell;
+pub mod socket_entry;
+pub mod thread_entry;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::LazyLock;
 
 use adw::ResponseAppearance;
@@ -12,7 +17,7 @@ use gtk::{
     gio, BitsetIter, ColumnView, ColumnViewColumn, EventControllerKey, FilterChange, ListItem,
     NumericSorter, SortType, StringSorter, Widget,
 };
-use process_data::Niceness;
+use process_data::{details::ProcessDetails, Niceness, ProcessDataOptions};
 
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f, ni18n_f};
@@ -21,9 +26,9 @@ use crate::ui::dialogs::process_options_dialog::ResProcessOptionsDialog;
 use crate::ui::pages::NICE_TO_LABEL;
 use crate::ui::window::{Action, MainWindow};
 use crate::utils::app::AppsContext;
-use crate::utils::process::ProcessAction;
-use crate::utils::settings::SETTINGS;
-use crate::utils::units::{convert_speed, convert_storage, format_time};
+use crate::utils::process::{process_tree_pids, signals, Process, ProcessAction};
+use crate::utils::settings::{MemoryMetric, SETTINGS};
+use crate::utils::units::{convert_rate, convert_speed, convert_storage, format_time};
 use crate::utils::NUM_CPUS;
 
 use self::process_entry::ProcessEntry;
@@ -31,6 +36,9 @@ use self::process_name_cell::ResProcessNameCell;
 
 pub const TAB_ID: &str = "processes";
 
+/// Position of the first I/O accounting column in the column view, right after the drive write total column
+const IO_ACCOUNTING_COLUMNS_POSITION: u32 = 9;
+
 static LONGEST_PRIORITY_LABEL: LazyLock<u32> = LazyLock::new(|| {
     // make sure that no matter how short the longest current locale's translation for a priority may be, a signed
     // two-digit number (+ 1 for more space) will always fit
@@ -89,6 +97,12 @@ mod imp {
         #[template_child]
         pub search_button: TemplateChild<gtk::ToggleButton>,
         #[template_child]
+        pub tree_view_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub listening_ports_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub containers_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
         pub options_button: TemplateChild<gtk::Button>,
         #[template_child]
         pub information_button: TemplateChild<gtk::Button>,
@@ -99,6 +113,15 @@ mod imp {
         #[template_child]
         pub end_process_menu_multiple: TemplateChild<gio::MenuModel>,
         pub store: RefCell<gio::ListStore>,
+        pub tree_model: RefCell<Option<gtk::TreeListModel>>,
+        /// Contains the processes without a (living) parent process when in tree view
+        pub root_store: RefCell<gio::ListStore>,
+        /// Key: PID of the parent process
+        pub children_stores: RefCell<HashMap<i32, gio::ListStore>>,
+        /// Key: PID, Value: PID of the parent in whose children store the process is currently placed, `None` if it's
+        /// placed in the root store
+        pub tree_placements: RefCell<HashMap<i32, Option<i32>>>,
+        pub expanded_pids: RefCell<HashSet<i32>>,
         pub selection_model: RefCell<gtk::MultiSelection>,
         pub filter_model: RefCell<gtk::FilterListModel>,
         pub sort_model: RefCell<gtk::SortListModel>,
@@ -158,12 +181,20 @@ mod imp {
                 search_entry: Default::default(),
                 processes_scrolled_window: Default::default(),
                 search_button: Default::default(),
+                tree_view_button: Default::default(),
+                listening_ports_button: Default::default(),
+                containers_button: Default::default(),
                 options_button: Default::default(),
                 information_button: Default::default(),
                 end_process_button: Default::default(),
                 end_process_menu: Default::default(),
                 end_process_menu_multiple: Default::default(),
                 store: gio::ListStore::new::<ProcessEntry>().into(),
+                tree_model: Default::default(),
+                root_store: gio::ListStore::new::<ProcessEntry>().into(),
+                children_stores: Default::default(),
+                tree_placements: Default::default(),
+                expanded_pids: Default::default(),
                 selection_model: RefCell::new(glib::object::Object::new::<gtk::MultiSelection>()),
                 filter_model: Default::default(),
                 sort_model: Default::default(),
@@ -255,6 +286,44 @@ mod imp {
                 },
             );
 
+            klass.install_action(
+                "processes.context-send-signal",
+                None,
+                move |res_processes, _, _| {
+                    if let Some(process_entry) =
+                        res_processes.imp().popped_over_process.borrow().as_ref()
+                    {
+                        res_processes.open_send_signal_dialog(vec![process_entry.clone()]);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "processes.context-end-process-tree",
+                None,
+                move |res_processes, _, _| {
+                    if let Some(process_entry) =
+                        res_processes.imp().popped_over_process.borrow().as_ref()
+                    {
+                        res_processes
+                            .open_process_tree_action_dialog(process_entry, ProcessAction::TERM);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "processes.context-kill-process-tree",
+                None,
+                move |res_processes, _, _| {
+                    if let Some(process_entry) =
+                        res_processes.imp().popped_over_process.borrow().as_ref()
+                    {
+                        res_processes
+                            .open_process_tree_action_dialog(process_entry, ProcessAction::KILL);
+                    }
+                },
+            );
+
             klass.install_action(
                 "processes.context-information",
                 None,
@@ -319,6 +388,13 @@ mod imp {
                 },
             );
 
+            klass.install_action("processes.send-signal", None, move |res_processes, _, _| {
+                let selected = res_processes.get_selected_process_entries();
+                if !selected.is_empty() {
+                    res_processes.open_send_signal_dialog(selected);
+                }
+            });
+
             Self::bind_template(klass);
         }
 
@@ -388,6 +464,24 @@ impl ResProcesses {
 
         self.setup_widgets();
         self.setup_signals();
+        self.update_data_options();
+    }
+
+    /// Requests the optional parts of the process data that are needed for the currently shown columns and filters
+    fn update_data_options(&self) {
+        let imp = self.imp();
+
+        // the info dialog shows the waits of the inspected process regardless of the shown columns
+        let info_dialog_open =
+            imp.open_info_dialog.borrow().is_some() && !imp.info_dialog_closed.get();
+
+        Process::set_data_options(ProcessDataOptions {
+            listening_ports: SETTINGS.processes_show_listening_ports()
+                || imp.listening_ports_button.is_active(),
+            thread_delays: SETTINGS.processes_show_cpu_wait()
+                || SETTINGS.processes_show_io_wait()
+                || info_dialog_open,
+        });
     }
 
     fn add_gestures(&self, item: &ListItem) {
@@ -403,7 +497,7 @@ impl ResProcesses {
             #[weak(rename_to = this)]
             self,
             move |_, _, x, y| {
-                if let Some(entry) = item.item().and_downcast::<ProcessEntry>() {
+                if let Some(entry) = item.item().and_then(entry_from_row) {
                     let imp = this.imp();
 
                     let selected = this.get_selected_process_entries();
@@ -465,11 +559,37 @@ impl ResProcesses {
         columns.push(self.add_system_cpu_time_column(&column_view));
         columns.push(self.add_priority_column(&column_view));
         columns.push(self.add_swap_column(&column_view));
+        columns.push(self.add_state_column(&column_view));
+        columns.push(self.add_threads_column(&column_view));
+        columns.push(self.add_voluntary_switches_column(&column_view));
+        columns.push(self.add_involuntary_switches_column(&column_view));
+        columns.push(self.add_minor_faults_column(&column_view));
+        columns.push(self.add_major_faults_column(&column_view));
+
+        columns.push(self.add_subtree_cpu_column(&column_view));
+        columns.push(self.add_subtree_memory_column(&column_view));
+        columns.push(self.add_io_priority_column(&column_view));
+        columns.push(self.add_policy_column(&column_view));
+        columns.push(self.add_oom_score_column(&column_view));
+        columns.push(self.add_fd_count_column(&column_view));
+        columns.push(self.add_listening_ports_column(&column_view));
+        columns.push(self.add_logical_read_speed_column(&column_view));
+        columns.push(self.add_logical_write_speed_column(&column_view));
+        columns.push(self.add_read_syscall_rate_column(&column_view));
+        columns.push(self.add_write_syscall_rate_column(&column_view));
+
+        // the sort column is saved as its index in `columns`, so new columns have to be appended there, but the I/O
+        // accounting columns belong next to the drive columns
+        let io_accounting_columns = &columns[columns.len() - 4..];
+        for (i, column) in io_accounting_columns.iter().enumerate() {
+            column_view.insert_column(IO_ACCOUNTING_COLUMNS_POSITION + i as u32, column);
+        }
 
-        let store = gio::ListStore::new::<ProcessEntry>();
+        columns.push(self.add_cpu_wait_column(&column_view));
+        columns.push(self.add_io_wait_column(&column_view));
 
         let filter_model = gtk::FilterListModel::new(
-            Some(store.clone()),
+            None::<gio::ListModel>,
             Some(gtk::CustomFilter::new(clone!(
                 #[strong(rename_to = this)]
                 self,
@@ -477,7 +597,11 @@ impl ResProcesses {
             ))),
         );
 
-        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
+        // the tree list row sorter keeps children below their parents, in flat view every row is a root row
+        let sort_model = gtk::SortListModel::new(
+            Some(filter_model.clone()),
+            Some(gtk::TreeListRowSorter::new(column_view.sorter())),
+        );
 
         let selection_model = gtk::MultiSelection::new(Some(sort_model.clone()));
 
@@ -492,12 +616,16 @@ impl ResProcesses {
 
         column_view.add_css_class("resources-columnview");
 
-        *imp.store.borrow_mut() = store;
         *imp.selection_model.borrow_mut() = selection_model;
         *imp.sort_model.borrow_mut() = sort_model;
         *imp.filter_model.borrow_mut() = filter_model;
 
         imp.processes_scrolled_window.set_child(Some(&*column_view));
+
+        imp.tree_view_button
+            .set_active(SETTINGS.processes_tree_view());
+
+        self.setup_tree_model();
     }
 
     pub fn setup_signals(&self) {
@@ -540,6 +668,7 @@ impl ResProcesses {
                 if let Some(filter) = imp.filter_model.borrow().filter() {
                     filter.changed(FilterChange::Different);
                 }
+                this.expand_search_matches();
                 if button.is_active() {
                     imp.search_entry.grab_focus();
                 }
@@ -554,6 +683,54 @@ impl ResProcesses {
                 if let Some(filter) = imp.filter_model.borrow().filter() {
                     filter.changed(FilterChange::Different);
                 }
+                this.expand_search_matches();
+            }
+        ));
+
+        for button in [&*imp.listening_ports_button, &*imp.containers_button] {
+            button.connect_toggled(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_| {
+                    this.update_data_options();
+
+                    let imp = this.imp();
+                    if let Some(filter) = imp.filter_model.borrow().filter() {
+                        filter.changed(FilterChange::Different);
+                    }
+                    this.expand_search_matches();
+                }
+            ));
+        }
+
+        imp.tree_view_button.connect_toggled(|button| {
+            if SETTINGS.processes_tree_view() != button.is_active() {
+                let _ = SETTINGS.set_processes_tree_view(button.is_active());
+            }
+        });
+
+        SETTINGS.connect_processes_tree_view(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |tree_view| {
+                let imp = this.imp();
+
+                imp.tree_view_button.set_active(tree_view);
+
+                if tree_view {
+                    this.update_tree();
+                } else {
+                    this.remember_expanded_rows();
+                    imp.root_store.borrow().remove_all();
+                    imp.children_stores.borrow_mut().clear();
+                    imp.tree_placements.borrow_mut().clear();
+                }
+
+                this.setup_tree_model();
+
+                if tree_view {
+                    this.restore_expanded_rows();
+                }
             }
         ));
 
@@ -579,7 +756,7 @@ impl ResProcesses {
                     .selection_model
                     .borrow()
                     .item(bitset.maximum()) // the info button is only available when only 1 item is selected, so this should be fine
-                    .map(|object| object.downcast::<ProcessEntry>().unwrap());
+                    .and_then(entry_from_row);
                 if let Some(selection) = selection_option {
                     this.open_options_dialog(&selection);
                 }
@@ -596,7 +773,7 @@ impl ResProcesses {
                     .selection_model
                     .borrow()
                     .item(bitset.maximum()) // the info button is only available when only 1 item is selected, so this should be fine
-                    .map(|object| object.downcast::<ProcessEntry>().unwrap());
+                    .and_then(entry_from_row);
                 if let Some(selection) = selection_option {
                     this.open_info_dialog(&selection);
                 }
@@ -669,12 +846,16 @@ impl ResProcesses {
             #[weak(rename_to = this)]
             self,
             move |_| {
+                Process::set_inspected_pid(None);
                 this.imp().options_dialog_closed.set(true);
             }
         ));
 
         dialog.present(Some(&MainWindow::default()));
 
+        // the threads that can be adjusted individually are part of the details
+        Process::set_inspected_pid(Some(process.pid()));
+
         *imp.open_options_dialog.borrow_mut() = Some((process.pid(), dialog));
     }
 
@@ -689,28 +870,73 @@ impl ResProcesses {
 
         let dialog = ResProcessDialog::new();
 
-        dialog.init(process, process.user());
+        dialog.init(
+            process,
+            process.user(),
+            imp.sender.get().unwrap().clone(),
+            &imp.toast_overlay,
+        );
 
         dialog.connect_closed(clone!(
             #[weak(rename_to = this)]
             self,
             move |_| {
+                Process::set_inspected_pid(None);
                 this.imp().info_dialog_closed.set(true);
+                this.update_data_options();
             }
         ));
 
         dialog.present(Some(&MainWindow::default()));
 
+        Process::set_inspected_pid(Some(process.pid()));
+
         *imp.open_info_dialog.borrow_mut() = Some((process.pid(), dialog));
+
+        self.update_data_options();
     }
 
-    fn search_filter(&self, obj: &Object) -> bool {
+    fn process_filter(&self) -> ProcessFilter {
         let imp = self.imp();
-        let item = obj.downcast_ref::<ProcessEntry>().unwrap();
-        let search_string = imp.search_entry.text().to_string().to_lowercase();
-        !imp.search_revealer.reveals_child()
-            || item.name().to_lowercase().contains(&search_string)
-            || item.commandline().to_lowercase().contains(&search_string)
+
+        ProcessFilter {
+            search_string: imp
+                .search_revealer
+                .reveals_child()
+                .then(|| imp.search_entry.text().to_string().to_lowercase())
+                .filter(|search_string| !search_string.is_empty()),
+            listening_only: imp.listening_ports_button.is_active(),
+            containers_only: imp.containers_button.is_active(),
+        }
+    }
+
+    fn search_filter(&self, obj: &Object) -> bool {
+        let Some(item) = entry_from_row(obj.clone()) else {
+            return false;
+        };
+        let filter = self.process_filter();
+        !filter.is_active() || self.subtree_matches(&item, &filter)
+    }
+
+    /// Returns whether `entry` or, in tree view, any of its descendants match `filter`
+    fn subtree_matches(&self, entry: &ProcessEntry, filter: &ProcessFilter) -> bool {
+        filter.matches(entry) || self.children_match(entry, filter)
+    }
+
+    fn children_match(&self, entry: &ProcessEntry, filter: &ProcessFilter) -> bool {
+        let children_store = self
+            .imp()
+            .children_stores
+            .borrow()
+            .get(&entry.pid())
+            .cloned();
+
+        children_store.is_some_and(|children_store| {
+            children_store
+                .iter::<ProcessEntry>()
+                .flatten()
+                .any(|child| self.subtree_matches(&child, filter))
+        })
     }
 
     pub fn get_selected_process_entries(&self) -> Vec<ProcessEntry> {
@@ -724,7 +950,7 @@ impl ResProcesses {
                     imp.selection_model
                         .borrow()
                         .item(position)
-                        .map(|object| object.downcast::<ProcessEntry>().unwrap())
+                        .and_then(entry_from_row)
                 })
                 .collect();
 
@@ -732,7 +958,7 @@ impl ResProcesses {
                 .selection_model
                 .borrow()
                 .item(first)
-                .map(|object| object.downcast::<ProcessEntry>().unwrap())
+                .and_then(entry_from_row)
             {
                 return_vec.insert(0, first_process);
             }
@@ -743,6 +969,25 @@ impl ResProcesses {
         }
     }
 
+    /// Passes the details of the inspected process on to the info or options dialog if it's still open.
+    pub fn refresh_process_details(&self, details: Option<&ProcessDetails>) {
+        let Some(details) = details else {
+            return;
+        };
+
+        if let Some((dialog_pid, dialog)) = &*self.imp().open_info_dialog.borrow() {
+            if *dialog_pid == details.pid {
+                dialog.update_details(details);
+            }
+        }
+
+        if let Some((dialog_pid, dialog)) = &*self.imp().open_options_dialog.borrow() {
+            if *dialog_pid == details.pid {
+                dialog.update_threads(&details.threads);
+            }
+        }
+    }
+
     pub fn refresh_processes_list(&self, apps_context: &AppsContext) {
         let imp = self.imp();
 
@@ -813,16 +1058,259 @@ impl ResProcesses {
             .collect();
         store.extend_from_slice(&items);
 
+        let running_processes = store.n_items();
+
+        std::mem::drop(store);
+
+        if SETTINGS.processes_tree_view() {
+            // while searching or filtering, rows are expanded automatically and we don't want to remember that
+            if !imp.search_revealer.reveals_child()
+                && !imp.listening_ports_button.is_active()
+                && !imp.containers_button.is_active()
+            {
+                self.remember_expanded_rows();
+            }
+            self.update_tree();
+            self.restore_expanded_rows();
+            self.expand_search_matches();
+        }
+
         if let Some(sorter) = imp.column_view.borrow().sorter() {
             sorter.changed(gtk::SorterChange::Different);
         }
 
+        // unlike names, listening ports change all the time, so the filter has to be reevaluated
+        if imp.listening_ports_button.is_active() {
+            if let Some(filter) = imp.filter_model.borrow().filter() {
+                filter.changed(FilterChange::Different);
+            }
+        }
+
         self.set_tab_usage_string(i18n_f(
             "Running Processes: {}",
-            &[&(store.n_items()).to_string()],
+            &[&running_processes.to_string()],
         ));
     }
 
+    /// (Re-)creates the tree list model that feeds the column view. In flat view, its root model is the store of all
+    /// processes and no row can be expanded, in tree view its root model is the root store.
+    fn setup_tree_model(&self) {
+        let imp = self.imp();
+
+        let root_model = if SETTINGS.processes_tree_view() {
+            imp.root_store.borrow().clone()
+        } else {
+            imp.store.borrow().clone()
+        };
+
+        let tree_model = gtk::TreeListModel::new(
+            root_model,
+            false,
+            false,
+            clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[upgrade_or_default]
+                move |object| {
+                    if !SETTINGS.processes_tree_view() {
+                        return None;
+                    }
+
+                    object
+                        .downcast_ref::<ProcessEntry>()
+                        .map(|entry| this.children_store(entry.pid()).upcast::<gio::ListModel>())
+                }
+            ),
+        );
+
+        imp.filter_model.borrow().set_model(Some(&tree_model));
+
+        *imp.tree_model.borrow_mut() = Some(tree_model);
+    }
+
+    /// Returns the children store of the process with the given PID, creating it if it doesn't exist yet
+    fn children_store(&self, pid: i32) -> gio::ListStore {
+        self.imp()
+            .children_stores
+            .borrow_mut()
+            .entry(pid)
+            .or_insert_with(gio::ListStore::new::<ProcessEntry>)
+            .clone()
+    }
+
+    /// Moves every process entry into the children store of its parent (or into the root store if the parent is not
+    /// alive anymore) and calculates the aggregated usages of each subtree
+    fn update_tree(&self) {
+        let imp = self.imp();
+
+        let entries: HashMap<i32, ProcessEntry> = imp
+            .store
+            .borrow()
+            .iter::<ProcessEntry>()
+            .flatten()
+            .map(|entry| (entry.pid(), entry))
+            .collect();
+
+        let desired_placements: HashMap<i32, Option<i32>> = entries
+            .values()
+            .map(|entry| {
+                let parent_pid = entry.parent_pid();
+                let placement = (parent_pid != entry.pid() && entries.contains_key(&parent_pid))
+                    .then_some(parent_pid);
+                (entry.pid(), placement)
+            })
+            .collect();
+
+        let mut to_remove: HashMap<Option<i32>, HashSet<i32>> = HashMap::new();
+        let mut to_add: HashMap<Option<i32>, Vec<ProcessEntry>> = HashMap::new();
+
+        {
+            let mut placements = imp.tree_placements.borrow_mut();
+
+            for (pid, placement) in placements.iter() {
+                if desired_placements.get(pid) != Some(placement) {
+                    to_remove.entry(*placement).or_default().insert(*pid);
+                }
+            }
+
+            for (pid, placement) in &desired_placements {
+                if placements.get(pid) != Some(placement) {
+                    to_add
+                        .entry(*placement)
+                        .or_default()
+                        .push(entries[pid].clone());
+                }
+            }
+
+            placements.clone_from(&desired_placements);
+        }
+
+        // the stores must not be borrowed while they're being modified because the tree list model and the filter may
+        // call back into us
+        for (placement, pids) in to_remove {
+            let store = match placement {
+                Some(parent_pid) => imp.children_stores.borrow().get(&parent_pid).cloned(),
+                None => Some(imp.root_store.borrow().clone()),
+            };
+
+            if let Some(store) = store {
+                store.retain(|object| {
+                    !pids.contains(&object.downcast_ref::<ProcessEntry>().unwrap().pid())
+                });
+            }
+        }
+
+        imp.children_stores
+            .borrow_mut()
+            .retain(|pid, _| entries.contains_key(pid));
+
+        imp.expanded_pids
+            .borrow_mut()
+            .retain(|pid| entries.contains_key(pid));
+
+        for (placement, new_entries) in to_add {
+            let store = match placement {
+                Some(parent_pid) => self.children_store(parent_pid),
+                None => imp.root_store.borrow().clone(),
+            };
+
+            store.extend_from_slice(&new_entries);
+        }
+
+        let mut children: HashMap<i32, Vec<i32>> = HashMap::new();
+        for (pid, placement) in &desired_placements {
+            if let Some(parent_pid) = placement {
+                children.entry(*parent_pid).or_default().push(*pid);
+            }
+        }
+
+        for (pid, placement) in &desired_placements {
+            if placement.is_none() {
+                aggregate_subtree(*pid, &children, &entries);
+            }
+        }
+    }
+
+    /// Saves the expanded state of all rows that are currently visible in tree view
+    fn remember_expanded_rows(&self) {
+        let imp = self.imp();
+
+        let Some(tree_model) = imp.tree_model.borrow().clone() else {
+            return;
+        };
+
+        let mut expanded_pids = imp.expanded_pids.borrow_mut();
+
+        let mut position = 0;
+        while let Some(row) = tree_model.row(position) {
+            if let Some(entry) = row.item().and_downcast::<ProcessEntry>() {
+                if row.is_expanded() {
+                    expanded_pids.insert(entry.pid());
+                } else {
+                    expanded_pids.remove(&entry.pid());
+                }
+            }
+            position += 1;
+        }
+    }
+
+    /// Expands the rows of all processes whose rows have been expanded before
+    fn restore_expanded_rows(&self) {
+        let imp = self.imp();
+
+        let Some(tree_model) = imp.tree_model.borrow().clone() else {
+            return;
+        };
+
+        let expanded_pids = imp.expanded_pids.borrow().clone();
+
+        // expanding a row inserts its children right below it, so they will be visited as well
+        let mut position = 0;
+        while let Some(row) = tree_model.row(position) {
+            if !row.is_expanded()
+                && row
+                    .item()
+                    .and_downcast::<ProcessEntry>()
+                    .is_some_and(|entry| expanded_pids.contains(&entry.pid()))
+            {
+                row.set_expanded(true);
+            }
+            position += 1;
+        }
+    }
+
+    /// Expands the rows of all processes that have descendants matching the current search in tree view
+    fn expand_search_matches(&self) {
+        let imp = self.imp();
+
+        if !SETTINGS.processes_tree_view() {
+            return;
+        }
+
+        let Some(tree_model) = imp.tree_model.borrow().clone() else {
+            return;
+        };
+
+        let filter = self.process_filter();
+
+        if !filter.is_active() {
+            return;
+        }
+
+        let mut position = 0;
+        while let Some(row) = tree_model.row(position) {
+            if !row.is_expanded()
+                && row
+                    .item()
+                    .and_downcast::<ProcessEntry>()
+                    .is_some_and(|entry| self.children_match(&entry, &filter))
+            {
+                row.set_expanded(true);
+            }
+            position += 1;
+        }
+    }
+
     pub fn open_process_action_dialog(&self, processes: Vec<ProcessEntry>, action: ProcessAction) {
         // Nothing too bad can happen on Continue so dont show the dialog
         if action == ProcessAction::CONT {
@@ -912,49 +1400,279 @@ impl ResProcesses {
         dialog.present(Some(&MainWindow::default()));
     }
 
-    fn add_name_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
-        let name_col_factory = gtk::SignalListItemFactory::new();
-
-        let name_col =
-            gtk::ColumnViewColumn::new(Some(&i18n("Process")), Some(name_col_factory.clone()));
+    /// Opens a dialog that lets the user choose an arbitrary signal and sends it to `processes`.
+    pub fn open_send_signal_dialog(&self, processes: Vec<ProcessEntry>) {
+        let signals = signals();
 
-        name_col.set_resizable(true);
+        let signal_labels: Vec<String> = signals
+            .iter()
+            .map(|(name, number)| format!("{name} ({number})"))
+            .collect();
 
-        name_col.set_expand(true);
+        let signal_row = adw::ComboRow::builder()
+            .title(i18n("Signal"))
+            .model(&gtk::StringList::new(
+                &signal_labels.iter().map(String::as_str).collect::<Vec<_>>(),
+            ))
+            .enable_search(true)
+            .build();
 
-        name_col_factory.connect_setup(clone!(
-            #[weak(rename_to = this)]
-            self,
-            move |_factory, item| {
-                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+        if let Some(position) = signals
+            .iter()
+            .position(|(_, number)| *number == libc::SIGTERM)
+        {
+            signal_row.set_selected(position as u32);
+        }
 
-                let row = ResProcessNameCell::new();
+        let list_box = gtk::ListBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .css_classes(["boxed-list"])
+            .build();
+        list_box.append(&signal_row);
 
-                item.set_child(Some(&row));
+        let heading = if processes.len() == 1 {
+            i18n_f("Send Signal to {}", &[&processes[0].name()])
+        } else {
+            ni18n_f(
+                "Send Signal to Process",
+                "Send Signal to {} Processes",
+                processes.len() as u32,
+                &[&processes.len().to_string()],
+            )
+        };
 
-                item.property_expression("item")
-                    .chain_property::<ProcessEntry>("name")
-                    .bind(&row, "name", Widget::NONE);
+        let dialog = adw::AlertDialog::builder()
+            .heading(heading)
+            .body(i18n(
+                "Sending signals to processes can come with serious risks such as losing data and security implications. Use with caution.",
+            ))
+            .extra_child(&list_box)
+            .build();
 
-                item.property_expression("item")
-                    .chain_property::<ProcessEntry>("icon")
-                    .bind(&row, "icon", Widget::NONE);
+        dialog.add_response("send", &i18n("Send Signal"));
+        dialog.set_response_appearance("send", ResponseAppearance::Destructive);
 
-                item.property_expression("item")
-                    .chain_property::<ProcessEntry>("commandline")
-                    .bind(&row, "tooltip", Widget::NONE);
+        dialog.add_response("cancel", &i18n("Cancel"));
+        dialog.set_default_response(Some("cancel"));
+        dialog.set_close_response("cancel");
 
-                item.property_expression("item")
-                    .chain_property::<ProcessEntry>("symbolic")
-                    .bind(&row, "symbolic", Widget::NONE);
+        dialog.connect_response(
+            None,
+            clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[weak]
+                signal_row,
+                #[strong]
+                processes,
+                move |_, response| {
+                    if response != "send" {
+                        return;
+                    }
 
-                this.add_gestures(item);
-            }
-        ));
+                    let Some((signal_name, signal)) =
+                        signals.get(signal_row.selected() as usize).cloned()
+                    else {
+                        return;
+                    };
 
-        name_col_factory.connect_teardown(move |_factory, item| {
-            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
-            item.set_child(None::<&ResProcessNameCell>);
+                    let main_context = MainContext::default();
+                    main_context.spawn_local(clone!(
+                        #[weak]
+                        this,
+                        #[strong]
+                        processes,
+                        async move {
+                            let imp = this.imp();
+                            let _ = imp
+                                .sender
+                                .get()
+                                .unwrap()
+                                .send(Action::SendSignal(
+                                    signal,
+                                    signal_name,
+                                    processes
+                                        .iter()
+                                        .map(|process_entry| process_entry.pid())
+                                        .collect(),
+                                    imp.toast_overlay.get(),
+                                ))
+                                .await;
+                        }
+                    ));
+                }
+            ),
+        );
+
+        dialog.present(Some(&MainWindow::default()));
+    }
+
+    /// Opens a confirmation dialog listing `process` and all of its descendants. If confirmed, the
+    /// whole tree receives `action`, children first.
+    pub fn open_process_tree_action_dialog(&self, process: &ProcessEntry, action: ProcessAction) {
+        let root_pid = process.pid();
+
+        let entries: HashMap<i32, ProcessEntry> = self
+            .imp()
+            .store
+            .borrow()
+            .iter::<ProcessEntry>()
+            .flatten()
+            .map(|entry| (entry.pid(), entry))
+            .collect();
+
+        let tree = process_tree_pids(
+            root_pid,
+            entries
+                .values()
+                .map(|entry| (entry.pid(), entry.parent_pid())),
+        );
+
+        let list_box = gtk::ListBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .css_classes(["boxed-list"])
+            .build();
+
+        // show the root process first and its descendants afterwards
+        for pid in tree.iter().rev() {
+            if let Some(entry) = entries.get(pid) {
+                let row = adw::ActionRow::builder()
+                    .title(glib::markup_escape_text(&entry.name()))
+                    .subtitle(i18n_f("PID: {}", &[&pid.to_string()]))
+                    .build();
+                list_box.append(&row);
+            }
+        }
+
+        let scrolled_window = gtk::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .max_content_height(300)
+            .propagate_natural_height(true)
+            .child(&list_box)
+            .build();
+
+        let dialog = adw::AlertDialog::builder()
+            .heading(get_tree_action_name(action, &process.name(), tree.len()))
+            .body(get_action_warning(action))
+            .extra_child(&scrolled_window)
+            .build();
+
+        dialog.add_response("yes", &get_tree_action_description(action));
+        dialog.set_response_appearance("yes", ResponseAppearance::Destructive);
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.set_default_response(Some("no"));
+        dialog.set_close_response("no");
+
+        dialog.connect_response(
+            None,
+            clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_, response| {
+                    if response == "yes" {
+                        let main_context = MainContext::default();
+                        main_context.spawn_local(clone!(
+                            #[weak]
+                            this,
+                            async move {
+                                let imp = this.imp();
+                                let _ = imp
+                                    .sender
+                                    .get()
+                                    .unwrap()
+                                    .send(Action::ManipulateProcessTree(
+                                        action,
+                                        root_pid,
+                                        imp.toast_overlay.get(),
+                                    ))
+                                    .await;
+                            }
+                        ));
+                    }
+                }
+            ),
+        );
+
+        dialog.present(Some(&MainWindow::default()));
+    }
+
+    fn add_name_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let name_col_factory = gtk::SignalListItemFactory::new();
+
+        let name_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Process")), Some(name_col_factory.clone()));
+
+        name_col.set_resizable(true);
+
+        name_col.set_expand(true);
+
+        name_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = ResProcessNameCell::new();
+
+                let expander = gtk::TreeExpander::new();
+                expander.set_child(Some(&row));
+
+                item.set_child(Some(&expander));
+
+                item.property_expression("item")
+                    .bind(&expander, "list-row", Widget::NONE);
+
+                // in flat view, no row is expandable and we don't want any indentation
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("expandable")
+                    .bind(&expander, "indent-for-icon", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("has_children")
+                    .chain_closure::<bool>(closure!(|_: Option<Object>, has_children: bool| {
+                        !has_children
+                    }))
+                    .bind(&expander, "hide-expander", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("name")
+                    .bind(&row, "name", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("icon")
+                    .bind(&row, "icon", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("commandline")
+                    .bind(&row, "tooltip", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("symbolic")
+                    .bind(&row, "symbolic", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("container")
+                    .bind(&row, "container", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("container_runtime")
+                    .bind(&row, "container_runtime", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        name_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::TreeExpander>);
         });
 
         let name_col_sorter = StringSorter::builder()
@@ -991,6 +1709,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("pid")
                     .bind(&row, "text", Widget::NONE);
 
@@ -1045,6 +1764,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("user")
                     .bind(&row, "text", Widget::NONE);
 
@@ -1083,8 +1803,10 @@ impl ResProcesses {
     fn add_memory_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
         let memory_col_factory = gtk::SignalListItemFactory::new();
 
-        let memory_col =
-            gtk::ColumnViewColumn::new(Some(&i18n("Memory")), Some(memory_col_factory.clone()));
+        let memory_col = gtk::ColumnViewColumn::new(
+            Some(&memory_column_title(SETTINGS.processes_memory_metric())),
+            Some(memory_col_factory.clone()),
+        );
 
         memory_col.set_resizable(true);
 
@@ -1100,9 +1822,14 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("memory_usage")
-                    .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: u64| {
-                        convert_storage(memory_usage as f64, false)
+                    .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: i64| {
+                        if memory_usage == -1 {
+                            i18n("N/A")
+                        } else {
+                            convert_storage(memory_usage as f64, false)
+                        }
                     }))
                     .bind(&row, "text", Widget::NONE);
 
@@ -1135,6 +1862,12 @@ impl ResProcesses {
             move |visible| memory_col.set_visible(visible)
         ));
 
+        SETTINGS.connect_processes_memory_metric(clone!(
+            #[weak]
+            memory_col,
+            move |metric| memory_col.set_title(Some(&memory_column_title(metric)))
+        ));
+
         memory_col
     }
 
@@ -1158,6 +1891,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("cpu_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
                         let mut percentage = cpu_usage * 100.0;
@@ -1223,6 +1957,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("read_speed")
                     .chain_closure::<String>(closure!(|_: Option<Object>, read_speed: f64| {
                         if read_speed == -1.0 {
@@ -1289,6 +2024,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("read_total")
                     .chain_closure::<String>(closure!(|_: Option<Object>, read_total: i64| {
                         if read_total == -1 {
@@ -1355,6 +2091,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("write_speed")
                     .chain_closure::<String>(closure!(|_: Option<Object>, write_speed: f64| {
                         if write_speed == -1.0 {
@@ -1421,6 +2158,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("write_total")
                     .chain_closure::<String>(closure!(|_: Option<Object>, write_total: i64| {
                         if write_total == -1 {
@@ -1484,6 +2222,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("gpu_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, gpu_usage: f32| {
                         format!("{:.1} %", gpu_usage * 100.0)
@@ -1544,6 +2283,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("enc_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, enc_usage: f32| {
                         format!("{:.1} %", enc_usage * 100.0)
@@ -1604,6 +2344,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("dec_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, dec_usage: f32| {
                         format!("{:.1} %", dec_usage * 100.0)
@@ -1663,6 +2404,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("gpu_mem_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, gpu_mem: u64| {
                         convert_storage(gpu_mem as f64, false)
@@ -1722,6 +2464,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("total_cpu_time")
                     .chain_closure::<String>(closure!(|_: Option<Object>, total_cpu_time: f64| {
                         format_time(total_cpu_time)
@@ -1781,6 +2524,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("user_cpu_time")
                     .chain_closure::<String>(closure!(|_: Option<Object>, user_cpu_time: f64| {
                         format_time(user_cpu_time)
@@ -1840,6 +2584,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("system_cpu_time")
                     .chain_closure::<String>(closure!(|_: Option<Object>, system_cpu_time: f64| {
                         format_time(system_cpu_time)
@@ -1897,6 +2642,7 @@ impl ResProcesses {
 
                 item.set_child(Some(&row));
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("niceness")
                     .chain_closure::<String>(closure!(|_: Option<Object>, niceness: i8| {
                         if SETTINGS.detailed_priority() {
@@ -1965,6 +2711,7 @@ impl ResProcesses {
                 item.set_child(Some(&row));
 
                 item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
                     .chain_property::<ProcessEntry>("swap_usage")
                     .chain_closure::<String>(closure!(|_: Option<Object>, swap_usage: u64| {
                         convert_storage(swap_usage as f64, false)
@@ -2002,60 +2749,1347 @@ impl ResProcesses {
 
         swap_col
     }
-}
 
-fn get_action_name(action: ProcessAction, name: &str) -> String {
-    match action {
-        ProcessAction::TERM => i18n_f("End {}?", &[name]),
-        ProcessAction::STOP => i18n_f("Halt {}?", &[name]),
-        ProcessAction::KILL => i18n_f("Kill {}?", &[name]),
-        ProcessAction::CONT => i18n_f("Continue {}?", &[name]),
+    fn add_state_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let state_col_factory = gtk::SignalListItemFactory::new();
+
+        let state_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("State")), Some(state_col_factory.clone()));
+
+        state_col.set_resizable(true);
+
+        state_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("state")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        state_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let state_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "state",
+            ))
+            .build();
+
+        state_col.set_sorter(Some(&state_col_sorter));
+        state_col.set_visible(SETTINGS.processes_show_state());
+
+        column_view.append_column(&state_col);
+
+        SETTINGS.connect_processes_show_state(clone!(
+            #[weak]
+            state_col,
+            move |visible| state_col.set_visible(visible)
+        ));
+
+        state_col
     }
-}
 
-fn get_action_name_multiple(action: ProcessAction, count: usize) -> String {
-    match action {
-        ProcessAction::TERM => ni18n_f(
-            "End process?",
-            "End {} processes?",
-            count as u32,
-            &[&count.to_string()],
-        ),
-        ProcessAction::STOP => ni18n_f(
-            "Halt process?",
-            "Halt {} processes?",
-            count as u32,
-            &[&count.to_string()],
-        ),
-        ProcessAction::KILL => ni18n_f(
-            "Kill process?",
-            "Kill {} processes?",
-            count as u32,
-            &[&count.to_string()],
-        ),
-        ProcessAction::CONT => ni18n_f(
-            "Kill process?",
-            "Kill {} processes?",
-            count as u32,
-            &[&count.to_string()],
-        ),
+    fn add_threads_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let threads_col_factory = gtk::SignalListItemFactory::new();
+
+        let threads_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Threads")), Some(threads_col_factory.clone()));
+
+        threads_col.set_resizable(true);
+
+        threads_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("num_threads")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        threads_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let threads_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "num_threads",
+            ))
+            .build();
+
+        threads_col.set_sorter(Some(&threads_col_sorter));
+        threads_col.set_visible(SETTINGS.processes_show_threads());
+
+        column_view.append_column(&threads_col);
+
+        SETTINGS.connect_processes_show_threads(clone!(
+            #[weak]
+            threads_col,
+            move |visible| threads_col.set_visible(visible)
+        ));
+
+        threads_col
     }
-}
 
-fn get_action_warning(action: ProcessAction) -> String {
-    match action {
-            ProcessAction::TERM => i18n("Unsaved work might be lost."),
-            ProcessAction::STOP => i18n("Halting a process can come with serious risks such as losing data and security implications. Use with caution."),
-            ProcessAction::KILL => i18n("Killing a process can come with serious risks such as losing data and security implications. Use with caution."),
-            ProcessAction::CONT => String::new(),
-        }
-}
+    fn add_voluntary_switches_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let voluntary_switches_col_factory = gtk::SignalListItemFactory::new();
 
-fn get_action_description(action: ProcessAction) -> String {
-    match action {
-        ProcessAction::TERM => i18n("End Process"),
-        ProcessAction::STOP => i18n("Halt Process"),
-        ProcessAction::KILL => i18n("Kill Process"),
-        ProcessAction::CONT => i18n("Continue Process"),
+        let voluntary_switches_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Voluntary Context Switches")),
+            Some(voluntary_switches_col_factory.clone()),
+        );
+
+        voluntary_switches_col.set_resizable(true);
+
+        voluntary_switches_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("voluntary_context_switches")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        voluntary_switches_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let voluntary_switches_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "voluntary_context_switches",
+            ))
+            .build();
+
+        voluntary_switches_col.set_sorter(Some(&voluntary_switches_col_sorter));
+        voluntary_switches_col.set_visible(SETTINGS.processes_show_voluntary_context_switches());
+
+        column_view.append_column(&voluntary_switches_col);
+
+        SETTINGS.connect_processes_show_voluntary_context_switches(clone!(
+            #[weak]
+            voluntary_switches_col,
+            move |visible| voluntary_switches_col.set_visible(visible)
+        ));
+
+        voluntary_switches_col
+    }
+
+    fn add_involuntary_switches_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let involuntary_switches_col_factory = gtk::SignalListItemFactory::new();
+
+        let involuntary_switches_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Involuntary Context Switches")),
+            Some(involuntary_switches_col_factory.clone()),
+        );
+
+        involuntary_switches_col.set_resizable(true);
+
+        involuntary_switches_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("involuntary_context_switches")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        involuntary_switches_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let involuntary_switches_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "involuntary_context_switches",
+            ))
+            .build();
+
+        involuntary_switches_col.set_sorter(Some(&involuntary_switches_col_sorter));
+        involuntary_switches_col
+            .set_visible(SETTINGS.processes_show_involuntary_context_switches());
+
+        column_view.append_column(&involuntary_switches_col);
+
+        SETTINGS.connect_processes_show_involuntary_context_switches(clone!(
+            #[weak]
+            involuntary_switches_col,
+            move |visible| involuntary_switches_col.set_visible(visible)
+        ));
+
+        involuntary_switches_col
+    }
+
+    fn add_minor_faults_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let minor_faults_col_factory = gtk::SignalListItemFactory::new();
+
+        let minor_faults_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Minor Page Faults")),
+            Some(minor_faults_col_factory.clone()),
+        );
+
+        minor_faults_col.set_resizable(true);
+
+        minor_faults_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("minor_faults")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        minor_faults_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let minor_faults_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "minor_faults",
+            ))
+            .build();
+
+        minor_faults_col.set_sorter(Some(&minor_faults_col_sorter));
+        minor_faults_col.set_visible(SETTINGS.processes_show_minor_faults());
+
+        column_view.append_column(&minor_faults_col);
+
+        SETTINGS.connect_processes_show_minor_faults(clone!(
+            #[weak]
+            minor_faults_col,
+            move |visible| minor_faults_col.set_visible(visible)
+        ));
+
+        minor_faults_col
+    }
+
+    fn add_major_faults_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let major_faults_col_factory = gtk::SignalListItemFactory::new();
+
+        let major_faults_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Major Page Faults")),
+            Some(major_faults_col_factory.clone()),
+        );
+
+        major_faults_col.set_resizable(true);
+
+        major_faults_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("major_faults")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        major_faults_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let major_faults_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "major_faults",
+            ))
+            .build();
+
+        major_faults_col.set_sorter(Some(&major_faults_col_sorter));
+        major_faults_col.set_visible(SETTINGS.processes_show_major_faults());
+
+        column_view.append_column(&major_faults_col);
+
+        SETTINGS.connect_processes_show_major_faults(clone!(
+            #[weak]
+            major_faults_col,
+            move |visible| major_faults_col.set_visible(visible)
+        ));
+
+        major_faults_col
+    }
+
+    fn add_subtree_cpu_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let subtree_cpu_col_factory = gtk::SignalListItemFactory::new();
+
+        let subtree_cpu_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Processor (Tree)")),
+            Some(subtree_cpu_col_factory.clone()),
+        );
+
+        subtree_cpu_col.set_resizable(true);
+
+        subtree_cpu_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("subtree_cpu_usage")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, subtree_cpu_usage: f32| {
+                            let mut percentage = subtree_cpu_usage * 100.0;
+                            if !SETTINGS.normalize_cpu_usage() {
+                                percentage *= *NUM_CPUS as f32;
+                            }
+
+                            format!("{percentage:.1} %")
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        subtree_cpu_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let subtree_cpu_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "subtree_cpu_usage",
+            ))
+            .build();
+
+        subtree_cpu_col.set_sorter(Some(&subtree_cpu_col_sorter));
+        subtree_cpu_col.set_visible(SETTINGS.processes_tree_view());
+
+        column_view.append_column(&subtree_cpu_col);
+
+        SETTINGS.connect_processes_tree_view(clone!(
+            #[weak]
+            subtree_cpu_col,
+            move |visible| subtree_cpu_col.set_visible(visible)
+        ));
+
+        subtree_cpu_col
+    }
+
+    fn add_subtree_memory_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let subtree_memory_col_factory = gtk::SignalListItemFactory::new();
+
+        let subtree_memory_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Memory (Tree)")),
+            Some(subtree_memory_col_factory.clone()),
+        );
+
+        subtree_memory_col.set_resizable(true);
+
+        subtree_memory_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("subtree_memory_usage")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, subtree_memory_usage: u64| {
+                            convert_storage(subtree_memory_usage as f64, false)
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        subtree_memory_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let subtree_memory_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "subtree_memory_usage",
+            ))
+            .build();
+
+        subtree_memory_col.set_sorter(Some(&subtree_memory_col_sorter));
+        subtree_memory_col.set_visible(SETTINGS.processes_tree_view());
+
+        column_view.append_column(&subtree_memory_col);
+
+        SETTINGS.connect_processes_tree_view(clone!(
+            #[weak]
+            subtree_memory_col,
+            move |visible| subtree_memory_col.set_visible(visible)
+        ));
+
+        subtree_memory_col
+    }
+
+    fn add_io_priority_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let io_priority_col_factory = gtk::SignalListItemFactory::new();
+
+        let io_priority_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("I/O Priority")),
+            Some(io_priority_col_factory.clone()),
+        );
+
+        io_priority_col.set_resizable(true);
+
+        io_priority_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("io_priority")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        io_priority_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let io_priority_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "io_priority",
+            ))
+            .build();
+
+        io_priority_col.set_sorter(Some(&io_priority_col_sorter));
+        io_priority_col.set_visible(SETTINGS.processes_show_io_priority());
+
+        column_view.append_column(&io_priority_col);
+
+        SETTINGS.connect_processes_show_io_priority(clone!(
+            #[weak]
+            io_priority_col,
+            move |visible| io_priority_col.set_visible(visible)
+        ));
+
+        io_priority_col
+    }
+
+    fn add_policy_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let policy_col_factory = gtk::SignalListItemFactory::new();
+
+        let policy_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Scheduling Policy")),
+            Some(policy_col_factory.clone()),
+        );
+
+        policy_col.set_resizable(true);
+
+        policy_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("scheduling_policy")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        policy_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let policy_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "scheduling_policy",
+            ))
+            .build();
+
+        policy_col.set_sorter(Some(&policy_col_sorter));
+        policy_col.set_visible(SETTINGS.processes_show_scheduling_policy());
+
+        column_view.append_column(&policy_col);
+
+        SETTINGS.connect_processes_show_scheduling_policy(clone!(
+            #[weak]
+            policy_col,
+            move |visible| policy_col.set_visible(visible)
+        ));
+
+        policy_col
+    }
+
+    fn add_oom_score_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let oom_score_col_factory = gtk::SignalListItemFactory::new();
+
+        let oom_score_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("OOM Score")),
+            Some(oom_score_col_factory.clone()),
+        );
+
+        oom_score_col.set_resizable(true);
+
+        oom_score_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("oom_score")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        oom_score_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let oom_score_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "oom_score",
+            ))
+            .build();
+
+        oom_score_col.set_sorter(Some(&oom_score_col_sorter));
+        oom_score_col.set_visible(SETTINGS.processes_show_oom_score());
+
+        column_view.append_column(&oom_score_col);
+
+        SETTINGS.connect_processes_show_oom_score(clone!(
+            #[weak]
+            oom_score_col,
+            move |visible| oom_score_col.set_visible(visible)
+        ));
+
+        oom_score_col
+    }
+
+    fn add_fd_count_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let fd_count_col_factory = gtk::SignalListItemFactory::new();
+
+        let fd_count_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Open Files")),
+            Some(fd_count_col_factory.clone()),
+        );
+
+        fd_count_col.set_resizable(true);
+
+        fd_count_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("fd_count")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, fd_count: i64| {
+                        if fd_count == -1 {
+                            i18n("N/A")
+                        } else {
+                            fd_count.to_string()
+                        }
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        fd_count_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let fd_count_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "fd_count",
+            ))
+            .build();
+
+        fd_count_col.set_sorter(Some(&fd_count_col_sorter));
+        fd_count_col.set_visible(SETTINGS.processes_show_fd_count());
+
+        column_view.append_column(&fd_count_col);
+
+        SETTINGS.connect_processes_show_fd_count(clone!(
+            #[weak]
+            fd_count_col,
+            move |visible| fd_count_col.set_visible(visible)
+        ));
+
+        fd_count_col
+    }
+
+    fn add_listening_ports_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let listening_ports_col_factory = gtk::SignalListItemFactory::new();
+
+        let listening_ports_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Listening Ports")),
+            Some(listening_ports_col_factory.clone()),
+        );
+
+        listening_ports_col.set_resizable(true);
+
+        listening_ports_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("listening_ports")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        listening_ports_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let listening_ports_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "listening_ports",
+            ))
+            .build();
+
+        listening_ports_col.set_sorter(Some(&listening_ports_col_sorter));
+        listening_ports_col.set_visible(SETTINGS.processes_show_listening_ports());
+
+        column_view.append_column(&listening_ports_col);
+
+        SETTINGS.connect_processes_show_listening_ports(clone!(
+            #[weak(rename_to = this)]
+            self,
+            #[weak]
+            listening_ports_col,
+            move |visible| {
+                listening_ports_col.set_visible(visible);
+                this.update_data_options();
+            }
+        ));
+
+        listening_ports_col
+    }
+
+    fn add_logical_read_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let logical_read_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let logical_read_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Logical Read")),
+            Some(logical_read_speed_col_factory.clone()),
+        );
+
+        logical_read_speed_col.set_resizable(true);
+
+        logical_read_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("logical_read_speed")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, logical_read_speed: f64| {
+                            if logical_read_speed == -1.0 {
+                                i18n("N/A")
+                            } else {
+                                convert_speed(logical_read_speed, false)
+                            }
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        logical_read_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let logical_read_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "logical_read_speed",
+            ))
+            .build();
+
+        logical_read_speed_col.set_sorter(Some(&logical_read_speed_col_sorter));
+        logical_read_speed_col.set_visible(SETTINGS.processes_show_logical_read_speed());
+
+        column_view.append_column(&logical_read_speed_col);
+
+        SETTINGS.connect_processes_show_logical_read_speed(clone!(
+            #[weak]
+            logical_read_speed_col,
+            move |visible| logical_read_speed_col.set_visible(visible)
+        ));
+
+        logical_read_speed_col
+    }
+
+    fn add_logical_write_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let logical_write_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let logical_write_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Logical Write")),
+            Some(logical_write_speed_col_factory.clone()),
+        );
+
+        logical_write_speed_col.set_resizable(true);
+
+        logical_write_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("logical_write_speed")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, logical_write_speed: f64| {
+                            if logical_write_speed == -1.0 {
+                                i18n("N/A")
+                            } else {
+                                convert_speed(logical_write_speed, false)
+                            }
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        logical_write_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let logical_write_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "logical_write_speed",
+            ))
+            .build();
+
+        logical_write_speed_col.set_sorter(Some(&logical_write_speed_col_sorter));
+        logical_write_speed_col.set_visible(SETTINGS.processes_show_logical_write_speed());
+
+        column_view.append_column(&logical_write_speed_col);
+
+        SETTINGS.connect_processes_show_logical_write_speed(clone!(
+            #[weak]
+            logical_write_speed_col,
+            move |visible| logical_write_speed_col.set_visible(visible)
+        ));
+
+        logical_write_speed_col
+    }
+
+    fn add_read_syscall_rate_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let read_syscall_rate_col_factory = gtk::SignalListItemFactory::new();
+
+        let read_syscall_rate_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Read Calls")),
+            Some(read_syscall_rate_col_factory.clone()),
+        );
+
+        read_syscall_rate_col.set_resizable(true);
+
+        read_syscall_rate_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("read_syscall_rate")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, read_syscall_rate: f64| {
+                            if read_syscall_rate == -1.0 {
+                                i18n("N/A")
+                            } else {
+                                convert_rate(read_syscall_rate)
+                            }
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        read_syscall_rate_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let read_syscall_rate_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "read_syscall_rate",
+            ))
+            .build();
+
+        read_syscall_rate_col.set_sorter(Some(&read_syscall_rate_col_sorter));
+        read_syscall_rate_col.set_visible(SETTINGS.processes_show_read_syscall_rate());
+
+        column_view.append_column(&read_syscall_rate_col);
+
+        SETTINGS.connect_processes_show_read_syscall_rate(clone!(
+            #[weak]
+            read_syscall_rate_col,
+            move |visible| read_syscall_rate_col.set_visible(visible)
+        ));
+
+        read_syscall_rate_col
+    }
+
+    fn add_write_syscall_rate_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let write_syscall_rate_col_factory = gtk::SignalListItemFactory::new();
+
+        let write_syscall_rate_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Write Calls")),
+            Some(write_syscall_rate_col_factory.clone()),
+        );
+
+        write_syscall_rate_col.set_resizable(true);
+
+        write_syscall_rate_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("write_syscall_rate")
+                    .chain_closure::<String>(closure!(
+                        |_: Option<Object>, write_syscall_rate: f64| {
+                            if write_syscall_rate == -1.0 {
+                                i18n("N/A")
+                            } else {
+                                convert_rate(write_syscall_rate)
+                            }
+                        }
+                    ))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        write_syscall_rate_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let write_syscall_rate_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_syscall_rate",
+            ))
+            .build();
+
+        write_syscall_rate_col.set_sorter(Some(&write_syscall_rate_col_sorter));
+        write_syscall_rate_col.set_visible(SETTINGS.processes_show_write_syscall_rate());
+
+        column_view.append_column(&write_syscall_rate_col);
+
+        SETTINGS.connect_processes_show_write_syscall_rate(clone!(
+            #[weak]
+            write_syscall_rate_col,
+            move |visible| write_syscall_rate_col.set_visible(visible)
+        ));
+
+        write_syscall_rate_col
+    }
+
+    fn add_cpu_wait_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let cpu_wait_col_factory = gtk::SignalListItemFactory::new();
+
+        let cpu_wait_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("CPU Wait")), Some(cpu_wait_col_factory.clone()));
+
+        cpu_wait_col.set_resizable(true);
+
+        cpu_wait_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("cpu_wait")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, cpu_wait: f32| {
+                        if cpu_wait == -1.0 {
+                            return i18n("N/A");
+                        }
+
+                        let mut percentage = cpu_wait * 100.0;
+                        if !SETTINGS.normalize_cpu_usage() {
+                            percentage *= *NUM_CPUS as f32;
+                        }
+
+                        format!("{percentage:.1} %")
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        cpu_wait_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let cpu_wait_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "cpu_wait",
+            ))
+            .build();
+
+        cpu_wait_col.set_sorter(Some(&cpu_wait_col_sorter));
+        cpu_wait_col.set_visible(SETTINGS.processes_show_cpu_wait());
+
+        column_view.append_column(&cpu_wait_col);
+
+        SETTINGS.connect_processes_show_cpu_wait(clone!(
+            #[weak(rename_to = this)]
+            self,
+            #[weak]
+            cpu_wait_col,
+            move |visible| {
+                cpu_wait_col.set_visible(visible);
+                this.update_data_options();
+            }
+        ));
+
+        cpu_wait_col
+    }
+
+    fn add_io_wait_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let io_wait_col_factory = gtk::SignalListItemFactory::new();
+
+        let io_wait_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("I/O Wait")), Some(io_wait_col_factory.clone()));
+
+        io_wait_col.set_resizable(true);
+
+        io_wait_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ProcessEntry>("io_wait")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, io_wait: f32| {
+                        if io_wait == -1.0 {
+                            return i18n("N/A");
+                        }
+
+                        let mut percentage = io_wait * 100.0;
+                        if !SETTINGS.normalize_cpu_usage() {
+                            percentage *= *NUM_CPUS as f32;
+                        }
+
+                        format!("{percentage:.1} %")
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        io_wait_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let io_wait_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ProcessEntry::static_type(),
+                None::<&gtk::Expression>,
+                "io_wait",
+            ))
+            .build();
+
+        io_wait_col.set_sorter(Some(&io_wait_col_sorter));
+        io_wait_col.set_visible(SETTINGS.processes_show_io_wait());
+
+        column_view.append_column(&io_wait_col);
+
+        SETTINGS.connect_processes_show_io_wait(clone!(
+            #[weak(rename_to = this)]
+            self,
+            #[weak]
+            io_wait_col,
+            move |visible| {
+                io_wait_col.set_visible(visible);
+                this.update_data_options();
+            }
+        ));
+
+        io_wait_col
+    }
+}
+
+/// The criteria the shown processes are currently filtered by
+#[derive(Debug, Clone, Default)]
+struct ProcessFilter {
+    /// Lowercase, `None` if not searching or the search entry is empty
+    search_string: Option<String>,
+    listening_only: bool,
+    containers_only: bool,
+}
+
+impl ProcessFilter {
+    fn is_active(&self) -> bool {
+        self.search_string.is_some() || self.listening_only || self.containers_only
+    }
+
+    /// Returns whether `entry` itself (not taking its descendants into account) matches
+    fn matches(&self, entry: &ProcessEntry) -> bool {
+        let matches_search = self.search_string.as_deref().map_or(true, |search_string| {
+            entry.name().to_lowercase().contains(search_string)
+                || entry.commandline().to_lowercase().contains(search_string)
+                || entry.listening_ports().contains(search_string)
+                || entry.container().to_lowercase().contains(search_string)
+        });
+        let matches_listening = !self.listening_only || !entry.listening_ports().is_empty();
+        let matches_container = !self.containers_only || !entry.container().is_empty();
+
+        matches_search && matches_listening && matches_container
+    }
+}
+
+/// Returns the process entry of a row of the column view's model
+fn entry_from_row(object: Object) -> Option<ProcessEntry> {
+    object
+        .downcast::<gtk::TreeListRow>()
+        .ok()
+        .and_then(|row| row.item())
+        .and_downcast::<ProcessEntry>()
+}
+
+/// Sets the subtree usages of the process with the given PID and all of its descendants and returns its
+/// processor and memory usage
+fn aggregate_subtree(
+    pid: i32,
+    children: &HashMap<i32, Vec<i32>>,
+    entries: &HashMap<i32, ProcessEntry>,
+) -> (f32, u64) {
+    let Some(entry) = entries.get(&pid) else {
+        return (0.0, 0);
+    };
+
+    // processes whose memory usage isn't available don't add to the subtree
+    let (mut cpu_usage, mut memory_usage) = (entry.cpu_usage(), entry.memory_usage().max(0) as u64);
+
+    let child_pids = children.get(&pid);
+
+    entry.set_has_children(child_pids.is_some());
+
+    for child_pid in child_pids.into_iter().flatten() {
+        let (child_cpu_usage, child_memory_usage) =
+            aggregate_subtree(*child_pid, children, entries);
+        cpu_usage += child_cpu_usage;
+        memory_usage += child_memory_usage;
+    }
+
+    entry.set_subtree_cpu_usage(cpu_usage);
+    entry.set_subtree_memory_usage(memory_usage);
+
+    (cpu_usage, memory_usage)
+}
+
+fn get_action_name(action: ProcessAction, name: &str) -> String {
+    match action {
+        ProcessAction::TERM => i18n_f("End {}?", &[name]),
+        ProcessAction::STOP => i18n_f("Halt {}?", &[name]),
+        ProcessAction::KILL => i18n_f("Kill {}?", &[name]),
+        ProcessAction::CONT => i18n_f("Continue {}?", &[name]),
+    }
+}
+
+fn get_action_name_multiple(action: ProcessAction, count: usize) -> String {
+    match action {
+        ProcessAction::TERM => ni18n_f(
+            "End process?",
+            "End {} processes?",
+            count as u32,
+            &[&count.to_string()],
+        ),
+        ProcessAction::STOP => ni18n_f(
+            "Halt process?",
+            "Halt {} processes?",
+            count as u32,
+            &[&count.to_string()],
+        ),
+        ProcessAction::KILL => ni18n_f(
+            "Kill process?",
+            "Kill {} processes?",
+            count as u32,
+            &[&count.to_string()],
+        ),
+        ProcessAction::CONT => ni18n_f(
+            "Kill process?",
+            "Kill {} processes?",
+            count as u32,
+            &[&count.to_string()],
+        ),
+    }
+}
+
+fn get_tree_action_name(action: ProcessAction, name: &str, count: usize) -> String {
+    if count <= 1 {
+        return get_action_name(action, name);
+    }
+
+    let descendants = count - 1;
+    match action {
+        ProcessAction::KILL => ni18n_f(
+            "Kill {} and its descendant?",
+            "Kill {} and its {} descendants?",
+            descendants as u32,
+            &[name, &descendants.to_string()],
+        ),
+        _ => ni18n_f(
+            "End {} and its descendant?",
+            "End {} and its {} descendants?",
+            descendants as u32,
+            &[name, &descendants.to_string()],
+        ),
+    }
+}
+
+fn get_tree_action_description(action: ProcessAction) -> String {
+    match action {
+        ProcessAction::KILL => i18n("Kill Process Tree"),
+        _ => i18n("End Process Tree"),
+    }
+}
+
+fn get_action_warning(action: ProcessAction) -> String {
+    match action {
+            ProcessAction::TERM => i18n("Unsaved work might be lost."),
+            ProcessAction::STOP => i18n("Halting a process can come with serious risks such as losing data and security implications. Use with caution."),
+            ProcessAction::KILL => i18n("Killing a process can come with serious risks such as losing data and security implications. Use with caution."),
+            ProcessAction::CONT => String::new(),
+        }
+}
+
+fn get_action_description(action: ProcessAction) -> String {
+    match action {
+        ProcessAction::TERM => i18n("End Process"),
+        ProcessAction::STOP => i18n("Halt Process"),
+        ProcessAction::KILL => i18n("Kill Process"),
+        ProcessAction::CONT => i18n("Continue Process"),
+    }
+}
+
+fn memory_column_title(metric: MemoryMetric) -> String {
+    match metric {
+        MemoryMetric::Resident => i18n("Memory"),
+        MemoryMetric::Proportional => i18n("Memory (PSS)"),
+        MemoryMetric::Unique => i18n("Memory (USS)"),
+        MemoryMetric::Shared => i18n("Shared Memory"),
+        MemoryMetric::Anonymous => i18n("Anonymous Memory"),
     }
 }
@@ -0,0 +1,83 @@
+use gtk::glib;
+use process_data::details::OpenFile;
+
+use crate::ui::pages::{file_kind_label, open_flags_label};
+
+mod imp {
+    use std::cell::Cell;
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use super::*;
+
+    #[derive(Properties, Default)]
+    #[properties(wrapper_type = super::OpenFileEntry)]
+    pub struct OpenFileEntry {
+        #[property(get = Self::kind, set = Self::set_kind, type = glib::GString)]
+        kind: Cell<glib::GString>,
+
+        #[property(get = Self::target, set = Self::set_target, type = glib::GString)]
+        target: Cell<glib::GString>,
+
+        #[property(get = Self::flags, set = Self::set_flags, type = glib::GString)]
+        flags: Cell<glib::GString>,
+
+        #[property(get, set)]
+        fd: Cell<i32>,
+
+        /// -1 if the position is unknown
+        #[property(get, set)]
+        pos: Cell<i64>,
+    }
+
+    impl OpenFileEntry {
+        gstring_getter_setter!(kind, target, flags);
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for OpenFileEntry {
+        const NAME: &'static str = "OpenFileEntry";
+        type Type = super::OpenFileEntry;
+    }
+
+    impl ObjectImpl for OpenFileEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct OpenFileEntry(ObjectSubclass<imp::OpenFileEntry>);
+}
+
+impl OpenFileEntry {
+    pub fn new(open_file: &OpenFile) -> Self {
+        let this: Self = glib::Object::builder().property("fd", open_file.fd).build();
+        this.update(open_file);
+        this
+    }
+
+    pub fn update(&self, open_file: &OpenFile) {
+        self.set_kind(file_kind_label(open_file.kind));
+        self.set_target(open_file.target.as_str());
+        self.set_flags(open_file.flags.map(open_flags_label).unwrap_or_default());
+        self.set_pos(open_file.pos.map_or(-1, |pos| pos as i64));
+    }
+}
@@ -1,12 +1,16 @@
 use gtk::{
     glib::{self, GString},
+    prelude::ObjectExt,
     subclass::prelude::ObjectSubclassIsExt,
 };
-use process_data::Containerization;
+use process_data::{IoPriority, SchedulingPolicy};
 
 use crate::{
-    i18n::i18n,
-    utils::{process::Process, TICK_RATE},
+    ui::pages::{
+        container_runtime_label, containerization_label, frozen_state_label, io_priority_label,
+        listening_ports_label, process_state_label, scheduling_policy_label,
+    },
+    utils::{process::Process, settings::SETTINGS, TICK_RATE},
 };
 
 mod imp {
@@ -39,11 +43,27 @@ mod imp {
         #[property(get, set)]
         pid: Cell<i32>,
 
+        #[property(get, set)]
+        parent_pid: Cell<i32>,
+
+        /// Start time in clock ticks after boot, identifies the process together with its PID
+        #[property(get, set)]
+        starttime: Cell<u64>,
+
+        #[property(get, set)]
+        has_children: Cell<bool>,
+
         #[property(get, set)]
         cpu_usage: Cell<f32>,
 
         #[property(get, set)]
-        memory_usage: Cell<u64>,
+        memory_usage: Cell<i64>,
+
+        #[property(get, set)]
+        subtree_cpu_usage: Cell<f32>, // only calculated in tree view
+
+        #[property(get, set)]
+        subtree_memory_usage: Cell<u64>, // only calculated in tree view
 
         #[property(get, set)]
         swap_usage: Cell<u64>,
@@ -60,6 +80,33 @@ mod imp {
         #[property(get, set)]
         write_total: Cell<i64>, // will be -1 if write data is not available
 
+        #[property(get, set)]
+        logical_read_speed: Cell<f64>, // will be -1.0 if read data is not available
+
+        #[property(get, set)]
+        logical_read_total: Cell<i64>, // will be -1 if read data is not available
+
+        #[property(get, set)]
+        logical_write_speed: Cell<f64>, // will be -1.0 if write data is not available
+
+        #[property(get, set)]
+        logical_write_total: Cell<i64>, // will be -1 if write data is not available
+
+        #[property(get, set)]
+        read_syscall_rate: Cell<f64>, // will be -1.0 if read data is not available
+
+        #[property(get, set)]
+        write_syscall_rate: Cell<f64>, // will be -1.0 if write data is not available
+
+        #[property(get, set)]
+        cancelled_write_total: Cell<i64>, // will be -1 if write data is not available
+
+        #[property(get, set)]
+        cpu_wait: Cell<f32>, // will be -1.0 if schedstat is not available
+
+        #[property(get, set)]
+        io_wait: Cell<f32>, // will be -1.0 if delay accounting is disabled
+
         #[property(get, set)]
         gpu_usage: Cell<f32>,
 
@@ -84,12 +131,62 @@ mod imp {
         #[property(get, set)]
         niceness: Cell<i8>,
 
+        #[property(get = Self::state, set = Self::set_state, type = glib::GString)]
+        state: Cell<glib::GString>,
+
+        #[property(get = Self::io_priority, set = Self::set_io_priority, type = glib::GString)]
+        io_priority: Cell<glib::GString>,
+
+        #[property(get = Self::scheduling_policy, set = Self::set_scheduling_policy, type = glib::GString)]
+        scheduling_policy: Cell<glib::GString>,
+
+        #[property(get, set)]
+        realtime_priority: Cell<u32>,
+
+        #[property(get, set)]
+        oom_score: Cell<u32>,
+
+        #[property(get, set)]
+        oom_score_adj: Cell<i32>,
+
+        /// -1 if the file descriptors couldn't be read
+        #[property(get, set)]
+        fd_count: Cell<i64>,
+
+        #[property(get = Self::listening_ports, set = Self::set_listening_ports, type = glib::GString)]
+        listening_ports: Cell<glib::GString>,
+
+        #[property(get, set)]
+        num_threads: Cell<u64>,
+
+        #[property(get, set)]
+        voluntary_context_switches: Cell<u64>,
+
+        #[property(get, set)]
+        involuntary_context_switches: Cell<u64>,
+
+        #[property(get, set)]
+        minor_faults: Cell<u64>,
+
+        #[property(get, set)]
+        major_faults: Cell<u64>,
+
         #[property(get = Self::cgroup, set = Self::set_cgroup)]
         cgroup: Cell<Option<glib::GString>>,
 
+        #[property(get = Self::executable, set = Self::set_executable)]
+        executable: Cell<Option<glib::GString>>,
+
         #[property(get = Self::containerization, set = Self::set_containerization)]
         containerization: Cell<glib::GString>,
 
+        /// Name of the container the process is running in, empty if it isn't running in one
+        #[property(get = Self::container, set = Self::set_container)]
+        container: Cell<glib::GString>,
+
+        #[property(get = Self::container_runtime, set = Self::set_container_runtime)]
+        container_runtime: Cell<glib::GString>,
+
         #[property(get = Self::running_since, set = Self::set_running_since)]
         running_since: Cell<Option<glib::GString>>,
 
@@ -99,6 +196,10 @@ mod imp {
         symbolic: Cell<bool>,
 
         pub affinity: RefCell<Vec<bool>>,
+
+        pub io_priority_value: Cell<IoPriority>,
+
+        pub scheduling_policy_value: Cell<SchedulingPolicy>,
     }
 
     impl Default for ProcessEntry {
@@ -109,13 +210,27 @@ mod imp {
                 user: Cell::new(glib::GString::default()),
                 icon: Cell::new(ThemedIcon::new("generic-process").into()),
                 pid: Cell::new(0),
+                parent_pid: Cell::new(0),
+                starttime: Cell::new(0),
+                has_children: Cell::new(false),
                 cpu_usage: Cell::new(0.0),
-                memory_usage: Cell::new(0),
+                memory_usage: Cell::new(-1),
+                subtree_cpu_usage: Cell::new(0.0),
+                subtree_memory_usage: Cell::new(0),
                 swap_usage: Cell::new(0),
                 read_speed: Cell::new(0.0),
                 read_total: Cell::new(0),
                 write_speed: Cell::new(0.0),
                 write_total: Cell::new(0),
+                logical_read_speed: Cell::new(0.0),
+                logical_read_total: Cell::new(0),
+                logical_write_speed: Cell::new(0.0),
+                logical_write_total: Cell::new(0),
+                read_syscall_rate: Cell::new(0.0),
+                write_syscall_rate: Cell::new(0.0),
+                cancelled_write_total: Cell::new(0),
+                cpu_wait: Cell::new(0.0),
+                io_wait: Cell::new(0.0),
                 gpu_usage: Cell::new(0.0),
                 enc_usage: Cell::new(0.0),
                 dec_usage: Cell::new(0.0),
@@ -124,18 +239,47 @@ mod imp {
                 user_cpu_time: Cell::new(0.0),
                 system_cpu_time: Cell::new(0.0),
                 niceness: Cell::new(0),
+                state: Cell::new(glib::GString::default()),
+                io_priority: Cell::new(glib::GString::default()),
+                scheduling_policy: Cell::new(glib::GString::default()),
+                realtime_priority: Cell::new(0),
+                oom_score: Cell::new(0),
+                oom_score_adj: Cell::new(0),
+                fd_count: Cell::new(-1),
+                listening_ports: Cell::new(glib::GString::default()),
+                num_threads: Cell::new(0),
+                voluntary_context_switches: Cell::new(0),
+                involuntary_context_switches: Cell::new(0),
+                minor_faults: Cell::new(0),
+                major_faults: Cell::new(0),
                 cgroup: Cell::new(None),
+                executable: Cell::new(None),
                 containerization: Cell::new(glib::GString::default()),
+                container: Cell::new(glib::GString::default()),
+                container_runtime: Cell::new(glib::GString::default()),
                 running_since: Cell::new(None),
                 symbolic: Cell::new(false),
                 affinity: Default::default(),
+                io_priority_value: Default::default(),
+                scheduling_policy_value: Default::default(),
             }
         }
     }
 
     impl ProcessEntry {
-        gstring_getter_setter!(user, commandline, name, containerization);
-        gstring_option_getter_setter!(cgroup, running_since);
+        gstring_getter_setter!(
+            user,
+            commandline,
+            name,
+            containerization,
+            container,
+            container_runtime,
+            state,
+            io_priority,
+            scheduling_policy,
+            listening_ports
+        );
+        gstring_option_getter_setter!(cgroup, executable, running_since);
 
         pub fn icon(&self) -> Icon {
             let icon = self.icon.replace(ThemedIcon::new("generic-process").into());
@@ -200,20 +344,31 @@ glib::wrapper! {
 
 impl ProcessEntry {
     pub fn new(process: &Process) -> Self {
-        let containerization = match process.data.containerization {
-            Containerization::None => i18n("No"),
-            Containerization::Flatpak => i18n("Yes (Flatpak)"),
-            Containerization::Snap => i18n("Yes (Snap)"),
-        };
-
         let this: Self = glib::Object::builder()
             .property("name", &process.display_name)
             .property("commandline", process.data.commandline.replace('\0', " "))
             .property("user", &process.data.user)
             .property("icon", &process.icon)
             .property("pid", process.data.pid)
+            .property("starttime", process.data.starttime)
             .property("cgroup", process.data.cgroup.clone().map(GString::from))
-            .property("containerization", containerization)
+            .property(
+                "containerization",
+                containerization_label(process.data.containerization),
+            )
+            .property(
+                "container",
+                process
+                    .data
+                    .container
+                    .as_ref()
+                    .map(|container| container.display_name())
+                    .unwrap_or_default(),
+            )
+            .property(
+                "container_runtime",
+                container_runtime_label(process.data.containerization),
+            )
             .property("running_since", process.running_since().ok())
             .build();
         this.update(process);
@@ -221,8 +376,14 @@ impl ProcessEntry {
     }
 
     pub fn update(&self, process: &Process) {
+        self.set_parent_pid(process.data.parent_pid);
+        self.set_property("executable", process.data.exe.clone().map(GString::from));
         self.set_cpu_usage(process.cpu_time_ratio());
-        self.set_memory_usage(process.data.memory_usage as u64);
+        self.set_memory_usage(
+            process
+                .memory_usage(SETTINGS.processes_memory_metric())
+                .map_or(-1, |memory_usage| memory_usage as i64),
+        );
         self.set_swap_usage(process.data.swap_usage as u64);
         self.set_read_speed(process.read_speed().unwrap_or(-1.0));
         self.set_read_total(
@@ -238,6 +399,20 @@ impl ProcessEntry {
                 .write_bytes
                 .map_or(-1, |write_total| write_total as i64),
         );
+        self.set_logical_read_speed(process.logical_read_speed().unwrap_or(-1.0));
+        self.set_logical_read_total(process.data.read_chars.map_or(-1, |total| total as i64));
+        self.set_logical_write_speed(process.logical_write_speed().unwrap_or(-1.0));
+        self.set_logical_write_total(process.data.write_chars.map_or(-1, |total| total as i64));
+        self.set_read_syscall_rate(process.read_syscall_rate().unwrap_or(-1.0));
+        self.set_write_syscall_rate(process.write_syscall_rate().unwrap_or(-1.0));
+        self.set_cancelled_write_total(
+            process
+                .data
+                .cancelled_write_bytes
+                .map_or(-1, |total| total as i64),
+        );
+        self.set_cpu_wait(process.cpu_wait_ratio().unwrap_or(-1.0));
+        self.set_io_wait(process.io_wait_ratio().unwrap_or(-1.0));
         self.set_gpu_usage(process.gpu_usage());
         self.set_enc_usage(process.enc_usage());
         self.set_dec_usage(process.dec_usage());
@@ -246,10 +421,42 @@ impl ProcessEntry {
         self.set_system_cpu_time((process.data.system_cpu_time as f64) / (*TICK_RATE as f64));
         self.set_total_cpu_time(self.user_cpu_time() + self.system_cpu_time());
         self.set_niceness(*process.data.niceness);
+        if process.data.frozen {
+            self.set_state(frozen_state_label());
+        } else {
+            self.set_state(process_state_label(process.data.state));
+        }
+        self.set_io_priority(io_priority_label(process.data.io_priority));
+        self.set_scheduling_policy(scheduling_policy_label(
+            process.data.scheduling_policy,
+            process.data.realtime_priority,
+        ));
+        self.set_realtime_priority(process.data.realtime_priority);
+        self.set_oom_score(process.data.oom_score);
+        self.set_oom_score_adj(process.data.oom_score_adj);
+        self.set_fd_count(process.data.fd_count.map_or(-1, |fd_count| fd_count as i64));
+        self.set_listening_ports(listening_ports_label(&process.data.listening_ports));
+        self.set_num_threads(process.data.num_threads as u64);
+        self.set_voluntary_context_switches(process.data.voluntary_context_switches);
+        self.set_involuntary_context_switches(process.data.involuntary_context_switches);
+        self.set_minor_faults(process.data.minor_faults);
+        self.set_major_faults(process.data.major_faults);
         *self.imp().affinity.borrow_mut() = process.data.affinity.clone();
+        self.imp().io_priority_value.set(process.data.io_priority);
+        self.imp()
+            .scheduling_policy_value
+            .set(process.data.scheduling_policy);
     }
 
     pub fn affinity(&self) -> Vec<bool> {
         self.imp().affinity.borrow().clone()
     }
+
+    pub fn io_priority_value(&self) -> IoPriority {
+        self.imp().io_priority_value.get()
+    }
+
+    pub fn scheduling_policy_value(&self) -> SchedulingPolicy {
+        self.imp().scheduling_policy_value.get()
+    }
 }
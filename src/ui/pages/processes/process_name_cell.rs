@@ -1,6 +1,8 @@
 use adw::{glib::property::PropertySet, prelude::*, subclass::prelude::*};
 use gtk::{gio::Icon, glib};
 
+use crate::i18n::i18n_f;
+
 mod imp {
     use std::cell::{Cell, RefCell};
 
@@ -20,6 +22,8 @@ mod imp {
         pub image: TemplateChild<gtk::Image>,
         #[template_child]
         pub inscription: TemplateChild<gtk::Inscription>,
+        #[template_child]
+        pub container_badge: TemplateChild<gtk::Label>,
 
         #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
         name: Cell<glib::GString>,
@@ -29,6 +33,10 @@ mod imp {
         icon: RefCell<Icon>,
         #[property(get, set = Self::set_symbolic)]
         symbolic: Cell<bool>,
+        #[property(get = Self::container, set = Self::set_container, type = glib::GString)]
+        container: Cell<glib::GString>,
+        #[property(get = Self::container_runtime, set = Self::set_container_runtime, type = glib::GString)]
+        container_runtime: Cell<glib::GString>,
     }
 
     impl Default for ResProcessNameCell {
@@ -36,10 +44,13 @@ mod imp {
             Self {
                 image: Default::default(),
                 inscription: Default::default(),
+                container_badge: Default::default(),
                 name: Default::default(),
                 tooltip: Default::default(),
                 icon: RefCell::new(ThemedIcon::new("generic-process").into()),
                 symbolic: Default::default(),
+                container: Default::default(),
+                container_runtime: Default::default(),
             }
         }
     }
@@ -99,6 +110,34 @@ mod imp {
                 self.image.set_css_classes(&["lowres-icon"]);
             }
         }
+
+        pub fn container(&self) -> glib::GString {
+            let container = self.container.take();
+            self.container.set(container.clone());
+            container
+        }
+
+        pub fn set_container(&self, container: &str) {
+            self.container.set(glib::GString::from(container));
+            self.container_badge.set_label(container);
+            self.container_badge.set_visible(!container.is_empty());
+        }
+
+        pub fn container_runtime(&self) -> glib::GString {
+            let container_runtime = self.container_runtime.take();
+            self.container_runtime.set(container_runtime.clone());
+            container_runtime
+        }
+
+        pub fn set_container_runtime(&self, container_runtime: &str) {
+            self.container_runtime
+                .set(glib::GString::from(container_runtime));
+            self.container_badge.set_tooltip_text(
+                (!container_runtime.is_empty())
+                    .then(|| i18n_f("{} Container", &[container_runtime]))
+                    .as_deref(),
+            );
+        }
     }
 
     #[glib::object_subclass]
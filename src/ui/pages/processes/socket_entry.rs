@@ -0,0 +1,108 @@
+use gtk::glib;
+use process_data::sockets::Socket;
+
+use crate::{
+    i18n::i18n,
+    ui::pages::{protocol_label, socket_state_label},
+};
+
+mod imp {
+    use std::cell::Cell;
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use super::*;
+
+    #[derive(Properties, Default)]
+    #[properties(wrapper_type = super::SocketEntry)]
+    pub struct SocketEntry {
+        #[property(get = Self::protocol, set = Self::set_protocol, type = glib::GString)]
+        protocol: Cell<glib::GString>,
+
+        #[property(get = Self::local_address, set = Self::set_local_address, type = glib::GString)]
+        local_address: Cell<glib::GString>,
+
+        #[property(get = Self::remote_address, set = Self::set_remote_address, type = glib::GString)]
+        remote_address: Cell<glib::GString>,
+
+        #[property(get = Self::state, set = Self::set_state, type = glib::GString)]
+        state: Cell<glib::GString>,
+
+        #[property(get, set)]
+        inode: Cell<u64>,
+
+        /// -1 if the queue size is unknown
+        #[property(get, set)]
+        send_queue: Cell<i64>,
+
+        /// -1 if the queue size is unknown
+        #[property(get, set)]
+        receive_queue: Cell<i64>,
+    }
+
+    impl SocketEntry {
+        gstring_getter_setter!(protocol, local_address, remote_address, state);
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for SocketEntry {
+        const NAME: &'static str = "SocketEntry";
+        type Type = super::SocketEntry;
+    }
+
+    impl ObjectImpl for SocketEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct SocketEntry(ObjectSubclass<imp::SocketEntry>);
+}
+
+impl SocketEntry {
+    pub fn new(socket: &Socket) -> Self {
+        let this: Self = glib::Object::builder()
+            .property("inode", socket.inode)
+            .build();
+        this.update(socket);
+        this
+    }
+
+    pub fn update(&self, socket: &Socket) {
+        let local_address = match (&socket.local_address, &socket.path) {
+            (Some(address), _) => address.to_string(),
+            (None, Some(path)) if !path.is_empty() => path.clone(),
+            _ => i18n("Unnamed"),
+        };
+
+        let remote_address = socket
+            .remote_address
+            .map(|address| address.to_string())
+            .unwrap_or_default();
+
+        self.set_protocol(protocol_label(socket.protocol));
+        self.set_local_address(local_address.as_str());
+        self.set_remote_address(remote_address.as_str());
+        self.set_state(socket_state_label(socket.state));
+        self.set_send_queue(socket.send_queue.map_or(-1, |queue| queue as i64));
+        self.set_receive_queue(socket.receive_queue.map_or(-1, |queue| queue as i64));
+    }
+}
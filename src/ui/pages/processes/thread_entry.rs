@@ -0,0 +1,124 @@
+use gtk::{glib, subclass::prelude::ObjectSubclassIsExt};
+use process_data::ThreadData;
+
+use crate::{
+    ui::pages::process_state_label,
+    utils::{FiniteOr, NUM_CPUS, TICK_RATE},
+};
+
+mod imp {
+    use std::cell::Cell;
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use super::*;
+
+    #[derive(Properties, Default)]
+    #[properties(wrapper_type = super::ThreadEntry)]
+    pub struct ThreadEntry {
+        #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
+        name: Cell<glib::GString>,
+
+        #[property(get = Self::state, set = Self::set_state, type = glib::GString)]
+        state: Cell<glib::GString>,
+
+        #[property(get, set)]
+        tid: Cell<i32>,
+
+        #[property(get, set)]
+        cpu_usage: Cell<f32>,
+
+        #[property(get, set)]
+        user_cpu_time: Cell<f64>,
+
+        #[property(get, set)]
+        system_cpu_time: Cell<f64>,
+
+        #[property(get, set)]
+        last_cpu: Cell<u32>,
+
+        #[property(get, set)]
+        niceness: Cell<i8>,
+
+        /// CPU time of the thread during the last update
+        pub cpu_time_last: Cell<u64>,
+        /// Timestamp of the last update, 0 if there hasn't been one
+        pub timestamp_last: Cell<u64>,
+    }
+
+    impl ThreadEntry {
+        gstring_getter_setter!(name, state);
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ThreadEntry {
+        const NAME: &'static str = "ThreadEntry";
+        type Type = super::ThreadEntry;
+    }
+
+    impl ObjectImpl for ThreadEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct ThreadEntry(ObjectSubclass<imp::ThreadEntry>);
+}
+
+impl ThreadEntry {
+    pub fn new(thread: &ThreadData, timestamp: u64) -> Self {
+        let this: Self = glib::Object::builder().property("tid", thread.tid).build();
+        this.update(thread, timestamp);
+        this
+    }
+
+    /// Updates the entry with `thread` as it was at `timestamp`, the CPU usage is calculated relative to the
+    /// previous update
+    pub fn update(&self, thread: &ThreadData, timestamp: u64) {
+        let imp = self.imp();
+
+        let cpu_time = thread.user_cpu_time.saturating_add(thread.system_cpu_time);
+
+        let cpu_usage = if imp.timestamp_last.get() == 0 {
+            0.0
+        } else {
+            let delta_cpu_time = cpu_time.saturating_sub(imp.cpu_time_last.get()) as f32 * 1000.0;
+            let delta_time = timestamp.saturating_sub(imp.timestamp_last.get());
+
+            (delta_cpu_time
+                / (delta_time
+                    .saturating_mul(*TICK_RATE as u64)
+                    .saturating_mul(*NUM_CPUS as u64)) as f32)
+                .finite_or_default()
+        };
+
+        imp.cpu_time_last.set(cpu_time);
+        imp.timestamp_last.set(timestamp);
+
+        self.set_name(thread.comm.as_str());
+        self.set_state(process_state_label(thread.state));
+        self.set_cpu_usage(cpu_usage);
+        self.set_user_cpu_time((thread.user_cpu_time as f64) / (*TICK_RATE as f64));
+        self.set_system_cpu_time((thread.system_cpu_time as f64) / (*TICK_RATE as f64));
+        self.set_last_cpu(thread.last_cpu as u32);
+        self.set_niceness(*thread.niceness);
+    }
+}
@@ -0,0 +1,1211 @@
+pub mod service_entry;
+
+use std::collections::{HashMap, HashSet};
+
+use adw::ResponseAppearance;
+use adw::{prelude::*, subclass::prelude::*};
+use async_channel::Sender;
+use gtk::glib::{self, clone, closure, MainContext, Object};
+use gtk::{
+    gio, ColumnView, ColumnViewColumn, EventControllerKey, FilterChange, ListItem, NumericSorter,
+    SortType, StringSorter, Widget,
+};
+
+use crate::config::PROFILE;
+use crate::i18n::{i18n, i18n_f};
+use crate::ui::window::{Action, MainWindow};
+use crate::utils::app::AppsContext;
+use crate::utils::settings::SETTINGS;
+use crate::utils::systemd::{UnitAction, UnitKind, UnitsContext};
+use crate::utils::units::{convert_speed, convert_storage};
+use crate::utils::NUM_CPUS;
+
+use self::service_entry::{process_key, ServiceEntry};
+
+pub const TAB_ID: &str = "services";
+
+mod imp {
+    use std::{
+        cell::{Cell, RefCell},
+        sync::OnceLock,
+    };
+
+    use crate::ui::{pages::SERVICES_PRIMARY_ORD, window::Action};
+
+    use super::*;
+
+    use gtk::{
+        gio::{Icon, ThemedIcon},
+        glib::{ParamSpec, Properties, Value},
+        ColumnViewColumn, CompositeTemplate,
+    };
+
+    #[derive(CompositeTemplate, Properties)]
+    #[template(resource = "/net/nokyan/Resources/ui/pages/services.ui")]
+    #[properties(wrapper_type = super::ResServices)]
+    pub struct ResServices {
+        #[template_child]
+        pub toast_overlay: TemplateChild<adw::ToastOverlay>,
+        #[template_child]
+        pub popover_menu: TemplateChild<gtk::PopoverMenu>,
+        #[template_child]
+        pub search_revealer: TemplateChild<gtk::Revealer>,
+        #[template_child]
+        pub search_entry: TemplateChild<gtk::SearchEntry>,
+        #[template_child]
+        pub services_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub services_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub search_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub stop_unit_button: TemplateChild<adw::SplitButton>,
+
+        /// Contains the units that aren't nested in another unit
+        pub root_store: RefCell<gio::ListStore>,
+        /// Key: key of the parent entry, see `ServiceEntry::key()`
+        pub children_stores: RefCell<HashMap<String, gio::ListStore>>,
+        /// All entries that are currently placed in the tree, key: see `ServiceEntry::key()`
+        pub entries: RefCell<HashMap<String, ServiceEntry>>,
+        pub tree_model: RefCell<Option<gtk::TreeListModel>>,
+        pub selection_model: RefCell<gtk::SingleSelection>,
+        pub filter_model: RefCell<gtk::FilterListModel>,
+        pub sort_model: RefCell<gtk::SortListModel>,
+        pub column_view: RefCell<gtk::ColumnView>,
+
+        pub sender: OnceLock<Sender<Action>>,
+
+        pub popped_over_unit: RefCell<Option<ServiceEntry>>,
+
+        pub columns: RefCell<Vec<ColumnViewColumn>>,
+
+        #[property(get)]
+        uses_progress_bar: Cell<bool>,
+
+        #[property(get)]
+        icon: RefCell<Icon>,
+
+        #[property(get = Self::tab_name, type = glib::GString)]
+        tab_name: Cell<glib::GString>,
+
+        #[property(get = Self::tab_detail_string, type = glib::GString)]
+        tab_detail_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_usage_string, set = Self::set_tab_usage_string, type = glib::GString)]
+        tab_usage_string: Cell<glib::GString>,
+
+        #[property(get = Self::tab_id, type = glib::GString)]
+        tab_id: Cell<glib::GString>,
+
+        #[property(get)]
+        graph_locked_max_y: Cell<bool>,
+
+        #[property(get)]
+        primary_ord: Cell<u32>,
+
+        #[property(get)]
+        secondary_ord: Cell<u32>,
+    }
+
+    impl ResServices {
+        gstring_getter_setter!(tab_name, tab_detail_string, tab_usage_string, tab_id);
+    }
+
+    impl Default for ResServices {
+        fn default() -> Self {
+            Self {
+                toast_overlay: Default::default(),
+                popover_menu: Default::default(),
+                search_revealer: Default::default(),
+                search_entry: Default::default(),
+                services_stack: Default::default(),
+                services_scrolled_window: Default::default(),
+                search_button: Default::default(),
+                stop_unit_button: Default::default(),
+                root_store: gio::ListStore::new::<ServiceEntry>().into(),
+                children_stores: Default::default(),
+                entries: Default::default(),
+                tree_model: Default::default(),
+                selection_model: Default::default(),
+                filter_model: Default::default(),
+                sort_model: Default::default(),
+                column_view: Default::default(),
+                sender: Default::default(),
+                popped_over_unit: Default::default(),
+                columns: Default::default(),
+                uses_progress_bar: Cell::new(false),
+                icon: RefCell::new(ThemedIcon::new("system-processes-symbolic").into()),
+                tab_name: Cell::from(glib::GString::from(i18n("Services"))),
+                tab_detail_string: Cell::new(glib::GString::new()),
+                tab_usage_string: Cell::new(glib::GString::new()),
+                tab_id: Cell::new(glib::GString::from(TAB_ID)),
+                graph_locked_max_y: Cell::new(true),
+                primary_ord: Cell::new(SERVICES_PRIMARY_ORD),
+                secondary_ord: Default::default(),
+            }
+        }
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ResServices {
+        const NAME: &'static str = "ResServices";
+        type Type = super::ResServices;
+        type ParentType = adw::Bin;
+
+        fn class_init(klass: &mut Self::Class) {
+            for (name, action) in [
+                ("services.context-start-unit", UnitAction::Start),
+                ("services.context-stop-unit", UnitAction::Stop),
+                ("services.context-restart-unit", UnitAction::Restart),
+            ] {
+                klass.install_action(name, None, move |res_services, _, _| {
+                    if let Some(unit_entry) = res_services.imp().popped_over_unit.borrow().as_ref()
+                    {
+                        res_services.open_unit_action_dialog(unit_entry, action);
+                    }
+                });
+            }
+
+            for (name, action) in [
+                ("services.start-unit", UnitAction::Start),
+                ("services.restart-unit", UnitAction::Restart),
+            ] {
+                klass.install_action(name, None, move |res_services, _, _| {
+                    if let Some(unit_entry) = res_services.get_selected_unit_entry() {
+                        res_services.open_unit_action_dialog(&unit_entry, action);
+                    }
+                });
+            }
+
+            Self::bind_template(klass);
+        }
+
+        // You must call `Widget`'s `init_template()` within `instance_init()`.
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ResServices {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+
+            // Devel Profile
+            if PROFILE == "Devel" {
+                obj.add_css_class("devel");
+            }
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+
+    impl WidgetImpl for ResServices {}
+    impl BinImpl for ResServices {}
+}
+
+glib::wrapper! {
+    pub struct ResServices(ObjectSubclass<imp::ResServices>)
+        @extends gtk::Widget, adw::Bin;
+}
+
+impl Default for ResServices {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResServices {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>()
+    }
+
+    pub fn toggle_search(&self) {
+        let imp = self.imp();
+        imp.search_button.set_active(!imp.search_button.is_active());
+    }
+
+    pub fn close_search(&self) {
+        let imp = self.imp();
+        imp.search_button.set_active(false);
+    }
+
+    pub fn init(&self, sender: Sender<Action>) {
+        let imp = self.imp();
+        imp.sender.set(sender).unwrap();
+
+        self.setup_widgets();
+        self.setup_signals();
+    }
+
+    fn add_gestures(&self, item: &ListItem) {
+        let widget = item.child().unwrap();
+
+        let secondary_click = gtk::GestureClick::new();
+        secondary_click.set_button(3);
+        secondary_click.connect_released(clone!(
+            #[weak]
+            widget,
+            #[weak]
+            item,
+            #[weak(rename_to = this)]
+            self,
+            move |_, _, x, y| {
+                // processes can be managed on the Processes page, there's nothing to do with them here
+                if let Some(entry) = item
+                    .item()
+                    .and_then(entry_from_row)
+                    .filter(|entry| !entry.is_process())
+                {
+                    let imp = this.imp();
+                    let popover_menu = &imp.popover_menu;
+
+                    this.action_set_enabled("services.context-start-unit", entry.can_start());
+                    this.action_set_enabled("services.context-stop-unit", entry.can_stop());
+                    this.action_set_enabled("services.context-restart-unit", entry.can_restart());
+
+                    *imp.popped_over_unit.borrow_mut() = Some(entry);
+
+                    let position = widget
+                        .compute_point(&this, &gtk::graphene::Point::new(x as _, y as _))
+                        .unwrap();
+
+                    popover_menu.set_pointing_to(Some(&gtk::gdk::Rectangle::new(
+                        position.x().round() as i32,
+                        position.y().round() as i32,
+                        1,
+                        1,
+                    )));
+
+                    popover_menu.popup();
+                }
+            }
+        ));
+
+        widget.add_controller(secondary_click);
+    }
+
+    pub fn setup_widgets(&self) {
+        let imp = self.imp();
+
+        imp.popover_menu.set_parent(self);
+
+        *imp.column_view.borrow_mut() = gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = imp.column_view.borrow();
+
+        let mut columns = imp.columns.borrow_mut();
+
+        columns.push(self.add_name_column(&column_view));
+        columns.push(self.add_kind_column(&column_view));
+        columns.push(self.add_memory_column(&column_view));
+        columns.push(self.add_cpu_column(&column_view));
+        columns.push(self.add_read_speed_column(&column_view));
+        columns.push(self.add_write_speed_column(&column_view));
+        columns.push(self.add_tasks_column(&column_view));
+
+        let tree_model = gtk::TreeListModel::new(
+            imp.root_store.borrow().clone(),
+            false,
+            false,
+            clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[upgrade_or_default]
+                move |object| {
+                    object
+                        .downcast_ref::<ServiceEntry>()
+                        .filter(|entry| !entry.is_process())
+                        .map(|entry| {
+                            this.children_store(entry.key().as_str())
+                                .upcast::<gio::ListModel>()
+                        })
+                }
+            ),
+        );
+
+        let filter_model = gtk::FilterListModel::new(
+            Some(tree_model.clone()),
+            Some(gtk::CustomFilter::new(clone!(
+                #[strong(rename_to = this)]
+                self,
+                move |obj| this.search_filter(obj)
+            ))),
+        );
+
+        // the tree list row sorter keeps children below their parents
+        let sort_model = gtk::SortListModel::new(
+            Some(filter_model.clone()),
+            Some(gtk::TreeListRowSorter::new(column_view.sorter())),
+        );
+
+        let selection_model = gtk::SingleSelection::new(Some(sort_model.clone()));
+        selection_model.set_can_unselect(true);
+        selection_model.set_autoselect(false);
+
+        column_view.set_model(Some(&selection_model));
+
+        column_view.sort_by_column(
+            columns
+                .get(SETTINGS.services_sort_by() as usize)
+                .or_else(|| columns.first()),
+            SETTINGS.services_sort_by_ascending(),
+        );
+
+        column_view.add_css_class("resources-columnview");
+
+        *imp.tree_model.borrow_mut() = Some(tree_model);
+        *imp.selection_model.borrow_mut() = selection_model;
+        *imp.sort_model.borrow_mut() = sort_model;
+        *imp.filter_model.borrow_mut() = filter_model;
+
+        imp.services_scrolled_window.set_child(Some(&*column_view));
+
+        imp.services_stack.set_visible_child_name("empty");
+    }
+
+    pub fn setup_signals(&self) {
+        let imp = self.imp();
+
+        imp.selection_model
+            .borrow()
+            .connect_selection_changed(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |_, _, _| {
+                    this.update_action_sensitivity();
+                }
+            ));
+
+        imp.search_button.connect_toggled(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |button| {
+                let imp = this.imp();
+                imp.search_revealer.set_reveal_child(button.is_active());
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+                if button.is_active() {
+                    imp.search_entry.grab_focus();
+                }
+            }
+        ));
+
+        imp.search_entry.connect_search_changed(clone!(
+            #[strong(rename_to = this)]
+            self,
+            move |_| {
+                let imp = this.imp();
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+                this.expand_search_matches();
+            }
+        ));
+
+        let event_controller = EventControllerKey::new();
+        event_controller.connect_key_released(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_, key, _, _| {
+                if key.name().unwrap_or_default() == "Escape" {
+                    this.close_search();
+                }
+            }
+        ));
+        imp.search_entry.add_controller(event_controller);
+
+        imp.stop_unit_button.connect_clicked(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_| {
+                if let Some(unit_entry) = this.get_selected_unit_entry() {
+                    this.open_unit_action_dialog(&unit_entry, UnitAction::Stop);
+                }
+            }
+        ));
+
+        if let Some(column_view_sorter) = imp.column_view.borrow().sorter() {
+            column_view_sorter.connect_changed(clone!(
+                #[weak(rename_to = this)]
+                self,
+                move |sorter, _| {
+                    if let Some(sorter) = sorter.downcast_ref::<gtk::ColumnViewSorter>() {
+                        let current_column = sorter
+                            .primary_sort_column()
+                            .map(|column| column.as_ptr() as usize)
+                            .unwrap_or_default();
+
+                        let current_column_number = this
+                            .imp()
+                            .columns
+                            .borrow()
+                            .iter()
+                            .enumerate()
+                            .find(|(_, column)| column.as_ptr() as usize == current_column)
+                            .map_or(0, |(i, _)| i as u32); // 0 corresponds to the name column
+
+                        if SETTINGS.services_sort_by() != current_column_number {
+                            let _ = SETTINGS.set_services_sort_by(current_column_number);
+                        }
+
+                        if SETTINGS.services_sort_by_ascending() != sorter.primary_sort_order() {
+                            let _ = SETTINGS
+                                .set_services_sort_by_ascending(sorter.primary_sort_order());
+                        }
+                    }
+                }
+            ));
+        }
+    }
+
+    fn update_action_sensitivity(&self) {
+        let imp = self.imp();
+
+        let selected = self.get_selected_unit_entry();
+
+        imp.stop_unit_button
+            .set_sensitive(selected.as_ref().is_some_and(ServiceEntry::can_stop));
+
+        self.action_set_enabled(
+            "services.start-unit",
+            selected.as_ref().is_some_and(ServiceEntry::can_start),
+        );
+        self.action_set_enabled(
+            "services.restart-unit",
+            selected.as_ref().is_some_and(ServiceEntry::can_restart),
+        );
+    }
+
+    fn search_string(&self) -> Option<String> {
+        let imp = self.imp();
+        let search_string = imp.search_entry.text().to_lowercase();
+        (imp.search_revealer.reveals_child() && !search_string.is_empty()).then_some(search_string)
+    }
+
+    fn search_filter(&self, obj: &Object) -> bool {
+        let Some(entry) = entry_from_row(obj.clone()) else {
+            return false;
+        };
+        self.search_string().map_or(true, |search_string| {
+            self.subtree_matches(&entry, &search_string)
+        })
+    }
+
+    /// Returns whether `entry` or any of the units and processes nested in it match `search_string`
+    fn subtree_matches(&self, entry: &ServiceEntry, search_string: &str) -> bool {
+        entry.name().to_lowercase().contains(search_string)
+            || self.children_match(entry, search_string)
+    }
+
+    fn children_match(&self, entry: &ServiceEntry, search_string: &str) -> bool {
+        let children_store = self
+            .imp()
+            .children_stores
+            .borrow()
+            .get(entry.key().as_str())
+            .cloned();
+
+        children_store.is_some_and(|children_store| {
+            children_store
+                .iter::<ServiceEntry>()
+                .flatten()
+                .any(|child| self.subtree_matches(&child, search_string))
+        })
+    }
+
+    /// Expands the rows of all units that have descendants matching the current search
+    fn expand_search_matches(&self) {
+        let imp = self.imp();
+
+        let Some(tree_model) = imp.tree_model.borrow().clone() else {
+            return;
+        };
+
+        let Some(search_string) = self.search_string() else {
+            return;
+        };
+
+        // expanding a row inserts its children right below it, so they will be visited as well
+        let mut position = 0;
+        while let Some(row) = tree_model.row(position) {
+            if !row.is_expanded()
+                && row
+                    .item()
+                    .and_downcast::<ServiceEntry>()
+                    .is_some_and(|entry| self.children_match(&entry, &search_string))
+            {
+                row.set_expanded(true);
+            }
+            position += 1;
+        }
+    }
+
+    /// Returns the selected entry if it's a unit rather than a process
+    pub fn get_selected_unit_entry(&self) -> Option<ServiceEntry> {
+        self.imp()
+            .selection_model
+            .borrow()
+            .selected_item()
+            .and_then(entry_from_row)
+            .filter(|entry| !entry.is_process())
+    }
+
+    /// Returns the children store of the entry with the given key, creating it if it doesn't exist yet
+    fn children_store(&self, key: &str) -> gio::ListStore {
+        self.imp()
+            .children_stores
+            .borrow_mut()
+            .entry(key.to_string())
+            .or_insert_with(gio::ListStore::new::<ServiceEntry>)
+            .clone()
+    }
+
+    /// Returns the store the entry with the given parent key is placed in
+    fn parent_store(&self, parent_key: Option<&str>) -> gio::ListStore {
+        match parent_key {
+            Some(parent_key) => self.children_store(parent_key),
+            None => self.imp().root_store.borrow().clone(),
+        }
+    }
+
+    pub fn refresh_units_list(&self, units_context: &UnitsContext, apps_context: &AppsContext) {
+        let imp = self.imp();
+
+        let mut entries = imp.entries.borrow_mut();
+
+        let mut alive_keys = HashSet::new();
+        let mut new_entries = Vec::new();
+
+        for unit in units_context.units_iter() {
+            let unit_key = &unit.data.cgroup_path;
+
+            if let Some(entry) = entries.get(unit_key) {
+                entry.update_unit(unit);
+            } else {
+                let entry = ServiceEntry::from_unit(unit);
+                entries.insert(unit_key.clone(), entry.clone());
+                new_entries.push(entry);
+            }
+            alive_keys.insert(unit_key.clone());
+
+            for pid in &unit.data.pids {
+                let key = process_key(unit_key, *pid);
+                let process = apps_context.get_process(*pid);
+
+                if let Some(entry) = entries.get(&key) {
+                    entry.update_process(*pid, process);
+                } else {
+                    let entry = ServiceEntry::from_process(unit_key, *pid, process);
+                    entries.insert(key.clone(), entry.clone());
+                    new_entries.push(entry);
+                }
+                alive_keys.insert(key);
+            }
+        }
+
+        let mut to_remove: HashMap<Option<String>, HashSet<String>> = HashMap::new();
+        entries.retain(|key, entry| {
+            let alive = alive_keys.contains(key);
+            if !alive {
+                to_remove
+                    .entry(entry.parent_key().map(|parent_key| parent_key.to_string()))
+                    .or_default()
+                    .insert(key.clone());
+            }
+            alive
+        });
+
+        let mut to_add: HashMap<Option<String>, Vec<ServiceEntry>> = HashMap::new();
+        for entry in new_entries {
+            to_add
+                .entry(entry.parent_key().map(|parent_key| parent_key.to_string()))
+                .or_default()
+                .push(entry);
+        }
+
+        let unit_entries: Vec<ServiceEntry> = entries
+            .values()
+            .filter(|entry| !entry.is_process())
+            .cloned()
+            .collect();
+
+        // the stores must not be borrowed while they're being modified because the tree list model and the filter may
+        // call back into us
+        std::mem::drop(entries);
+
+        for (parent_key, keys) in to_remove {
+            self.parent_store(parent_key.as_deref()).retain(|object| {
+                !keys.contains(
+                    object
+                        .downcast_ref::<ServiceEntry>()
+                        .unwrap()
+                        .key()
+                        .as_str(),
+                )
+            });
+        }
+
+        imp.children_stores
+            .borrow_mut()
+            .retain(|key, _| alive_keys.contains(key));
+
+        for (parent_key, new_entries) in to_add {
+            self.parent_store(parent_key.as_deref())
+                .extend_from_slice(&new_entries);
+        }
+
+        for entry in unit_entries {
+            let has_children = imp
+                .children_stores
+                .borrow()
+                .get(entry.key().as_str())
+                .is_some_and(|store| store.n_items() > 0);
+            entry.set_has_children(has_children);
+        }
+
+        if imp
+            .popped_over_unit
+            .borrow()
+            .as_ref()
+            .is_some_and(|entry| !alive_keys.contains(entry.key().as_str()))
+        {
+            *imp.popped_over_unit.borrow_mut() = None;
+            imp.popover_menu.set_visible(false);
+        }
+
+        if let Some(sorter) = imp.column_view.borrow().sorter() {
+            sorter.changed(gtk::SorterChange::Different);
+        }
+
+        // processes come and go, so matches might have appeared or vanished
+        if self.search_string().is_some() {
+            if let Some(filter) = imp.filter_model.borrow().filter() {
+                filter.changed(FilterChange::Different);
+            }
+            self.expand_search_matches();
+        }
+
+        let root_items = imp.root_store.borrow().n_items();
+        imp.services_stack
+            .set_visible_child_name(if root_items == 0 { "empty" } else { "services" });
+
+        let services = units_context
+            .units_iter()
+            .filter(|unit| unit.data.kind == UnitKind::Service && unit.data.running)
+            .count();
+
+        self.set_tab_usage_string(i18n_f("Running Services: {}", &[&services.to_string()]));
+
+        self.update_action_sensitivity();
+    }
+
+    pub fn open_unit_action_dialog(&self, unit_entry: &ServiceEntry, action: UnitAction) {
+        // Nothing too bad can happen on Start so dont show the dialog
+        if action == UnitAction::Start {
+            let main_context = MainContext::default();
+            main_context.spawn_local(clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[weak]
+                unit_entry,
+                async move {
+                    let imp = this.imp();
+                    let _ = imp
+                        .sender
+                        .get()
+                        .unwrap()
+                        .send(Action::ManipulateUnit(
+                            action,
+                            unit_entry.key().to_string(),
+                            imp.toast_overlay.get(),
+                        ))
+                        .await;
+                }
+            ));
+            return;
+        }
+
+        // Confirmation dialog & warning
+        let dialog = adw::AlertDialog::builder()
+            .heading(get_action_name(action, &unit_entry.name()))
+            .body(get_action_warning(action))
+            .build();
+
+        dialog.add_response("yes", &get_action_description(action));
+        dialog.set_response_appearance("yes", ResponseAppearance::Destructive);
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.set_default_response(Some("no"));
+        dialog.set_close_response("no");
+
+        // Called when "yes" or "no" were clicked
+        dialog.connect_response(
+            None,
+            clone!(
+                #[weak(rename_to = this)]
+                self,
+                #[weak]
+                unit_entry,
+                move |_, response| {
+                    if response == "yes" {
+                        let main_context = MainContext::default();
+                        main_context.spawn_local(clone!(
+                            #[weak]
+                            this,
+                            #[strong]
+                            unit_entry,
+                            async move {
+                                let imp = this.imp();
+                                let _ = imp
+                                    .sender
+                                    .get()
+                                    .unwrap()
+                                    .send(Action::ManipulateUnit(
+                                        action,
+                                        unit_entry.key().to_string(),
+                                        imp.toast_overlay.get(),
+                                    ))
+                                    .await;
+                            }
+                        ));
+                    }
+                }
+            ),
+        );
+
+        dialog.present(Some(&MainWindow::default()));
+    }
+
+    fn add_name_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let name_col_factory = gtk::SignalListItemFactory::new();
+
+        let name_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Unit")), Some(name_col_factory.clone()));
+
+        name_col.set_resizable(true);
+
+        name_col.set_expand(true);
+
+        name_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(16);
+
+                let expander = gtk::TreeExpander::new();
+                expander.set_child(Some(&row));
+
+                item.set_child(Some(&expander));
+
+                item.property_expression("item")
+                    .bind(&expander, "list-row", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ServiceEntry>("has_children")
+                    .chain_closure::<bool>(closure!(|_: Option<Object>, has_children: bool| {
+                        !has_children
+                    }))
+                    .bind(&expander, "hide-expander", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ServiceEntry>("name")
+                    .bind(&row, "text", Widget::NONE);
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ServiceEntry>("name")
+                    .bind(&row, "tooltip-text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        name_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::TreeExpander>);
+        });
+
+        let name_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ServiceEntry::static_type(),
+                None::<&gtk::Expression>,
+                "name",
+            ))
+            .build();
+
+        name_col.set_sorter(Some(&name_col_sorter));
+
+        column_view.append_column(&name_col);
+
+        name_col
+    }
+
+    fn add_kind_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let kind_col_factory = gtk::SignalListItemFactory::new();
+
+        let kind_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Type")), Some(kind_col_factory.clone()));
+
+        kind_col.set_resizable(true);
+
+        kind_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ServiceEntry>("kind")
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        kind_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let kind_col_sorter = StringSorter::builder()
+            .ignore_case(true)
+            .expression(gtk::PropertyExpression::new(
+                ServiceEntry::static_type(),
+                None::<&gtk::Expression>,
+                "kind",
+            ))
+            .build();
+
+        kind_col.set_sorter(Some(&kind_col_sorter));
+
+        column_view.append_column(&kind_col);
+
+        kind_col
+    }
+
+    fn add_memory_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let memory_col_factory = gtk::SignalListItemFactory::new();
+
+        let memory_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Memory")), Some(memory_col_factory.clone()));
+
+        memory_col.set_resizable(true);
+
+        memory_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+
+                row.set_min_chars(9);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ServiceEntry>("memory_usage")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: u64| {
+                        convert_storage(memory_usage as f64, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        memory_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let memory_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ServiceEntry::static_type(),
+                None::<&gtk::Expression>,
+                "memory_usage",
+            ))
+            .build();
+
+        memory_col.set_sorter(Some(&memory_col_sorter));
+
+        column_view.append_column(&memory_col);
+
+        memory_col
+    }
+
+    fn add_cpu_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let cpu_col_factory = gtk::SignalListItemFactory::new();
+
+        let cpu_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Processor")), Some(cpu_col_factory.clone()));
+
+        cpu_col.set_resizable(true);
+
+        cpu_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(7);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ServiceEntry>("cpu_usage")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
+                        let mut percentage = cpu_usage * 100.0;
+                        if !SETTINGS.normalize_cpu_usage() {
+                            percentage *= *NUM_CPUS as f32;
+                        }
+
+                        format!("{percentage:.1} %")
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        cpu_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let cpu_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ServiceEntry::static_type(),
+                None::<&gtk::Expression>,
+                "cpu_usage",
+            ))
+            .build();
+
+        cpu_col.set_sorter(Some(&cpu_col_sorter));
+
+        column_view.append_column(&cpu_col);
+
+        cpu_col
+    }
+
+    fn add_read_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let read_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let read_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Read")),
+            Some(read_speed_col_factory.clone()),
+        );
+
+        read_speed_col.set_resizable(true);
+
+        read_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ServiceEntry>("read_speed")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, read_speed: f64| {
+                        convert_speed(read_speed, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        read_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let read_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ServiceEntry::static_type(),
+                None::<&gtk::Expression>,
+                "read_speed",
+            ))
+            .build();
+
+        read_speed_col.set_sorter(Some(&read_speed_col_sorter));
+
+        column_view.append_column(&read_speed_col);
+
+        read_speed_col
+    }
+
+    fn add_write_speed_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let write_speed_col_factory = gtk::SignalListItemFactory::new();
+
+        let write_speed_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Drive Write")),
+            Some(write_speed_col_factory.clone()),
+        );
+
+        write_speed_col.set_resizable(true);
+
+        write_speed_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(11);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ServiceEntry>("write_speed")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, write_speed: f64| {
+                        convert_speed(write_speed, false)
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        write_speed_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let write_speed_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ServiceEntry::static_type(),
+                None::<&gtk::Expression>,
+                "write_speed",
+            ))
+            .build();
+
+        write_speed_col.set_sorter(Some(&write_speed_col_sorter));
+
+        column_view.append_column(&write_speed_col);
+
+        write_speed_col
+    }
+
+    fn add_tasks_column(&self, column_view: &ColumnView) -> ColumnViewColumn {
+        let tasks_col_factory = gtk::SignalListItemFactory::new();
+
+        let tasks_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Tasks")), Some(tasks_col_factory.clone()));
+
+        tasks_col.set_resizable(true);
+
+        tasks_col_factory.connect_setup(clone!(
+            #[weak(rename_to = this)]
+            self,
+            move |_factory, item| {
+                let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+
+                let row = gtk::Inscription::new(None);
+                row.set_min_chars(5);
+
+                item.set_child(Some(&row));
+
+                item.property_expression("item")
+                    .chain_property::<gtk::TreeListRow>("item")
+                    .chain_property::<ServiceEntry>("tasks")
+                    .chain_closure::<String>(closure!(|_: Option<Object>, tasks: u64| {
+                        tasks.to_string()
+                    }))
+                    .bind(&row, "text", Widget::NONE);
+
+                this.add_gestures(item);
+            }
+        ));
+
+        tasks_col_factory.connect_teardown(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            item.set_child(None::<&gtk::Inscription>);
+        });
+
+        let tasks_col_sorter = NumericSorter::builder()
+            .sort_order(SortType::Ascending)
+            .expression(gtk::PropertyExpression::new(
+                ServiceEntry::static_type(),
+                None::<&gtk::Expression>,
+                "tasks",
+            ))
+            .build();
+
+        tasks_col.set_sorter(Some(&tasks_col_sorter));
+
+        column_view.append_column(&tasks_col);
+
+        tasks_col
+    }
+}
+
+/// Returns the entry of a row of the column view's model
+fn entry_from_row(object: Object) -> Option<ServiceEntry> {
+    object
+        .downcast::<gtk::TreeListRow>()
+        .ok()
+        .and_then(|row| row.item())
+        .and_downcast::<ServiceEntry>()
+}
+
+fn get_action_name(action: UnitAction, name: &str) -> String {
+    match action {
+        UnitAction::Start => i18n_f("Start {}?", &[name]),
+        UnitAction::Stop => i18n_f("Stop {}?", &[name]),
+        UnitAction::Restart => i18n_f("Restart {}?", &[name]),
+    }
+}
+
+fn get_action_warning(action: UnitAction) -> String {
+    match action {
+            UnitAction::Start => String::new(),
+            UnitAction::Stop => i18n("Stopping a unit also stops the units that depend on it. Stopping a slice stops all units in it."),
+            UnitAction::Restart => i18n("Restarting a unit interrupts whatever it's currently doing, which might cause connections to it to drop."),
+        }
+}
+
+fn get_action_description(action: UnitAction) -> String {
+    match action {
+        UnitAction::Start => i18n("Start Unit"),
+        UnitAction::Stop => i18n("Stop Unit"),
+        UnitAction::Restart => i18n("Restart Unit"),
+    }
+}
@@ -0,0 +1,197 @@
+use gtk::glib::{self, GString};
+
+use crate::{
+    i18n::i18n,
+    ui::pages::unit_kind_label,
+    utils::{
+        process::Process,
+        settings::SETTINGS,
+        systemd::{Unit, UnitAction},
+    },
+};
+
+mod imp {
+    use std::cell::Cell;
+
+    use gtk::{
+        glib::{ParamSpec, Properties, Value},
+        prelude::ObjectExt,
+        subclass::prelude::{DerivedObjectProperties, ObjectImpl, ObjectImplExt, ObjectSubclass},
+    };
+
+    use super::*;
+
+    #[derive(Properties)]
+    #[properties(wrapper_type = super::ServiceEntry)]
+    pub struct ServiceEntry {
+        /// The cgroup path for units, see `process_key()` for processes
+        #[property(get = Self::key, set = Self::set_key, type = glib::GString)]
+        key: Cell<glib::GString>,
+
+        /// The key of the unit this row is shown below, `None` for top-level units
+        #[property(get = Self::parent_key, set = Self::set_parent_key, type = Option<glib::GString>)]
+        parent_key: Cell<Option<glib::GString>>,
+
+        #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
+        name: Cell<glib::GString>,
+
+        #[property(get = Self::kind, set = Self::set_kind, type = glib::GString)]
+        kind: Cell<glib::GString>,
+
+        #[property(get, set)]
+        is_process: Cell<bool>,
+
+        #[property(get, set)]
+        has_children: Cell<bool>,
+
+        #[property(get, set)]
+        cpu_usage: Cell<f32>,
+
+        #[property(get, set)]
+        memory_usage: Cell<u64>,
+
+        #[property(get, set)]
+        read_speed: Cell<f64>,
+
+        #[property(get, set)]
+        write_speed: Cell<f64>,
+
+        #[property(get, set)]
+        tasks: Cell<u64>,
+
+        #[property(get, set)]
+        can_start: Cell<bool>,
+
+        #[property(get, set)]
+        can_stop: Cell<bool>,
+
+        #[property(get, set)]
+        can_restart: Cell<bool>,
+    }
+
+    impl Default for ServiceEntry {
+        fn default() -> Self {
+            Self {
+                key: Cell::new(glib::GString::default()),
+                parent_key: Cell::new(None),
+                name: Cell::new(glib::GString::default()),
+                kind: Cell::new(glib::GString::default()),
+                is_process: Cell::new(false),
+                has_children: Cell::new(false),
+                cpu_usage: Cell::new(0.0),
+                memory_usage: Cell::new(0),
+                read_speed: Cell::new(0.0),
+                write_speed: Cell::new(0.0),
+                tasks: Cell::new(0),
+                can_start: Cell::new(false),
+                can_stop: Cell::new(false),
+                can_restart: Cell::new(false),
+            }
+        }
+    }
+
+    impl ServiceEntry {
+        gstring_getter_setter!(key, name, kind);
+        gstring_option_getter_setter!(parent_key);
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for ServiceEntry {
+        const NAME: &'static str = "ServiceEntry";
+        type Type = super::ServiceEntry;
+    }
+
+    impl ObjectImpl for ServiceEntry {
+        fn constructed(&self) {
+            self.parent_constructed();
+        }
+
+        fn properties() -> &'static [ParamSpec] {
+            Self::derived_properties()
+        }
+
+        fn set_property(&self, id: usize, value: &Value, pspec: &ParamSpec) {
+            self.derived_set_property(id, value, pspec);
+        }
+
+        fn property(&self, id: usize, pspec: &ParamSpec) -> Value {
+            self.derived_property(id, pspec)
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct ServiceEntry(ObjectSubclass<imp::ServiceEntry>);
+}
+
+impl ServiceEntry {
+    pub fn from_unit(unit: &Unit) -> Self {
+        let reachable = unit.data.manager.is_reachable();
+
+        let this: Self = glib::Object::builder()
+            .property("key", &unit.data.cgroup_path)
+            .property("parent_key", unit.data.parent.as_deref().map(GString::from))
+            .property("name", &unit.data.name)
+            .property("kind", unit_kind_label(unit.data.kind))
+            .property("is_process", false)
+            .property(
+                "can_start",
+                reachable && UnitAction::Start.is_supported_by(unit.data.kind),
+            )
+            .property(
+                "can_stop",
+                reachable && UnitAction::Stop.is_supported_by(unit.data.kind),
+            )
+            .property(
+                "can_restart",
+                reachable && UnitAction::Restart.is_supported_by(unit.data.kind),
+            )
+            .build();
+        this.update_unit(unit);
+        this
+    }
+
+    /// Creates the row of a process in the unit with the cgroup path `unit_key`, `process` is `None` if the process is
+    /// not known (yet)
+    pub fn from_process(unit_key: &str, pid: libc::pid_t, process: Option<&Process>) -> Self {
+        let this: Self = glib::Object::builder()
+            .property("key", process_key(unit_key, pid))
+            .property("parent_key", Some(GString::from(unit_key)))
+            .property("kind", i18n("Process"))
+            .property("is_process", true)
+            .build();
+        this.update_process(pid, process);
+        this
+    }
+
+    pub fn update_unit(&self, unit: &Unit) {
+        let counters = &unit.data.counters;
+        self.set_cpu_usage(unit.cpu_usage());
+        self.set_memory_usage(counters.memory_current.unwrap_or_default());
+        self.set_read_speed(unit.read_speed().unwrap_or_default());
+        self.set_write_speed(unit.write_speed().unwrap_or_default());
+        self.set_tasks(counters.pids_current.unwrap_or_default());
+    }
+
+    pub fn update_process(&self, pid: libc::pid_t, process: Option<&Process>) {
+        if let Some(process) = process {
+            self.set_name(format!("{} ({pid})", process.display_name));
+            self.set_cpu_usage(process.cpu_time_ratio());
+            self.set_memory_usage(
+                process
+                    .memory_usage(SETTINGS.processes_memory_metric())
+                    .unwrap_or_default() as u64,
+            );
+            self.set_read_speed(process.read_speed().unwrap_or_default());
+            self.set_write_speed(process.write_speed().unwrap_or_default());
+            self.set_tasks(process.data.num_threads as u64);
+        } else {
+            self.set_name(pid.to_string());
+        }
+    }
+}
+
+/// Returns the key of the row of the process with the given PID in the unit with the cgroup path `unit_key`
+pub fn process_key(unit_key: &str, pid: libc::pid_t) -> String {
+    format!("{unit_key}/{pid}")
+}
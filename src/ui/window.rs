@@ -1,4 +1,5 @@
-use process_data::{Niceness, ProcessData};
+use process_data::{details::ProcessDetails, limits::ResourceLimit, ProcessData};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -15,29 +16,43 @@ use crate::gui::ARGS;
 use crate::i18n::{i18n, i18n_f, ni18n_f};
 use crate::ui::pages::applications::ResApplications;
 use crate::ui::pages::battery::ResBattery;
+use crate::ui::pages::containers::ResContainers;
 use crate::ui::pages::drive::ResDrive;
 use crate::ui::pages::processes::ResProcesses;
+use crate::ui::pages::services::ResServices;
 use crate::utils::app::AppsContext;
 use crate::utils::battery::{Battery, BatteryData};
+use crate::utils::container::{container_cgroups_data, ContainerCgroupData};
 use crate::utils::cpu::{self, CpuData};
 use crate::utils::drive::{Drive, DriveData};
 use crate::utils::gpu::{Gpu, GpuData};
 use crate::utils::memory::MemoryData;
 use crate::utils::network::{NetworkData, NetworkInterface};
 use crate::utils::npu::{Npu, NpuData};
-use crate::utils::process::{Process, ProcessAction};
+use crate::utils::process::{Process, ProcessAction, ProcessAdjustment, ProcessReplacedError};
 use crate::utils::settings::SETTINGS;
+use crate::utils::systemd::{
+    apply_limits, execute_unit_action, io_limit_devices, CgroupLimits, DBusSystemd, UnitAction,
+    UnitData, UnitsContext,
+};
 
 use super::pages::gpu::ResGPU;
 use super::pages::network::ResNetwork;
 use super::pages::npu::ResNPU;
-use super::pages::{applications, processes};
+use super::pages::{applications, containers, processes, services};
 
 #[derive(Debug, Clone)]
 pub enum Action {
     ManipulateProcesses(ProcessAction, Vec<libc::pid_t>, ToastOverlay),
+    ManipulateProcessTree(ProcessAction, libc::pid_t, ToastOverlay),
     ManipulateApp(ProcessAction, String, ToastOverlay),
-    AdjustProcess(libc::pid_t, Niceness, Vec<bool>, String, ToastOverlay),
+    ManipulateContainer(ProcessAction, String, ToastOverlay),
+    ManipulateUnit(UnitAction, String, ToastOverlay),
+    /// The ID of the app, its limits when they were shown to the user and the limits to apply
+    LimitApp(String, CgroupLimits, CgroupLimits, ToastOverlay),
+    SendSignal(i32, String, Vec<libc::pid_t>, ToastOverlay),
+    AdjustProcess(libc::pid_t, ProcessAdjustment, String, ToastOverlay),
+    SetProcessLimit(libc::pid_t, ResourceLimit, String, ToastOverlay),
 }
 
 mod imp {
@@ -46,8 +61,8 @@ mod imp {
     use crate::{
         ui::{
             pages::{
-                applications::ResApplications, cpu::ResCPU, memory::ResMemory,
-                processes::ResProcesses,
+                applications::ResApplications, containers::ResContainers, cpu::ResCPU,
+                memory::ResMemory, processes::ResProcesses, services::ResServices,
             },
             widgets::stack_sidebar::ResStackSidebar,
         },
@@ -84,6 +99,14 @@ mod imp {
         #[template_child]
         pub processes_page: TemplateChild<gtk::StackPage>,
         #[template_child]
+        pub containers: TemplateChild<ResContainers>,
+        #[template_child]
+        pub containers_page: TemplateChild<gtk::StackPage>,
+        #[template_child]
+        pub services: TemplateChild<ResServices>,
+        #[template_child]
+        pub services_page: TemplateChild<gtk::StackPage>,
+        #[template_child]
         pub memory: TemplateChild<ResMemory>,
         #[template_child]
         pub memory_page: TemplateChild<gtk::StackPage>,
@@ -100,6 +123,8 @@ mod imp {
 
         pub apps_context: RefCell<AppsContext>,
 
+        pub units_context: RefCell<UnitsContext>,
+
         pub sender: Sender<Action>,
         pub receiver: RefCell<Option<Receiver<Action>>>,
     }
@@ -120,11 +145,16 @@ mod imp {
                 applications_page: TemplateChild::default(),
                 processes: TemplateChild::default(),
                 processes_page: TemplateChild::default(),
+                containers: TemplateChild::default(),
+                containers_page: TemplateChild::default(),
+                services: TemplateChild::default(),
+                services_page: TemplateChild::default(),
                 cpu: TemplateChild::default(),
                 cpu_page: TemplateChild::default(),
                 memory: TemplateChild::default(),
                 memory_page: TemplateChild::default(),
                 apps_context: Default::default(),
+                units_context: Default::default(),
                 sender,
                 receiver,
                 processor_window_title: TemplateChild::default(),
@@ -202,6 +232,10 @@ struct RefreshData {
     battery_paths: Vec<PathBuf>,
     battery_data: Vec<BatteryData>,
     process_data: Vec<ProcessData>,
+    process_details: Option<ProcessDetails>,
+    /// Key: path of the container's cgroup
+    container_cgroups_data: HashMap<String, ContainerCgroupData>,
+    units_data: Vec<UnitData>,
 }
 
 impl MainWindow {
@@ -245,6 +279,10 @@ impl MainWindow {
             imp.applications.toggle_search();
         } else if selected_page.is::<ResProcesses>() {
             imp.processes.toggle_search();
+        } else if selected_page.is::<ResContainers>() {
+            imp.containers.toggle_search();
+        } else if selected_page.is::<ResServices>() {
+            imp.services.toggle_search();
         }
     }
 
@@ -264,6 +302,11 @@ impl MainWindow {
                 imp.processes
                     .open_process_action_dialog(selected, process_action);
             }
+        } else if selected_page.is::<ResContainers>() {
+            if let Some(container) = imp.containers.get_selected_container_entry() {
+                imp.containers
+                    .open_container_action_dialog(&container, process_action);
+            }
         }
     }
 
@@ -284,6 +327,19 @@ impl MainWindow {
         }
     }
 
+    pub fn shortcut_send_signal(&self) {
+        let imp = self.imp();
+
+        let selected_page = self.get_selected_page().unwrap();
+
+        if selected_page.is::<ResProcesses>() {
+            let selected = imp.processes.get_selected_process_entries();
+            if !selected.is_empty() {
+                imp.processes.open_send_signal_dialog(selected);
+            }
+        }
+    }
+
     pub fn shortcut_process_options(&self) {
         let imp = self.imp();
 
@@ -369,20 +425,37 @@ impl MainWindow {
         imp.resources_sidebar.set_stack(&imp.content_stack);
 
         if SETTINGS.show_search_on_start() {
-            // we want the search bar to show up for both but also let the last viewed page grab the focus, so order is
-            // important here
-            if SETTINGS.last_viewed_page() == applications::TAB_ID {
-                imp.processes.toggle_search();
-                imp.applications.toggle_search();
-            } else if SETTINGS.last_viewed_page() == processes::TAB_ID {
-                imp.applications.toggle_search();
-                imp.processes.toggle_search();
+            let toggle_search = |tab_id: &str| match tab_id {
+                applications::TAB_ID => imp.applications.toggle_search(),
+                processes::TAB_ID => imp.processes.toggle_search(),
+                containers::TAB_ID => imp.containers.toggle_search(),
+                services::TAB_ID => imp.services.toggle_search(),
+                _ => {}
+            };
+
+            // we want the search bar to show up for all pages that have one but also let the last viewed page grab
+            // the focus, so its search is toggled last
+            let last_viewed_page = SETTINGS.last_viewed_page();
+            let searchable_pages = [
+                applications::TAB_ID,
+                processes::TAB_ID,
+                containers::TAB_ID,
+                services::TAB_ID,
+            ];
+
+            if searchable_pages.contains(&last_viewed_page.as_str()) {
+                searchable_pages
+                    .into_iter()
+                    .filter(|tab_id| *tab_id != last_viewed_page)
+                    .for_each(toggle_search);
+                toggle_search(&last_viewed_page);
             }
         }
 
         if ARGS.disable_process_monitoring {
             self.remove_page(imp.applications_page.child().downcast_ref().unwrap());
             self.remove_page(imp.processes_page.child().downcast_ref().unwrap());
+            self.remove_page(imp.containers_page.child().downcast_ref().unwrap());
         } else {
             *imp.apps_context.borrow_mut() = AppsContext::new(
                 gpus.iter()
@@ -392,6 +465,13 @@ impl MainWindow {
             );
             imp.applications.init(imp.sender.clone());
             imp.processes.init(imp.sender.clone());
+            imp.containers.init(imp.sender.clone());
+        }
+
+        if ARGS.disable_services_monitoring {
+            self.remove_page(imp.services_page.child().downcast_ref().unwrap());
+        } else {
+            imp.services.init(imp.sender.clone());
         }
 
         if ARGS.disable_cpu_monitoring {
@@ -500,6 +580,34 @@ impl MainWindow {
                 .unwrap_or_default()
         };
 
+        let process_details = if ARGS.disable_process_monitoring {
+            None
+        } else {
+            Process::inspected_details()
+                .inspect_err(|e| {
+                    warn!(
+                        "Unable to update details of the inspected process!\n{e}\n{}",
+                        e.backtrace()
+                    );
+                })
+                .unwrap_or_default()
+        };
+
+        let container_cgroups_data = container_cgroups_data(&process_data);
+
+        let units_data = if ARGS.disable_services_monitoring {
+            Vec::new()
+        } else {
+            UnitData::all()
+                .inspect_err(|e| {
+                    warn!(
+                        "Unable to update systemd unit data!\n{e}\n{}",
+                        e.backtrace()
+                    );
+                })
+                .unwrap_or_default()
+        };
+
         RefreshData {
             cpu_data,
             mem_data,
@@ -512,6 +620,9 @@ impl MainWindow {
             battery_paths,
             battery_data,
             process_data,
+            process_details,
+            container_cgroups_data,
+            units_data,
         }
     }
 
@@ -530,6 +641,9 @@ impl MainWindow {
             battery_paths,
             battery_data,
             process_data,
+            process_details,
+            container_cgroups_data,
+            units_data,
         } = refresh_data;
 
         /*
@@ -537,10 +651,24 @@ impl MainWindow {
          */
 
         let mut apps_context = imp.apps_context.borrow_mut();
-        apps_context.refresh(process_data);
+        apps_context.refresh(process_data, &container_cgroups_data);
 
         imp.applications.refresh_apps_list(&apps_context);
         imp.processes.refresh_processes_list(&apps_context);
+        imp.containers.refresh_containers_list(&apps_context);
+        imp.processes
+            .refresh_process_details(process_details.as_ref());
+
+        /*
+         * systemd units
+         */
+
+        if !ARGS.disable_services_monitoring {
+            let mut units_context = imp.units_context.borrow_mut();
+            units_context.refresh(units_data);
+            imp.services
+                .refresh_units_list(&units_context, &apps_context);
+        }
 
         /*
          *  Gpu
@@ -972,27 +1100,33 @@ impl MainWindow {
         let apps_context = self.imp().apps_context.borrow();
         match action {
             Action::ManipulateProcesses(action, pids, toast_overlay) => {
-                let mut processes_unsuccessful: usize = 0;
+                let processes: Vec<&Process> = pids
+                    .iter()
+                    .filter_map(|pid| apps_context.get_process(*pid))
+                    .collect();
 
-                let mut first_process = None;
+                let first_process = processes.first().copied();
 
-                for (i, pid) in pids.iter().enumerate() {
-                    if let Some(process) = apps_context.get_process(*pid) {
-                        if i == 0 {
-                            first_process = Some(process);
-                        }
-                        if process.execute_process_action(action).is_err() {
-                            processes_unsuccessful += 1;
-                        }
-                    }
-                }
+                let results = Process::execute_process_action_on(&processes, action);
+
+                let processes_unsuccessful =
+                    results.iter().filter(|result| result.is_err()).count();
+                let process_replaced = results.iter().any(|result| {
+                    result
+                        .as_ref()
+                        .is_err_and(|err| err.is::<ProcessReplacedError>())
+                });
 
                 let toast_message = if processes_unsuccessful > 0 {
                     if pids.len() == 1 {
                         if let Some(display_name) =
                             first_process.map(|process| &process.display_name)
                         {
-                            get_named_action_failure(action, display_name)
+                            if process_replaced {
+                                get_process_replaced_failure(display_name)
+                            } else {
+                                get_named_action_failure(action, display_name)
+                            }
                         } else {
                             // this should never happen
                             get_action_failure(action, 1)
@@ -1014,34 +1148,241 @@ impl MainWindow {
                 toast_overlay.add_toast(Toast::new(&toast_message));
             }
 
+            Action::ManipulateProcessTree(action, pid, toast_overlay) => {
+                let display_name = apps_context
+                    .get_process(pid)
+                    .map(|process| process.display_name.clone())
+                    .unwrap_or_else(|| pid.to_string());
+
+                let results = apps_context.execute_process_tree_action(pid, action);
+
+                // the root process is the last one to be signaled
+                let toast_message = match results.last() {
+                    Some(Err(err)) if err.is::<ProcessReplacedError>() => {
+                        get_process_replaced_failure(&display_name)
+                    }
+                    _ if results.iter().any(Result::is_err) => {
+                        get_named_action_failure(action, &display_name)
+                    }
+                    _ => get_action_success(action, &display_name),
+                };
+
+                toast_overlay.add_toast(Toast::new(&toast_message));
+            }
+
             Action::ManipulateApp(action, id, toast_overlay) => {
-                let app = apps_context.get_app(&Some(id.clone())).unwrap();
-                let result = app.execute_process_action(&apps_context, action);
+                let Some(app) = apps_context.get_app(&Some(id)) else {
+                    return;
+                };
 
-                let processes_tried = result.len();
-                let processes_successful = result.iter().flatten().count();
-                let processes_unsuccessful = processes_tried - processes_successful;
+                let display_name = app.display_name.clone();
+                let (processes, cgroup_paths) = app.process_action_targets(&apps_context);
+                let processes_tried = processes.len();
+
+                // freezing the app's units might wait for the user to authenticate, so we must not block the UI in
+                // the meantime
+                let main_context = MainContext::default();
+                main_context.spawn_local(async move {
+                    let result = gio::spawn_blocking(move || {
+                        Process::execute_process_action_freezing(&processes, &cgroup_paths, action)
+                    })
+                    .await;
+
+                    let processes_unsuccessful = match result {
+                        Ok(results) => results.iter().filter(|result| result.is_err()).count(),
+                        Err(_) => processes_tried,
+                    };
 
-                let toast_message = if processes_unsuccessful > 0 {
-                    get_action_failure(action, processes_unsuccessful)
+                    let toast_message = if processes_unsuccessful > 0 {
+                        get_action_failure(action, processes_unsuccessful)
+                    } else {
+                        get_action_success(action, &display_name)
+                    };
+
+                    toast_overlay.add_toast(Toast::new(&toast_message));
+                });
+            }
+
+            Action::ManipulateContainer(action, key, toast_overlay) => {
+                // the container might have exited while the confirmation dialog was open
+                let Some(container) = apps_context.get_container(&key) else {
+                    return;
+                };
+
+                let display_name = container.container.display_name().to_string();
+                let (processes, cgroup_paths) = container.process_action_targets(&apps_context);
+
+                // containers usually belong to the system manager, so freezing them might wait for the user to
+                // authenticate and we must not block the UI in the meantime
+                let main_context = MainContext::default();
+                main_context.spawn_local(async move {
+                    let result = gio::spawn_blocking(move || {
+                        Process::execute_process_action_freezing(&processes, &cgroup_paths, action)
+                    })
+                    .await;
+
+                    let toast_message = match result {
+                        Ok(results) if !results.iter().any(Result::is_err) => {
+                            get_container_action_success(action, &display_name)
+                        }
+                        _ => get_container_action_failure(action, &display_name),
+                    };
+
+                    toast_overlay.add_toast(Toast::new(&toast_message));
+                });
+            }
+
+            Action::ManipulateUnit(action, key, toast_overlay) => {
+                let Some(unit) = self.imp().units_context.borrow().get_unit(&key).cloned() else {
+                    return;
+                };
+
+                // systemd might wait for the user to authenticate, so we must not block the UI in the meantime
+                let main_context = MainContext::default();
+                main_context.spawn_local(async move {
+                    let name = unit.data.name.clone();
+                    let result = gio::spawn_blocking(move || {
+                        execute_unit_action(
+                            &DBusSystemd::new(unit.data.manager),
+                            &unit.data.name,
+                            unit.data.kind,
+                            action,
+                        )
+                    })
+                    .await;
+
+                    let toast_message = match result {
+                        Ok(Ok(_)) => get_unit_action_success(action, &name),
+                        Ok(Err(e)) => {
+                            warn!("{e:?}");
+                            get_unit_action_failure(action, &name)
+                        }
+                        Err(_) => get_unit_action_failure(action, &name),
+                    };
+
+                    toast_overlay.add_toast(Toast::new(&toast_message));
+                });
+            }
+
+            Action::LimitApp(id, previous_limits, limits, toast_overlay) => {
+                let Some(app) = apps_context.get_app(&Some(id)) else {
+                    return;
+                };
+
+                let name = app.display_name.clone();
+                let units = app.units(&apps_context);
+
+                if units.is_empty() {
+                    toast_overlay.add_toast(Toast::new(&get_limit_failure(&name)));
+                    return;
+                }
+
+                // systemd might wait for the user to authenticate, so we must not block the UI in the meantime
+                let main_context = MainContext::default();
+                main_context.spawn_local(async move {
+                    let result = gio::spawn_blocking(move || {
+                        let devices = io_limit_devices();
+                        units.iter().try_for_each(|unit| {
+                            apply_limits(
+                                &DBusSystemd::new(unit.manager),
+                                &unit.name,
+                                &previous_limits,
+                                &limits,
+                                &devices,
+                            )
+                        })
+                    })
+                    .await;
+
+                    let toast_message = match result {
+                        Ok(Ok(())) => i18n_f("Successfully applied limits to {}", &[&name]),
+                        Ok(Err(e)) => {
+                            warn!("{e:?}");
+                            get_limit_failure(&name)
+                        }
+                        Err(_) => get_limit_failure(&name),
+                    };
+
+                    toast_overlay.add_toast(Toast::new(&toast_message));
+                });
+            }
+
+            Action::SendSignal(signal, signal_name, pids, toast_overlay) => {
+                let processes: Vec<&Process> = pids
+                    .iter()
+                    .filter_map(|pid| apps_context.get_process(*pid))
+                    .collect();
+                let results = Process::send_signal_to(&processes, signal);
+
+                let processes_unsuccessful =
+                    results.iter().filter(|result| result.is_err()).count();
+
+                let display_name = if processes.len() == 1 {
+                    Some(&processes[0].display_name)
                 } else {
-                    get_action_success(action, &app.display_name)
+                    None
+                };
+
+                let toast_message = match (results.first(), display_name) {
+                    (Some(Ok(())), Some(display_name)) => {
+                        i18n_f("Successfully sent {} to {}", &[&signal_name, display_name])
+                    }
+                    (Some(Err(err)), Some(display_name)) if err.is::<ProcessReplacedError>() => {
+                        get_process_replaced_failure(display_name)
+                    }
+                    (Some(Err(_)), Some(display_name)) => i18n_f(
+                        "There was a problem sending {} to {}",
+                        &[&signal_name, display_name],
+                    ),
+                    _ if processes_unsuccessful > 0 => ni18n_f(
+                        "There was a problem sending {} to a process",
+                        "There were problems sending {} to {} processes",
+                        processes_unsuccessful as u32,
+                        &[&signal_name, &processes_unsuccessful.to_string()],
+                    ),
+                    _ => ni18n_f(
+                        "Successfully sent {} to the process",
+                        "Successfully sent {} to {} processes",
+                        processes.len() as u32,
+                        &[&signal_name, &processes.len().to_string()],
+                    ),
                 };
 
                 toast_overlay.add_toast(Toast::new(&toast_message));
             }
 
-            Action::AdjustProcess(pid, niceness, affinity, display_name, toast_overlay) => {
+            Action::AdjustProcess(pid, adjustment, display_name, toast_overlay) => {
                 if let Some(process) = apps_context.get_process(pid) {
-                    let result = process.adjust(niceness, affinity);
+                    let result = process.adjust(&adjustment);
 
                     let toast_message = match result {
                         Ok(()) => i18n_f("Successfully adjusted {}", &[&display_name]),
+                        Err(err) if err.is::<ProcessReplacedError>() => {
+                            get_process_replaced_failure(&display_name)
+                        }
                         Err(_) => i18n_f("There was a problem adjusting {}", &[&display_name]),
                     };
                     toast_overlay.add_toast(Toast::new(&toast_message));
                 }
             }
+
+            Action::SetProcessLimit(pid, limit, display_name, toast_overlay) => {
+                if let Some(process) = apps_context.get_process(pid) {
+                    let result = process.set_limit(limit);
+
+                    let toast_message = match result {
+                        Ok(()) => i18n_f("Successfully changed limit of {}", &[&display_name]),
+                        Err(err) if err.is::<ProcessReplacedError>() => {
+                            get_process_replaced_failure(&display_name)
+                        }
+                        Err(_) => i18n_f(
+                            "There was a problem changing the limit of {}",
+                            &[&display_name],
+                        ),
+                    };
+                    toast_overlay.add_toast(Toast::new(&toast_message));
+                }
+            }
         };
     }
 
@@ -1190,3 +1531,48 @@ pub fn get_named_action_failure(action: ProcessAction, name: &str) -> String {
         ProcessAction::CONT => i18n_f("There was a problem continuing {}", &[name]),
     }
 }
+
+fn get_container_action_success(action: ProcessAction, name: &str) -> String {
+    match action {
+        ProcessAction::TERM => i18n_f("Successfully stopped {}", &[name]),
+        ProcessAction::STOP => i18n_f("Successfully paused {}", &[name]),
+        ProcessAction::KILL => i18n_f("Successfully killed {}", &[name]),
+        ProcessAction::CONT => i18n_f("Successfully resumed {}", &[name]),
+    }
+}
+
+fn get_container_action_failure(action: ProcessAction, name: &str) -> String {
+    match action {
+        ProcessAction::TERM => i18n_f("There was a problem stopping {}", &[name]),
+        ProcessAction::STOP => i18n_f("There was a problem pausing {}", &[name]),
+        ProcessAction::KILL => i18n_f("There was a problem killing {}", &[name]),
+        ProcessAction::CONT => i18n_f("There was a problem resuming {}", &[name]),
+    }
+}
+
+fn get_unit_action_success(action: UnitAction, name: &str) -> String {
+    match action {
+        UnitAction::Start => i18n_f("Successfully started {}", &[name]),
+        UnitAction::Stop => i18n_f("Successfully stopped {}", &[name]),
+        UnitAction::Restart => i18n_f("Successfully restarted {}", &[name]),
+    }
+}
+
+fn get_unit_action_failure(action: UnitAction, name: &str) -> String {
+    match action {
+        UnitAction::Start => i18n_f("There was a problem starting {}", &[name]),
+        UnitAction::Stop => i18n_f("There was a problem stopping {}", &[name]),
+        UnitAction::Restart => i18n_f("There was a problem restarting {}", &[name]),
+    }
+}
+
+fn get_limit_failure(name: &str) -> String {
+    i18n_f("There was a problem applying limits to {}", &[name])
+}
+
+fn get_process_replaced_failure(name: &str) -> String {
+    i18n_f(
+        "{} has already exited and its PID is now used by another process",
+        &[name],
+    )
+}
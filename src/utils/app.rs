@@ -18,7 +18,10 @@ use crate::i18n::i18n;
 
 use super::{
     boot_time,
-    process::{Process, ProcessAction},
+    container::{container_key, ContainerCgroupData, RunningContainer},
+    process::{process_tree_pids, Process, ProcessAction},
+    settings::MemoryMetric,
+    systemd::{UnitCgroup, UnitKind},
     FiniteOr,
 };
 
@@ -163,6 +166,8 @@ static MESSAGE_LOCALES: LazyLock<Vec<String>> = LazyLock::new(|| {
 pub struct AppsContext {
     apps: HashMap<Option<String>, App>,
     processes: HashMap<i32, Process>,
+    /// Key: see `container_key()`
+    containers: HashMap<String, RunningContainer>,
     gpus_with_combined_media_engine: Vec<PciSlot>,
 }
 
@@ -400,10 +405,17 @@ impl App {
             .filter(move |process| self.processes.contains(&process.data.pid))
     }
 
+    /// Returns the sum of the proportional set sizes of this app's processes, so that memory
+    /// shared between them is not counted multiple times. Processes whose proportional set size
+    /// can't be read (e.g. setuid helpers) are counted with their resident set size.
     #[must_use]
     pub fn memory_usage(&self, apps: &AppsContext) -> usize {
         self.processes_iter(apps)
-            .map(|process| process.data.memory_usage)
+            .map(|process| {
+                process
+                    .memory_usage(MemoryMetric::Proportional)
+                    .unwrap_or(process.data.memory_usage)
+            })
             .sum()
     }
 
@@ -479,14 +491,22 @@ impl App {
             .unwrap_or_default()
     }
 
-    pub fn execute_process_action(
-        &self,
-        apps: &AppsContext,
-        action: ProcessAction,
-    ) -> Vec<Result<()>> {
-        self.processes_iter(apps)
-            .map(|process| process.execute_process_action(action))
-            .collect()
+    /// Returns the data of the app's processes and the cgroup paths of its units, which are needed to execute process
+    /// actions on the app off the main thread with `Process::execute_process_action_freezing()`. Like for containers,
+    /// halting and continuing the app is done by freezing and thawing its units if it has any.
+    pub fn process_action_targets(&self, apps: &AppsContext) -> (Vec<ProcessData>, Vec<String>) {
+        let processes = self
+            .processes_iter(apps)
+            .map(|process| process.data.clone())
+            .collect();
+
+        let cgroup_paths = self
+            .units(apps)
+            .into_iter()
+            .map(|unit| unit.cgroup_path)
+            .collect();
+
+        (processes, cgroup_paths)
     }
 
     pub fn running_since(&self, apps: &AppsContext) -> Result<GString> {
@@ -502,6 +522,42 @@ impl App {
     pub fn running_processes(&self) -> usize {
         self.processes.len()
     }
+
+    /// Whether all of the app's processes are frozen by the cgroup freezer
+    #[must_use]
+    pub fn is_frozen(&self, apps: &AppsContext) -> bool {
+        self.is_running() && self.processes_iter(apps).all(|process| process.data.frozen)
+    }
+
+    /// Returns the systemd units the app's processes have been placed in by whoever launched them, like the
+    /// `app-gnome-….scope` of GNOME or the `app-flatpak-….scope` of Flatpak. Processes that have only been associated
+    /// with this app by their executable are not considered since their units might belong to something else.
+    pub fn units(&self, apps: &AppsContext) -> Vec<UnitCgroup> {
+        if self.id.is_none() {
+            return Vec::new();
+        }
+
+        let mut units = Vec::new();
+
+        for unit in self
+            .processes_iter(apps)
+            .filter(|process| process.data.cgroup == self.id)
+            .filter_map(|process| {
+                process
+                    .data
+                    .cgroup_path
+                    .as_ref()
+                    .and_then(UnitCgroup::containing)
+            })
+            .filter(|unit| unit.kind != UnitKind::Slice && unit.manager.is_reachable())
+        {
+            if !units.contains(&unit) {
+                units.push(unit);
+            }
+        }
+
+        units
+    }
 }
 
 impl AppsContext {
@@ -517,6 +573,7 @@ impl AppsContext {
         AppsContext {
             apps,
             processes: HashMap::new(),
+            containers: HashMap::new(),
             gpus_with_combined_media_engine,
         }
     }
@@ -727,6 +784,31 @@ impl AppsContext {
         self.processes.get(&pid)
     }
 
+    /// Returns the PIDs of the process with `pid` and all of its descendants, ordered such that
+    /// children come before their parents.
+    pub fn process_tree(&self, pid: libc::pid_t) -> Vec<libc::pid_t> {
+        process_tree_pids(
+            pid,
+            self.processes_iter()
+                .map(|process| (process.data.pid, process.data.parent_pid)),
+        )
+    }
+
+    /// Sends the signal corresponding to `action` to the process with `pid` and all of its
+    /// descendants, children first. Returns a result for each process of the tree.
+    pub fn execute_process_tree_action(
+        &self,
+        pid: libc::pid_t,
+        action: ProcessAction,
+    ) -> Vec<Result<()>> {
+        let processes: Vec<&Process> = self
+            .process_tree(pid)
+            .into_iter()
+            .filter_map(|pid| self.get_process(pid))
+            .collect();
+        Process::execute_process_action_on(&processes, action)
+    }
+
     pub fn get_app(&self, id: &Option<String>) -> Option<&App> {
         self.apps.get(id)
     }
@@ -745,6 +827,14 @@ impl AppsContext {
         self.apps.values()
     }
 
+    pub fn get_container(&self, key: &str) -> Option<&RunningContainer> {
+        self.containers.get(key)
+    }
+
+    pub fn containers_iter(&self) -> impl Iterator<Item = &RunningContainer> {
+        self.containers.values()
+    }
+
     pub fn running_apps_iter(&self) -> impl Iterator<Item = &App> {
         self.apps_iter().filter(|app| {
             app.is_running()
@@ -755,8 +845,13 @@ impl AppsContext {
         })
     }
 
-    /// Refreshes the statistics about the running applications and processes.
-    pub fn refresh(&mut self, new_process_data: Vec<ProcessData>) {
+    /// Refreshes the statistics about the running applications, processes and containers. The accounting of the
+    /// containers' cgroups is taken from `container_cgroups_data`.
+    pub fn refresh(
+        &mut self,
+        new_process_data: Vec<ProcessData>,
+        container_cgroups_data: &HashMap<String, ContainerCgroupData>,
+    ) {
         let mut updated_processes = HashSet::new();
 
         for mut process_data in new_process_data {
@@ -783,6 +878,16 @@ impl AppsContext {
                 old_process.timestamp_last = old_process.data.timestamp;
                 old_process.read_bytes_last = old_process.data.read_bytes;
                 old_process.write_bytes_last = old_process.data.write_bytes;
+                old_process.read_chars_last = old_process.data.read_chars;
+                old_process.write_chars_last = old_process.data.write_chars;
+                old_process.read_syscalls_last = old_process.data.read_syscalls;
+                old_process.write_syscalls_last = old_process.data.write_syscalls;
+                old_process.thread_delays_last = old_process
+                    .data
+                    .thread_delays
+                    .iter()
+                    .map(|delays| (delays.tid, *delays))
+                    .collect();
                 old_process.gpu_usage_stats_last = old_process.data.gpu_usage_stats.clone();
 
                 old_process.data = process_data.clone();
@@ -838,5 +943,20 @@ impl AppsContext {
         // all the not-updated processes have unfortunately died, probably
         self.processes
             .retain(|pid, _| updated_processes.contains(pid));
+
+        // unlike apps, containers come and go, so they're rebuilt from scratch
+        let old_containers = std::mem::take(&mut self.containers);
+        for process in self.processes.values() {
+            if let Some(container) = &process.data.container {
+                self.containers
+                    .entry(container_key(container))
+                    .or_insert_with(|| RunningContainer::new(container.clone()))
+                    .add_process(process);
+            }
+        }
+
+        for (key, container) in &mut self.containers {
+            container.set_cgroup_data(container_cgroups_data, old_containers.get(key));
+        }
     }
 }
@@ -0,0 +1,236 @@
+use std::{collections::HashMap, path::Path};
+
+use process_data::{container::Container, unix_as_millis, ProcessData, ProcessState};
+
+use super::{
+    app::AppsContext,
+    process::Process,
+    settings::MemoryMetric,
+    systemd::{CgroupCounters, CGROUP_ROOT},
+    NUM_CPUS,
+};
+
+/// The kernel's accounting of a container's cgroup at the time it was read
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ContainerCgroupData {
+    pub counters: CgroupCounters,
+    pub timestamp: u64,
+}
+
+/// Reads the accounting of the cgroups of all containers the processes of `process_data` are running in.
+///
+/// Key: path of the container's cgroup, see `Container::cgroup_of()`
+pub fn container_cgroups_data(
+    process_data: &[ProcessData],
+) -> HashMap<String, ContainerCgroupData> {
+    let mut cgroups_data = HashMap::new();
+
+    for cgroup_path in process_data
+        .iter()
+        .filter(|process_data| process_data.container.is_some())
+        .filter_map(|process_data| process_data.cgroup_path.as_ref())
+        .filter_map(Container::cgroup_of)
+    {
+        cgroups_data
+            .entry(cgroup_path)
+            .or_insert_with_key(|cgroup_path| ContainerCgroupData {
+                counters: CgroupCounters::from_cgroup(Path::new(CGROUP_ROOT).join(cgroup_path)),
+                timestamp: unix_as_millis(),
+            });
+    }
+
+    cgroups_data
+}
+
+/// A container that has at least one running process, detected using the processes' cgroups
+#[derive(Debug, Clone)]
+pub struct RunningContainer {
+    pub container: Container,
+    /// Path of the container's cgroup relative to the cgroup root, `None` if the cgroups of its processes are unknown
+    pub cgroup_path: Option<String>,
+    processes: Vec<libc::pid_t>,
+    cgroup_data: Option<ContainerCgroupData>,
+    cgroup_data_last: Option<ContainerCgroupData>,
+}
+
+impl RunningContainer {
+    pub fn new(container: Container) -> Self {
+        Self {
+            container,
+            cgroup_path: None,
+            processes: Vec::new(),
+            cgroup_data: None,
+            cgroup_data_last: None,
+        }
+    }
+
+    /// Returns a string that identifies this container across all runtimes
+    pub fn key(&self) -> String {
+        container_key(&self.container)
+    }
+
+    pub fn add_process(&mut self, process: &Process) {
+        // not all processes of a container might allow us to look for its name
+        if self.container.name.is_none() {
+            if let Some(container) = &process.data.container {
+                self.container.name.clone_from(&container.name);
+            }
+        }
+
+        if self.cgroup_path.is_none() {
+            self.cgroup_path = process
+                .data
+                .cgroup_path
+                .as_ref()
+                .and_then(Container::cgroup_of);
+        }
+
+        self.processes.push(process.data.pid);
+    }
+
+    /// Sets the accounting of the container's cgroup from `cgroups_data` and keeps the one of `old`, the same container
+    /// during the previous refresh, to calculate rates
+    pub fn set_cgroup_data(
+        &mut self,
+        cgroups_data: &HashMap<String, ContainerCgroupData>,
+        old: Option<&RunningContainer>,
+    ) {
+        self.cgroup_data = self
+            .cgroup_path
+            .as_ref()
+            .and_then(|cgroup_path| cgroups_data.get(cgroup_path))
+            .copied();
+        self.cgroup_data_last = old.and_then(|old| old.cgroup_data);
+    }
+
+    /// Returns the change of `counter` of the container's cgroup per second since the last refresh, `None` if it
+    /// isn't available
+    fn cgroup_rate<F>(&self, counter: F) -> Option<f64>
+    where
+        F: Fn(&CgroupCounters) -> Option<u64>,
+    {
+        let current = self.cgroup_data?;
+        let last = self.cgroup_data_last?;
+
+        let delta_time = current.timestamp.saturating_sub(last.timestamp);
+        if delta_time == 0 {
+            return None;
+        }
+
+        let delta = counter(&current.counters)?.saturating_sub(counter(&last.counters)?);
+        Some(delta as f64 / (delta_time as f64 / 1000.0))
+    }
+
+    /// Whether `counter` is available for the container's cgroup, it isn't if the corresponding controller is not
+    /// enabled for it (like for rootless containers without delegated controllers)
+    fn has_cgroup_counter<F>(&self, counter: F) -> bool
+    where
+        F: Fn(&CgroupCounters) -> Option<u64>,
+    {
+        self.cgroup_data
+            .is_some_and(|cgroup_data| counter(&cgroup_data.counters).is_some())
+    }
+
+    pub fn processes_iter<'a>(
+        &'a self,
+        apps: &'a AppsContext,
+    ) -> impl Iterator<Item = &'a Process> {
+        self.processes
+            .iter()
+            .filter_map(|pid| apps.get_process(*pid))
+    }
+
+    pub fn running_processes(&self) -> usize {
+        self.processes.len()
+    }
+
+    /// Returns the memory usage of the container's cgroup (`memory.current`), which is readable even for containers of
+    /// other users. If the memory controller isn't enabled for it, the proportional set sizes of the container's
+    /// processes are summed up instead, processes whose proportional set size can't be read are counted with their
+    /// resident set size.
+    #[must_use]
+    pub fn memory_usage(&self, apps: &AppsContext) -> usize {
+        if let Some(memory_current) = self
+            .cgroup_data
+            .and_then(|cgroup_data| cgroup_data.counters.memory_current)
+        {
+            return memory_current as usize;
+        }
+
+        self.processes_iter(apps)
+            .map(|process| {
+                process
+                    .memory_usage(MemoryMetric::Proportional)
+                    .unwrap_or(process.data.memory_usage)
+            })
+            .sum()
+    }
+
+    /// Returns the fraction of the time of all CPUs the container has used since the last refresh according to its
+    /// cgroup's `cpu.stat`, or according to its processes if that isn't available
+    #[must_use]
+    pub fn cpu_time_ratio(&self, apps: &AppsContext) -> f32 {
+        if self.has_cgroup_counter(|counters| counters.cpu_usage_usec) {
+            return self
+                .cgroup_rate(|counters| counters.cpu_usage_usec)
+                .map_or(0.0, |usec_per_sec| {
+                    (usec_per_sec / 1_000_000.0 / *NUM_CPUS as f64) as f32
+                });
+        }
+
+        self.processes_iter(apps).map(Process::cpu_time_ratio).sum()
+    }
+
+    #[must_use]
+    pub fn read_speed(&self, apps: &AppsContext) -> f64 {
+        if self.has_cgroup_counter(|counters| counters.io_read_bytes) {
+            return self
+                .cgroup_rate(|counters| counters.io_read_bytes)
+                .unwrap_or_default();
+        }
+
+        self.processes_iter(apps)
+            .filter_map(Process::read_speed)
+            .sum()
+    }
+
+    #[must_use]
+    pub fn write_speed(&self, apps: &AppsContext) -> f64 {
+        if self.has_cgroup_counter(|counters| counters.io_write_bytes) {
+            return self
+                .cgroup_rate(|counters| counters.io_write_bytes)
+                .unwrap_or_default();
+        }
+
+        self.processes_iter(apps)
+            .filter_map(Process::write_speed)
+            .sum()
+    }
+
+    /// Whether all processes of this container have been frozen or halted
+    #[must_use]
+    pub fn is_paused(&self, apps: &AppsContext) -> bool {
+        let mut processes = self.processes_iter(apps).peekable();
+        processes.peek().is_some()
+            && processes
+                .all(|process| process.data.frozen || process.data.state == ProcessState::Stopped)
+    }
+
+    /// Returns the data of this container's processes and the path of its cgroup, which are needed to execute process
+    /// actions on the container off the main thread with `Process::execute_process_action_freezing()`. Pausing and
+    /// resuming freezes and thaws the container's cgroup like `docker pause` does, stopping a container sends signals
+    /// and bypasses its runtime, so it might get restarted depending on the container's restart policy.
+    pub fn process_action_targets(&self, apps: &AppsContext) -> (Vec<ProcessData>, Vec<String>) {
+        let processes = self
+            .processes_iter(apps)
+            .map(|process| process.data.clone())
+            .collect();
+
+        (processes, self.cgroup_path.iter().cloned().collect())
+    }
+}
+
+/// Returns a string that identifies `container` across all runtimes
+pub fn container_key(container: &Container) -> String {
+    format!("{:?}/{}", container.runtime, container.id)
+}
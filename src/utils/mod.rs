@@ -8,6 +8,7 @@ use process_data::unix_as_millis;
 
 pub mod app;
 pub mod battery;
+pub mod container;
 pub mod cpu;
 pub mod drive;
 pub mod gpu;
@@ -15,8 +16,10 @@ pub mod memory;
 pub mod network;
 pub mod npu;
 pub mod pci;
+pub mod privileged_helper;
 pub mod process;
 pub mod settings;
+pub mod systemd;
 pub mod units;
 
 const FLATPAK_SPAWN: &str = "/usr/bin/flatpak-spawn";
@@ -0,0 +1,219 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::Shutdown,
+    os::{fd::OwnedFd, unix::net::UnixStream},
+    process::{Child, Command, Stdio},
+    sync::{LazyLock, Mutex},
+};
+
+use anyhow::{bail, Context, Result};
+use log::{debug, info};
+use process_data::pidfd::parse_batch_output;
+
+use crate::config::LIBEXECDIR;
+
+use super::{FLATPAK_APP_PATH, FLATPAK_SPAWN, IS_FLATPAK};
+
+/// Exit code of pkexec if the user dismissed the authentication dialog or wasn't authorized
+const PKEXEC_NOT_AUTHORIZED: i32 = 126;
+
+static HELPER: LazyLock<Mutex<Option<PrivilegedHelper>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Returned if the persistent privileged helper couldn't be started because the user didn't authorize it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotAuthorizedError;
+
+impl std::fmt::Display for NotAuthorizedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "not authorized to start the privileged helper")
+    }
+}
+
+impl std::error::Error for NotAuthorizedError {}
+
+/// A running instance of `resources-helper`. It is started through pkexec once and then receives
+/// requests over a Unix socket that is passed to it as its stdin and stdout.
+struct PrivilegedHelper {
+    child: Child,
+    reader: BufReader<UnixStream>,
+    writer: UnixStream,
+}
+
+impl PrivilegedHelper {
+    fn spawn() -> Result<Self> {
+        let helper_path = if *IS_FLATPAK {
+            format!(
+                "{}/libexec/resources/resources-helper",
+                FLATPAK_APP_PATH.as_str()
+            )
+        } else {
+            format!("{LIBEXECDIR}/resources-helper")
+        };
+
+        let (socket, helper_socket) = UnixStream::pair()?;
+
+        // the command has to be dropped after spawning the child so that the helper's end of the
+        // socket is closed on our side, otherwise we'd never notice if the helper has exited
+        let mut child = {
+            let helper_stdout: OwnedFd = helper_socket.try_clone()?.into();
+            let helper_stdin: OwnedFd = helper_socket.into();
+
+            let mut command = if *IS_FLATPAK {
+                debug!(
+                    "Spawning privileged helper: {FLATPAK_SPAWN} --host pkexec --disable-internal-agent {helper_path}"
+                );
+                let mut command = Command::new(FLATPAK_SPAWN);
+                command.args(["--host", "pkexec", "--disable-internal-agent"]);
+                command
+            } else {
+                debug!("Spawning privileged helper: pkexec --disable-internal-agent {helper_path}");
+                let mut command = Command::new("pkexec");
+                command.arg("--disable-internal-agent");
+                command
+            };
+
+            command
+                .arg(&helper_path)
+                .stdin(Stdio::from(helper_stdin))
+                .stdout(Stdio::from(helper_stdout))
+                .stderr(Stdio::null())
+                .spawn()?
+        };
+
+        let mut reader = BufReader::new(socket.try_clone()?);
+
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+
+        if line.trim() != "ready" {
+            let exit_code = child.wait()?.code();
+            if exit_code == Some(PKEXEC_NOT_AUTHORIZED) {
+                return Err(NotAuthorizedError.into());
+            }
+            bail!("privileged helper didn't start, exit code: {exit_code:?}");
+        }
+
+        info!("Started persistent privileged helper");
+
+        Ok(Self {
+            child,
+            reader,
+            writer: socket,
+        })
+    }
+
+    /// Returns the output of the helper binary `helper` in batch mode
+    fn request(&mut self, helper: &str, entries: &[String]) -> Result<String> {
+        writeln!(self.writer, "{helper} {}", entries.join(" "))?;
+        self.writer.flush()?;
+
+        read_response(&mut self.reader)
+    }
+}
+
+/// Reads a response of the privileged helper from `reader`, which is terminated by an empty line
+fn read_response<R: BufRead>(reader: &mut R) -> Result<String> {
+    let mut output = String::new();
+
+    loop {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line)? == 0 {
+            bail!("privileged helper has exited");
+        }
+
+        // an empty line terminates the response
+        if line.trim().is_empty() {
+            break;
+        }
+
+        output.push_str(&line);
+    }
+
+    Ok(output)
+}
+
+impl Drop for PrivilegedHelper {
+    fn drop(&mut self) {
+        // closing the socket makes the helper exit
+        let _ = self.writer.shutdown(Shutdown::Both);
+        let _ = self.child.wait();
+    }
+}
+
+/// Runs the helper binary `helper` in batch mode with `entries` through the persistent privileged
+/// helper and returns the result code of every entry. The privileged helper is started first if it
+/// isn't running yet, which requires the user to authenticate.
+///
+/// # Errors
+///
+/// Will return `Err` if the privileged helper couldn't be started or has exited unexpectedly. In
+/// the former case, the error is a `NotAuthorizedError` if the user didn't authorize it.
+pub fn run_batch(helper: &str, entries: &[String]) -> Result<Vec<i32>> {
+    let codes: Vec<i32> = parse_batch_output(run_batch_output(helper, entries)?)
+        .into_iter()
+        .map(|(_, code)| code)
+        .collect();
+
+    if codes.len() == entries.len() {
+        Ok(codes)
+    } else {
+        bail!("expected {} results but got {}", entries.len(), codes.len())
+    }
+}
+
+/// Like `run_batch()`, but returns the unparsed output of `helper` for helpers that print more
+/// than their result codes.
+///
+/// # Errors
+///
+/// See `run_batch()`.
+pub fn run_batch_output(helper: &str, entries: &[String]) -> Result<String> {
+    let mut privileged_helper = HELPER
+        .lock()
+        .ok()
+        .context("privileged helper lock is poisoned")?;
+
+    if privileged_helper.is_none() {
+        *privileged_helper = Some(PrivilegedHelper::spawn()?);
+    }
+
+    let result = privileged_helper
+        .as_mut()
+        .context("privileged helper is not running")?
+        .request(helper, entries);
+
+    // the helper has most likely exited, so start a new one next time
+    if result.is_err() {
+        *privileged_helper = None;
+    }
+
+    result
+}
+
+/// Stops the persistent privileged helper if it is running.
+pub fn stop() {
+    if let Ok(mut privileged_helper) = HELPER.lock() {
+        if privileged_helper.take().is_some() {
+            info!("Stopped persistent privileged helper");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::read_response;
+
+    #[test]
+    fn responses_end_with_empty_line() {
+        let mut reader = "1234 0\n4321 1\n\n\n5678 0\n".as_bytes();
+
+        assert_eq!("1234 0\n4321 1\n", read_response(&mut reader).unwrap());
+        // a request for a helper that isn't allowed gets an empty response
+        assert_eq!("", read_response(&mut reader).unwrap());
+        // the helper exited before finishing the response
+        assert!(read_response(&mut reader).is_err());
+    }
+}
@@ -1,13 +1,25 @@
-use anyhow::{bail, Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use config::LIBEXECDIR;
-use log::{debug, error, info};
-use process_data::{pci_slot::PciSlot, GpuUsageStats, Niceness, ProcessData};
+use log::{debug, error, info, warn};
+use nix::sys::signal::Signal;
+use process_data::{
+    details::ProcessDetails,
+    limits::ResourceLimit,
+    pci_slot::PciSlot,
+    pidfd::{parse_batch_output, EXIT_STARTTIME_MISMATCH},
+    wait_state::{self, WaitState},
+    GpuUsageStats, IoPriority, Niceness, ProcessData, ProcessDataOptions, ProcessState,
+    SchedulingPolicy, ThreadDelays,
+};
+use serde::de::DeserializeOwned;
 use std::{
-    collections::BTreeMap,
-    ffi::{OsStr, OsString},
+    collections::{BTreeMap, HashMap, HashSet},
     io::{Read, Write},
-    process::{ChildStdin, ChildStdout, Command, Stdio},
-    sync::{LazyLock, Mutex},
+    process::{ChildStdin, ChildStdout, Command, Output, Stdio},
+    sync::{
+        atomic::{AtomicI32, Ordering},
+        LazyLock, Mutex,
+    },
 };
 use strum_macros::Display;
 
@@ -19,7 +31,10 @@ use gtk::{
 use crate::config;
 
 use super::{
-    boot_time, FiniteOr, FLATPAK_APP_PATH, FLATPAK_SPAWN, IS_FLATPAK, NUM_CPUS, TICK_RATE,
+    boot_time,
+    privileged_helper::{self, NotAuthorizedError},
+    settings::{MemoryMetric, SETTINGS},
+    systemd, FiniteOr, FLATPAK_APP_PATH, FLATPAK_SPAWN, IS_FLATPAK, NUM_CPUS, TICK_RATE,
 };
 
 static OTHER_PROCESS: LazyLock<Mutex<(ChildStdin, ChildStdout)>> = LazyLock::new(|| {
@@ -57,6 +72,87 @@ static OTHER_PROCESS: LazyLock<Mutex<(ChildStdin, ChildStdout)>> = LazyLock::new
     Mutex::new((stdin, stdout))
 });
 
+/// Optional parts of the process data that are currently shown and therefore have to be gathered
+static DATA_OPTIONS: Mutex<ProcessDataOptions> = Mutex::new(ProcessDataOptions {
+    listening_ports: false,
+    thread_delays: false,
+});
+
+/// PID of the process that is currently inspected by the user, 0 if there is none
+static INSPECTED_PID: AtomicI32 = AtomicI32::new(0);
+
+/// Scheduling parameters that are applied to a process and all of its threads by `resources-adjust`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessAdjustment {
+    pub niceness: Niceness,
+    pub affinity: Vec<bool>,
+    /// `None` leaves the I/O priority unchanged
+    pub io_priority: Option<IoPriority>,
+    /// Scheduling policy and real-time priority, `None` leaves both unchanged. The real-time priority is only used for
+    /// real-time scheduling policies and must be between 1 and 99 then.
+    pub scheduling: Option<(SchedulingPolicy, u32)>,
+    /// `None` leaves the OOM score adjustment unchanged. It always applies to the whole process.
+    pub oom_score_adj: Option<i32>,
+    /// Threads the adjustment is restricted to, the whole process is adjusted if this is empty
+    pub threads: Vec<libc::pid_t>,
+}
+
+impl ProcessAdjustment {
+    /// Returns the batch entry of `resources-adjust` for the process identified by `pid_arg`
+    fn entry(&self, pid_arg: &str) -> String {
+        let target = if self.threads.is_empty() {
+            pid_arg.to_string()
+        } else {
+            let tids = self
+                .threads
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("{pid_arg}/{tids}")
+        };
+
+        format!("{target}={}", self.arguments())
+    }
+
+    fn arguments(&self) -> String {
+        let mask = self
+            .affinity
+            .iter()
+            .map(|b| if *b { '1' } else { '0' })
+            .collect::<String>();
+
+        // empty fields tell resources-adjust to leave the corresponding values alone
+        let io_priority = self.io_priority.map_or_else(
+            || String::from(":"),
+            |io_priority| format!("{}:{}", io_priority.class as i32, io_priority.level),
+        );
+
+        // SCHED_DEADLINE and unknown policies can't be set by us
+        let scheduling = self
+            .scheduling
+            .and_then(|(policy, realtime_priority)| {
+                let realtime_priority = if policy.is_realtime() {
+                    realtime_priority.clamp(1, 99)
+                } else {
+                    0
+                };
+                Some(format!("{}:{realtime_priority}", policy.to_raw()?))
+            })
+            .unwrap_or_else(|| String::from(":"));
+
+        let oom_score_adj = self
+            .oom_score_adj
+            .map(|oom_score_adj| oom_score_adj.clamp(-1000, 1000).to_string())
+            .unwrap_or_default();
+
+        format!(
+            "{}:{mask}:{io_priority}:{scheduling}:{oom_score_adj}",
+            *self.niceness
+        )
+    }
+}
+
 /// Represents a process that can be found within procfs.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Process {
@@ -68,10 +164,31 @@ pub struct Process {
     pub timestamp_last: u64,
     pub read_bytes_last: Option<u64>,
     pub write_bytes_last: Option<u64>,
+    pub read_chars_last: Option<u64>,
+    pub write_chars_last: Option<u64>,
+    pub read_syscalls_last: Option<u64>,
+    pub write_syscalls_last: Option<u64>,
+    pub thread_delays_last: HashMap<libc::pid_t, ThreadDelays>,
     pub gpu_usage_stats_last: BTreeMap<PciSlot, GpuUsageStats>,
     pub display_name: String,
 }
 
+/// Returned when a process has exited and its PID has been reused by another process before an action on it could be
+/// executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcessReplacedError;
+
+impl std::fmt::Display for ProcessReplacedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "process has exited and its PID has been reused by another process"
+        )
+    }
+}
+
+impl std::error::Error for ProcessReplacedError {}
+
 // TODO: Better name?
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
 pub enum ProcessAction {
@@ -89,9 +206,83 @@ impl Process {
     /// Will return `Err` if there are problems traversing and
     /// parsing procfs
     pub fn all_data() -> Result<Vec<ProcessData>> {
+        let request = DATA_OPTIONS.lock().unwrap().to_request();
+        Self::request_data(&request)
+    }
+
+    /// Sets which optional parts of the process data are gathered by `all_data()`.
+    pub fn set_data_options(options: ProcessDataOptions) {
+        *DATA_OPTIONS.lock().unwrap() = options;
+    }
+
+    /// Gathers the `ProcessDetails` of the process that is currently being inspected, if there is one.
+    pub fn inspected_details() -> Result<Option<ProcessDetails>> {
+        let Some(pid) = Self::inspected_pid() else {
+            return Ok(None);
+        };
+
+        Self::details(pid)
+    }
+
+    /// Gathers the `ProcessDetails` of the process with the PID `pid` right away, without anything that requires
+    /// elevated privileges. `None` if the process doesn't exist (anymore).
+    pub fn details(pid: libc::pid_t) -> Result<Option<ProcessDetails>> {
+        Self::request_data(&format!("details {pid}"))
+    }
+
+    /// Sets the process whose `ProcessDetails` are gathered on every refresh.
+    pub fn set_inspected_pid(pid: Option<libc::pid_t>) {
+        INSPECTED_PID.store(pid.unwrap_or_default(), Ordering::Relaxed);
+    }
+
+    /// Gathers the `WaitState` of the process with the PID `pid` and the start time `starttime` by running
+    /// `resources-kernel-stack` privileged, either through the persistent privileged helper (if enabled) or through
+    /// pkexec. Since this asks the user to authenticate, it's only done on request and blocks until the helper is
+    /// done.
+    pub fn privileged_wait_state(pid: libc::pid_t, starttime: u64) -> Result<WaitState> {
+        const HELPER: &str = "resources-kernel-stack";
+
+        let entries = [format!("{pid}:{starttime}")];
+
+        let pkexec_output = || {
+            Self::batch_output(&Self::helper_path(HELPER), &entries, true)
+                .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        };
+
+        let output = if SETTINGS.persistent_privileged_helper() {
+            match privileged_helper::run_batch_output(HELPER, &entries) {
+                Err(err) if !err.is::<NotAuthorizedError>() => {
+                    warn!("Unable to use the privileged helper, falling back to pkexec: {err}");
+                    pkexec_output()
+                }
+                result => result,
+            }
+        } else {
+            pkexec_output()
+        }?;
+
+        let (_, return_code, wait_state) = wait_state::parse_batch_output(output)
+            .into_iter()
+            .next()
+            .context("no result code")?;
+
+        if return_code == 0 {
+            Ok(wait_state)
+        } else if return_code == EXIT_STARTTIME_MISMATCH {
+            Err(ProcessReplacedError.into())
+        } else {
+            bail!("non-zero return code: {return_code}")
+        }
+    }
+
+    pub fn inspected_pid() -> Option<libc::pid_t> {
+        Some(INSPECTED_PID.load(Ordering::Relaxed)).filter(|pid| *pid != 0)
+    }
+
+    fn request_data<T: DeserializeOwned>(request: &str) -> Result<T> {
         let output = {
             let mut process = OTHER_PROCESS.lock().unwrap();
-            let _ = process.0.write_all(b"\n");
+            let _ = process.0.write_all(format!("{request}\n").as_bytes());
             let _ = process.0.flush();
 
             let mut len_bytes = [0_u8; (usize::BITS / 8) as usize];
@@ -110,12 +301,19 @@ impl Process {
     }
 
     pub fn from_process_data(process_data: ProcessData) -> Self {
+        // prefer the actual executable and only guess it from the commandline if we're not allowed to read it
         let executable_path = process_data
-            .commandline
-            .split('\0')
-            .nth(0)
-            .and_then(|nul_split| nul_split.split(" --").nth(0)) // chromium (and thus everything based on it) doesn't use \0 as delimiter
-            .unwrap_or(&process_data.commandline)
+            .exe
+            .as_deref()
+            .map(|exe| exe.trim_end_matches(" (deleted)")) // the executable has been replaced (e.g. by an update)
+            .unwrap_or_else(|| {
+                process_data
+                    .commandline
+                    .split('\0')
+                    .nth(0)
+                    .and_then(|nul_split| nul_split.split(" --").nth(0)) // chromium (and thus everything based on it) doesn't use \0 as delimiter
+                    .unwrap_or(&process_data.commandline)
+            })
             .to_string();
 
         let executable_name = executable_path
@@ -136,6 +334,11 @@ impl Process {
             None
         };
 
+        let read_chars_last = process_data.read_chars.map(|_| 0);
+        let write_chars_last = process_data.write_chars.map(|_| 0);
+        let read_syscalls_last = process_data.read_syscalls.map(|_| 0);
+        let write_syscalls_last = process_data.write_syscalls.map(|_| 0);
+
         let display_name = if executable_name.starts_with(&process_data.comm) {
             executable_name.clone()
         } else {
@@ -151,170 +354,359 @@ impl Process {
             timestamp_last: 0,
             read_bytes_last,
             write_bytes_last,
+            read_chars_last,
+            write_chars_last,
+            read_syscalls_last,
+            write_syscalls_last,
+            thread_delays_last: HashMap::new(),
             gpu_usage_stats_last: Default::default(),
             display_name,
         }
     }
 
-    /// Tries to run a command unprivileged and then privileged if permissions were missing
-    fn maybe_pkexec_command<S: AsRef<OsStr>, I: IntoIterator<Item = S>>(
-        command: S,
-        args: I,
-    ) -> Result<i32> {
-        let args = args
-            .into_iter()
-            .map(|s| s.as_ref().to_os_string())
-            .collect::<Vec<_>>();
+    fn helper_path(name: &str) -> String {
+        if *IS_FLATPAK {
+            format!("{}/libexec/resources/{name}", FLATPAK_APP_PATH.as_str())
+        } else {
+            format!("{LIBEXECDIR}/{name}")
+        }
+    }
+
+    /// Runs the helper binary `helper` in batch mode and returns the result code for each of the
+    /// `entries`. Entries that failed because of missing permissions are retried in a single
+    /// privileged invocation, either through the persistent privileged helper (if enabled) or
+    /// through pkexec.
+    fn maybe_pkexec_batch_command(helper: &str, entries: &[String]) -> Result<Vec<i32>> {
+        let command = Self::helper_path(helper);
+
+        let mut codes = Self::batch_command(&command, entries, false)?;
+
+        let unauthorized: Vec<usize> = codes
+            .iter()
+            .enumerate()
+            .filter(|(_, code)| **code == libc::EPERM || **code == libc::EACCES)
+            .map(|(i, _)| i)
+            .collect();
+
+        if !unauthorized.is_empty() {
+            let privileged_entries: Vec<String> =
+                unauthorized.iter().map(|i| entries[*i].clone()).collect();
 
-        let status_code = if *IS_FLATPAK {
             debug!(
-                "Executing command: {} --host {} {}",
-                FLATPAK_SPAWN,
-                command.as_ref().to_string_lossy(),
-                args.join(&OsString::from(" ")).to_string_lossy()
+                "Received EPERM or EACCES for {} entries, retrying privileged",
+                privileged_entries.len()
             );
 
-            Command::new(FLATPAK_SPAWN)
-                .arg("--host")
-                .arg(command.as_ref())
-                .args(args.clone())
-                .output()?
-                .status
-                .code()
-                .context("no status code?")?
+            let privileged_codes =
+                Self::privileged_batch_command(helper, &command, &privileged_entries);
+
+            // if this fails (e.g. because authentication has been cancelled), keep the original
+            // result codes
+            if let Ok(privileged_codes) = privileged_codes {
+                for (i, code) in unauthorized.into_iter().zip(privileged_codes) {
+                    codes[i] = code;
+                }
+            }
+        }
+
+        Ok(codes)
+    }
+
+    /// Runs `command` (the path of the helper binary `helper`) in batch mode for all of the
+    /// `entries` at once, either through the persistent privileged helper (if enabled) or through
+    /// pkexec.
+    fn privileged_batch_command(
+        helper: &str,
+        command: &str,
+        entries: &[String],
+    ) -> Result<Vec<i32>> {
+        if SETTINGS.persistent_privileged_helper() {
+            match privileged_helper::run_batch(helper, entries) {
+                Err(err) if !err.is::<NotAuthorizedError>() => {
+                    warn!("Unable to use the privileged helper, falling back to pkexec: {err}");
+                    Self::batch_command(command, entries, true)
+                }
+                result => result,
+            }
         } else {
+            Self::batch_command(command, entries, true)
+        }
+    }
+
+    /// Runs `resources-kill` in batch mode for `entries` in a single pass, so that the entries are
+    /// signaled in the order they were given in (e.g. children before their parents for process
+    /// trees). Since retrying only the entries that failed because of missing permissions would
+    /// break that order, `probe_entries` (the same processes with signal 0) are sent first to find
+    /// out whether any of them needs privileges, in which case all `entries` are signaled in a
+    /// single privileged invocation.
+    fn ordered_kill_batch_command(
+        probe_entries: &[String],
+        entries: &[String],
+    ) -> Result<Vec<i32>> {
+        let helper = "resources-kill";
+        let command = Self::helper_path(helper);
+
+        let needs_privileges = Self::batch_command(&command, probe_entries, false)?
+            .into_iter()
+            .any(|code| code == libc::EPERM || code == libc::EACCES);
+
+        if needs_privileges {
             debug!(
-                "Executing command: {} {}",
-                command.as_ref().to_string_lossy(),
-                args.join(&OsString::from(" ")).to_string_lossy()
+                "Missing permissions to signal some of {} processes, signaling all of them privileged",
+                entries.len()
             );
 
-            Command::new(command.as_ref())
-                .args(args.clone())
-                .output()?
-                .status
-                .code()
-                .context("no status code?")?
-        };
+            match Self::privileged_batch_command(helper, &command, entries) {
+                Ok(codes) => return Ok(codes),
+                // e.g. because authentication has been cancelled, still signal the processes we
+                // are permitted to
+                Err(err) => warn!("Unable to signal processes privileged: {err}"),
+            }
+        }
 
-        if status_code == libc::EPERM || status_code == libc::EACCES {
-            let pkexec_status_code = if *IS_FLATPAK {
-                debug!(
-                    "Received EPERM, executing command: {} --host pkexec --disable-internal-agent {} {}", 
-                    FLATPAK_SPAWN,
-                    command.as_ref().to_string_lossy(),
-                    args.join(&OsString::from(" ")).to_string_lossy()
-                );
-                Command::new(FLATPAK_SPAWN)
-                    .args(["--host", "pkexec", "--disable-internal-agent"])
-                    .arg(command)
-                    .args(args)
-                    .output()?
-                    .status
-                    .code()
-                    .context("no status code?")?
-            } else {
-                debug!(
-                    "Received EPERM or EACCES, executing command: pkexec --disable-internal-agent {} {}", 
-                    command.as_ref().to_string_lossy(),
-                    args.join(&OsString::from(" ")).to_string_lossy()
-                );
-                Command::new("pkexec")
-                    .arg("--disable-internal-agent")
-                    .arg(command)
-                    .args(args)
-                    .output()?
-                    .status
-                    .code()
-                    .context("no status code?")?
-            };
-
-            Ok(pkexec_status_code)
+        Self::batch_command(&command, entries, false)
+    }
+
+    fn batch_command(command: &str, entries: &[String], privileged: bool) -> Result<Vec<i32>> {
+        let output = Self::batch_output(command, entries, privileged)?;
+
+        let codes: Vec<i32> = parse_batch_output(String::from_utf8_lossy(&output.stdout))
+            .into_iter()
+            .map(|(_, code)| code)
+            .collect();
+
+        if codes.len() == entries.len() {
+            Ok(codes)
         } else {
-            Ok(status_code)
+            bail!(
+                "expected {} results but got {}, exit code: {:?}",
+                entries.len(),
+                codes.len(),
+                output.status.code()
+            )
         }
     }
 
-    pub fn adjust<I: IntoIterator<Item = bool>>(
-        &self,
-        niceness: Niceness,
-        affinity: I,
-    ) -> Result<()> {
-        let adjust_path = if *IS_FLATPAK {
-            format!(
-                "{}/libexec/resources/resources-adjust",
-                FLATPAK_APP_PATH.as_str()
-            )
+    fn batch_output(command: &str, entries: &[String], privileged: bool) -> Result<Output> {
+        let mut args = Vec::with_capacity(entries.len() + 4);
+        if privileged {
+            args.extend(["pkexec", "--disable-internal-agent"]);
+        }
+        args.extend([command, "--batch"]);
+        args.extend(entries.iter().map(String::as_str));
+
+        if *IS_FLATPAK {
+            debug!(
+                "Executing command: {} --host {}",
+                FLATPAK_SPAWN,
+                args.join(" ")
+            );
+            Ok(Command::new(FLATPAK_SPAWN)
+                .arg("--host")
+                .args(&args)
+                .output()?)
         } else {
-            format!("{LIBEXECDIR}/resources-adjust")
+            debug!("Executing command: {}", args.join(" "));
+            Ok(Command::new(args[0]).args(&args[1..]).output()?)
+        }
+    }
+
+    pub fn adjust(&self, adjustment: &ProcessAdjustment) -> Result<()> {
+        let entry = adjustment.entry(&pid_arg(&self.data));
+
+        Self::run_single_entry("resources-adjust", entry)?;
+
+        info!("Successfully adjusted {}", self.data.pid);
+        Ok(())
+    }
+
+    /// Sets a resource limit of this process using prlimit(2).
+    pub fn set_limit(&self, limit: ResourceLimit) -> Result<()> {
+        let ResourceLimit {
+            resource,
+            soft,
+            hard,
+        } = limit;
+
+        let format_limit = |limit: Option<u64>| {
+            limit.map_or_else(|| "unlimited".to_string(), |limit| limit.to_string())
         };
 
-        let adjust_string = affinity
-            .into_iter()
-            .map(|b| if b { '1' } else { '0' })
-            .collect::<String>();
+        let entry = format!(
+            "{}={resource}:{}:{}",
+            pid_arg(&self.data),
+            format_limit(soft),
+            format_limit(hard)
+        );
 
-        let result = Self::maybe_pkexec_command(
-            adjust_path,
-            [
-                self.data.pid.to_string(),
-                niceness.to_string(),
-                adjust_string,
-            ],
+        Self::run_single_entry("resources-limit", entry)?;
+
+        info!(
+            "Successfully set limit {resource} of {} to {soft:?}/{hard:?}",
+            self.data.pid
         );
+        Ok(())
+    }
 
-        if let Ok(return_code) = result {
-            if return_code == 0 {
-                info!("Successfully adjusted {}", self.data.pid);
-                Ok(())
-            } else {
-                bail!("non-zero return code: {return_code}")
-            }
-        } else if let Err(err) = result {
-            Err(err)
+    fn run_single_entry(helper: &str, entry: String) -> Result<()> {
+        let return_code = Self::maybe_pkexec_batch_command(helper, &[entry])?
+            .first()
+            .copied()
+            .context("no result code")?;
+
+        if return_code == 0 {
+            Ok(())
+        } else if return_code == EXIT_STARTTIME_MISMATCH {
+            Err(ProcessReplacedError.into())
         } else {
-            bail!("unknown error")
+            bail!("non-zero return code: {return_code}")
         }
     }
 
     pub fn execute_process_action(&self, action: ProcessAction) -> Result<()> {
-        let action_string = action.to_string();
+        Self::execute_process_action_on(&[self], action)
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| bail!("no result"))
+    }
 
-        let kill_path = if *IS_FLATPAK {
-            format!(
-                "{}/libexec/resources/resources-kill",
-                FLATPAK_APP_PATH.as_str()
-            )
-        } else {
-            format!("{LIBEXECDIR}/resources-kill")
+    /// Sends the signal corresponding to `action` to all `processes` in a single (possibly
+    /// privileged) invocation of `resources-kill`. The processes are signaled in the order of
+    /// `processes`, even if only some of them need privileges, and a result is returned for each
+    /// of them.
+    pub fn execute_process_action_on(
+        processes: &[&Process],
+        action: ProcessAction,
+    ) -> Vec<Result<()>> {
+        let processes: Vec<&ProcessData> = processes.iter().map(|process| &process.data).collect();
+        Self::kill(&processes, &action.to_string())
+    }
+
+    /// Executes `action` on the processes with the data `processes`. Halting and continuing the processes in the cgroups
+    /// at `cgroup_paths` (relative to the cgroup root) is done by freezing and thawing these cgroups as a whole since
+    /// signals race with processes that are being spawned in the meantime, see `systemd::freeze_or_thaw_cgroup()`.
+    /// Processes outside of them or in cgroups that couldn't be frozen or thawed get signals instead. A result is
+    /// returned for each of `processes`.
+    ///
+    /// Freezing might wait for the user to authenticate, so this has to be run off the main thread. That's why it takes
+    /// the processes' data instead of the processes.
+    pub fn execute_process_action_freezing(
+        processes: &[ProcessData],
+        cgroup_paths: &[String],
+        action: ProcessAction,
+    ) -> Vec<Result<()>> {
+        let frozen = match action {
+            ProcessAction::STOP => true,
+            ProcessAction::CONT => false,
+            _ => {
+                let processes: Vec<&ProcessData> = processes.iter().collect();
+                return Self::kill(&processes, &action.to_string());
+            }
         };
 
-        let result = Self::maybe_pkexec_command(
-            kill_path,
-            [self.data.pid.to_string(), action_string.clone()],
-        );
+        let frozen_cgroups: Vec<&String> = cgroup_paths
+            .iter()
+            .filter(|cgroup_path| {
+                systemd::freeze_or_thaw_cgroup(cgroup_path, frozen)
+                    .inspect_err(|err| debug!("{err}, falling back to signals"))
+                    .is_ok()
+            })
+            .collect();
 
-        if let Ok(return_code) = result {
-            if return_code == 0 || return_code == 3 {
-                info!("Successfully {action_string}ed {}", self.data.pid);
-                Ok(())
-            } else {
+        // processes in thawed cgroups might still be halted by a signal (e.g. from a time freezing them failed), so
+        // they're continued with one as well
+        let needs_signal = |process: &ProcessData| {
+            let in_frozen_cgroup = process.cgroup_path.as_ref().is_some_and(|cgroup_path| {
+                frozen_cgroups
+                    .iter()
+                    .any(|frozen_cgroup| systemd::cgroup_contains(frozen_cgroup, cgroup_path))
+            });
+
+            !in_frozen_cgroup || (!frozen && process.state == ProcessState::Stopped)
+        };
+
+        let signaled_processes: Vec<&ProcessData> = processes
+            .iter()
+            .filter(|process| needs_signal(process))
+            .collect();
+
+        let mut signal_results = Self::kill(&signaled_processes, &action.to_string()).into_iter();
+
+        processes
+            .iter()
+            .map(|process| {
+                if needs_signal(process) {
+                    signal_results
+                        .next()
+                        .unwrap_or_else(|| Err(anyhow!("no result")))
+                } else {
+                    Ok(())
+                }
+            })
+            .collect()
+    }
+
+    /// Sends the signal with the number `signal` to all `processes` in a single (possibly
+    /// privileged) invocation of `resources-kill`. The processes are signaled in the order of
+    /// `processes` and a result is returned for each of them.
+    pub fn send_signal_to(processes: &[&Process], signal: i32) -> Vec<Result<()>> {
+        let processes: Vec<&ProcessData> = processes.iter().map(|process| &process.data).collect();
+        Self::kill(&processes, &signal.to_string())
+    }
+
+    fn kill(processes: &[&ProcessData], signal_string: &str) -> Vec<Result<()>> {
+        if processes.is_empty() {
+            return Vec::new();
+        }
+
+        let entries = processes
+            .iter()
+            .map(|process| format!("{}={signal_string}", pid_arg(process)))
+            .collect::<Vec<_>>();
+
+        let codes = if processes.len() > 1 {
+            let probe_entries = processes
+                .iter()
+                .map(|process| format!("{}=0", pid_arg(process)))
+                .collect::<Vec<_>>();
+
+            Self::ordered_kill_batch_command(&probe_entries, &entries)
+        } else {
+            Self::maybe_pkexec_batch_command("resources-kill", &entries)
+        };
+
+        let codes = match codes {
+            Ok(codes) => codes,
+            Err(err) => {
                 error!(
-                    "Couldn't {action_string} {}, return code: {return_code}",
-                    self.data.pid
+                    "Unknown error while trying to send {signal_string} to {} processes\n{err}\n{}",
+                    processes.len(),
+                    err.backtrace()
                 );
-                bail!("non-zero return code: {return_code}")
+                return processes
+                    .iter()
+                    .map(|_| Err(anyhow!("unable to run resources-kill: {err}")))
+                    .collect();
             }
-        } else if let Err(err) = result {
-            error!(
-                "Unknown error while trying to {action_string} {}\n{err}\n{}",
-                self.data.pid,
-                err.backtrace()
-            );
-            Err(err)
-        } else {
-            bail!("unknown error")
-        }
+        };
+
+        processes
+            .iter()
+            .zip(codes)
+            .map(|(process, return_code)| {
+                let pid = process.pid;
+                if return_code == 0 || return_code == libc::ESRCH {
+                    info!("Successfully sent {signal_string} to {pid}");
+                    Ok(())
+                } else if return_code == EXIT_STARTTIME_MISMATCH {
+                    error!("Couldn't send {signal_string} to {pid}, its PID has been reused");
+                    Err(ProcessReplacedError.into())
+                } else {
+                    error!("Couldn't send {signal_string} to {pid}, return code: {return_code}");
+                    bail!("non-zero return code: {return_code}")
+                }
+            })
+            .collect()
     }
 
     #[must_use]
@@ -338,34 +730,123 @@ impl Process {
         }
     }
 
+    /// Calculates how much of the time since the last refresh this process' threads have spent runnable but waiting for
+    /// a CPU, relative to the number of CPUs like `cpu_time_ratio()`
     #[must_use]
-    pub fn read_speed(&self) -> Option<f64> {
-        if let (Some(read_bytes), Some(read_bytes_last)) =
-            (self.data.read_bytes, self.read_bytes_last)
+    pub fn cpu_wait_ratio(&self) -> Option<f32> {
+        let delta_wait = self.thread_delays_delta(|delays| delays.run_queue_wait)?;
+
+        if self.timestamp_last == 0 {
+            return Some(0.0);
+        }
+
+        // run_queue_wait is in nanoseconds and the timestamps are in milliseconds
+        let delta_wait = delta_wait as f32;
+        let delta_time = self.data.timestamp.saturating_sub(self.timestamp_last);
+
+        Some(
+            (delta_wait
+                / (delta_time
+                    .saturating_mul(1_000_000)
+                    .saturating_mul(*NUM_CPUS as u64)) as f32)
+                .finite_or_default(),
+        )
+    }
+
+    /// Calculates how much of the time since the last refresh this process' threads have spent waiting for block I/O,
+    /// relative to the number of CPUs like `cpu_time_ratio()`
+    #[must_use]
+    pub fn io_wait_ratio(&self) -> Option<f32> {
+        let delta_delay = self.thread_delays_delta(|delays| delays.io_delay)?;
+
+        if self.timestamp_last == 0 {
+            return Some(0.0);
+        }
+
+        let delta_delay = delta_delay as f32 * 1000.0;
+        let delta_time = self.data.timestamp.saturating_sub(self.timestamp_last);
+
+        Some(
+            (delta_delay
+                / (delta_time
+                    .saturating_mul(*TICK_RATE as u64)
+                    .saturating_mul(*NUM_CPUS as u64)) as f32)
+                .finite_or_default(),
+        )
+    }
+
+    /// Sums up how much the `counter` of each thread has increased since the last refresh. Threads that didn't exist
+    /// during the last refresh or whose counter couldn't be read are skipped, `None` is only returned if the counter
+    /// isn't available for any thread.
+    fn thread_delays_delta<F>(&self, counter: F) -> Option<u64>
+    where
+        F: Fn(&ThreadDelays) -> Option<u64>,
+    {
+        if !self
+            .data
+            .thread_delays
+            .iter()
+            .any(|delays| counter(delays).is_some())
         {
-            if self.timestamp_last == 0 {
-                Some(0.0)
-            } else {
-                let bytes_delta = read_bytes.saturating_sub(read_bytes_last) as f64;
-                let time_delta = self.data.timestamp.saturating_sub(self.timestamp_last) as f64;
-                Some((bytes_delta / time_delta) * 1000.0)
-            }
-        } else {
-            None
+            return None;
         }
+
+        Some(
+            self.data
+                .thread_delays
+                .iter()
+                .filter_map(|delays| {
+                    let current = counter(delays)?;
+                    let last = counter(self.thread_delays_last.get(&delays.tid)?)?;
+                    Some(current.saturating_sub(last))
+                })
+                .sum(),
+        )
+    }
+
+    #[must_use]
+    pub fn read_speed(&self) -> Option<f64> {
+        self.counter_rate(self.data.read_bytes, self.read_bytes_last)
     }
 
     #[must_use]
     pub fn write_speed(&self) -> Option<f64> {
-        if let (Some(write_bytes), Some(write_bytes_last)) =
-            (self.data.write_bytes, self.write_bytes_last)
-        {
+        self.counter_rate(self.data.write_bytes, self.write_bytes_last)
+    }
+
+    /// Bytes per second read through system calls, including reads served from the page cache
+    #[must_use]
+    pub fn logical_read_speed(&self) -> Option<f64> {
+        self.counter_rate(self.data.read_chars, self.read_chars_last)
+    }
+
+    /// Bytes per second written through system calls, including writes that haven't reached the drive yet
+    #[must_use]
+    pub fn logical_write_speed(&self) -> Option<f64> {
+        self.counter_rate(self.data.write_chars, self.write_chars_last)
+    }
+
+    /// Read system calls per second
+    #[must_use]
+    pub fn read_syscall_rate(&self) -> Option<f64> {
+        self.counter_rate(self.data.read_syscalls, self.read_syscalls_last)
+    }
+
+    /// Write system calls per second
+    #[must_use]
+    pub fn write_syscall_rate(&self) -> Option<f64> {
+        self.counter_rate(self.data.write_syscalls, self.write_syscalls_last)
+    }
+
+    /// Calculates by how much `counter` has increased per second since the last refresh
+    fn counter_rate(&self, counter: Option<u64>, counter_last: Option<u64>) -> Option<f64> {
+        if let (Some(counter), Some(counter_last)) = (counter, counter_last) {
             if self.timestamp_last == 0 {
                 Some(0.0)
             } else {
-                let bytes_delta = write_bytes.saturating_sub(write_bytes_last) as f64;
+                let delta = counter.saturating_sub(counter_last) as f64;
                 let time_delta = self.data.timestamp.saturating_sub(self.timestamp_last) as f64;
-                Some((bytes_delta / time_delta) * 1000.0)
+                Some((delta / time_delta) * 1000.0)
             }
         } else {
             None
@@ -456,6 +937,19 @@ impl Process {
             .sum()
     }
 
+    /// Returns the memory usage of this process according to `metric` or `None` if the metric is
+    /// not available for this process (e.g. due to missing permissions)
+    #[must_use]
+    pub fn memory_usage(&self, metric: MemoryMetric) -> Option<usize> {
+        match metric {
+            MemoryMetric::Resident => Some(self.data.memory_usage),
+            MemoryMetric::Proportional => self.data.proportional_memory_usage,
+            MemoryMetric::Unique => self.data.unique_memory_usage,
+            MemoryMetric::Shared => self.data.shared_memory_usage,
+            MemoryMetric::Anonymous => self.data.anonymous_memory_usage,
+        }
+    }
+
     #[must_use]
     pub fn starttime(&self) -> f64 {
         self.data.starttime as f64 / *TICK_RATE as f64
@@ -480,3 +974,116 @@ impl Process {
         }
     }
 }
+
+/// Returns the argument identifying the process with the data `data` for the helper binaries. Besides the PID, it
+/// contains the start time of the process so that the helpers can detect if the PID has been reused in the meantime.
+fn pid_arg(data: &ProcessData) -> String {
+    format!("{}:{}", data.pid, data.starttime)
+}
+
+/// Returns the names and numbers of all signals that can be sent to processes, including the
+/// real-time signals.
+pub fn signals() -> Vec<(String, i32)> {
+    let mut signals: Vec<(String, i32)> = Signal::iterator()
+        .map(|signal| (signal.as_str().to_string(), signal as i32))
+        .collect();
+
+    let rt_min = libc::SIGRTMIN();
+    let rt_max = libc::SIGRTMAX();
+    for signal in rt_min..=rt_max {
+        let name = if signal == rt_min {
+            "SIGRTMIN".to_string()
+        } else if signal == rt_max {
+            "SIGRTMAX".to_string()
+        } else {
+            format!("SIGRTMIN+{}", signal - rt_min)
+        };
+        signals.push((name, signal));
+    }
+
+    signals
+}
+
+/// Returns `root` and all of its descendants according to the given `(pid, parent_pid)` pairs.
+///
+/// The returned `Vec` is ordered such that every process comes after all of its children, so
+/// signaling the processes in that order will signal children before their parents.
+pub fn process_tree_pids<I: IntoIterator<Item = (libc::pid_t, libc::pid_t)>>(
+    root: libc::pid_t,
+    pids: I,
+) -> Vec<libc::pid_t> {
+    let mut children: HashMap<libc::pid_t, Vec<libc::pid_t>> = HashMap::new();
+    for (pid, parent_pid) in pids {
+        if pid != parent_pid {
+            children.entry(parent_pid).or_default().push(pid);
+        }
+    }
+
+    for child_pids in children.values_mut() {
+        child_pids.sort_unstable();
+    }
+
+    let mut visited = HashSet::from([root]);
+    let mut stack = vec![(root, false)];
+    let mut tree = Vec::new();
+
+    while let Some((pid, children_pushed)) = stack.pop() {
+        if children_pushed {
+            tree.push(pid);
+            continue;
+        }
+
+        stack.push((pid, true));
+        for child_pid in children.get(&pid).into_iter().flatten().rev() {
+            if visited.insert(*child_pid) {
+                stack.push((*child_pid, false));
+            }
+        }
+    }
+
+    tree
+}
+
+#[cfg(test)]
+mod test {
+    use pretty_assertions::assert_eq;
+
+    use super::process_tree_pids;
+
+    #[test]
+    fn process_tree_children_before_parents() {
+        // 1 ─┬─ 10 ─┬─ 100
+        //    │      └─ 101 ── 1010
+        //    └─ 11
+        let pids = [
+            (1, 0),
+            (11, 1),
+            (10, 1),
+            (101, 10),
+            (100, 10),
+            (1010, 101),
+            (2, 0),
+        ];
+
+        assert_eq!(vec![100, 1010, 101, 10, 11, 1], process_tree_pids(1, pids));
+        assert_eq!(vec![1010, 101], process_tree_pids(101, pids));
+    }
+
+    #[test]
+    fn process_tree_excludes_unrelated_processes() {
+        let pids = [(1, 0), (10, 1), (20, 2), (2, 0)];
+
+        assert_eq!(vec![10, 1], process_tree_pids(1, pids));
+        assert_eq!(vec![30], process_tree_pids(30, pids));
+    }
+
+    #[test]
+    fn process_tree_with_cycle() {
+        // PID reuse can make a stale snapshot contain cycles, which must not loop forever or
+        // return a process twice
+        let pids = [(1, 3), (2, 1), (3, 2), (4, 4)];
+
+        assert_eq!(vec![3, 2, 1], process_tree_pids(1, pids));
+        assert_eq!(vec![4], process_tree_pids(4, pids));
+    }
+}
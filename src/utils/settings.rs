@@ -143,6 +143,17 @@ pub enum SidebarMeterType {
     Graph,
 }
 
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, EnumString, Display, Hash, FromRepr)]
+pub enum MemoryMetric {
+    #[default]
+    Resident,
+    Proportional,
+    Unique,
+    Shared,
+    Anonymous,
+}
+
 #[derive(Clone, Debug, Hash)]
 pub struct Settings(gio::Settings);
 
@@ -244,6 +255,29 @@ impl Settings {
         })
     }
 
+    pub fn processes_memory_metric(&self) -> MemoryMetric {
+        MemoryMetric::from_str(self.string("processes-memory-metric").as_str()).unwrap_or_default()
+    }
+
+    pub fn set_processes_memory_metric(
+        &self,
+        value: MemoryMetric,
+    ) -> Result<(), glib::error::BoolError> {
+        self.set_string("processes-memory-metric", &value.to_string())
+    }
+
+    pub fn connect_processes_memory_metric<F: Fn(MemoryMetric) + 'static>(
+        &self,
+        f: F,
+    ) -> glib::SignalHandlerId {
+        self.connect_changed(Some("processes-memory-metric"), move |settings, _key| {
+            f(
+                MemoryMetric::from_str(settings.string("processes-memory-metric").as_str())
+                    .unwrap_or_default(),
+            );
+        })
+    }
+
     // the following three functions are kept for compatibility reasons and for not having an oddly named function
     // called "set_is_maximized" generated by the macro
     pub fn maximized(&self) -> bool {
@@ -329,9 +363,51 @@ impl Settings {
         })
     }
 
+    pub fn containers_sort_by_ascending(&self) -> SortType {
+        if self.boolean("containers-sort-by-ascending") {
+            SortType::Ascending
+        } else {
+            SortType::Descending
+        }
+    }
+
+    pub fn set_containers_sort_by_ascending(
+        &self,
+        value: SortType,
+    ) -> Result<(), glib::error::BoolError> {
+        self.set_boolean(
+            "containers-sort-by-ascending",
+            matches!(value, SortType::Ascending),
+        )
+    }
+
+    pub fn services_sort_by_ascending(&self) -> SortType {
+        if self.boolean("services-sort-by-ascending") {
+            SortType::Ascending
+        } else {
+            SortType::Descending
+        }
+    }
+
+    pub fn set_services_sort_by_ascending(
+        &self,
+        value: SortType,
+    ) -> Result<(), glib::error::BoolError> {
+        self.set_boolean(
+            "services-sort-by-ascending",
+            matches!(value, SortType::Ascending),
+        )
+    }
+
     int_settings!(window_width, window_height);
 
-    uint_settings!(graph_data_points, apps_sort_by, processes_sort_by);
+    uint_settings!(
+        graph_data_points,
+        apps_sort_by,
+        processes_sort_by,
+        containers_sort_by,
+        services_sort_by
+    );
 
     bool_settings!(
         show_search_on_start,
@@ -368,10 +444,29 @@ impl Settings {
         processes_show_system_cpu_time,
         processes_show_priority,
         processes_show_swap,
+        processes_show_state,
+        processes_show_threads,
+        processes_show_voluntary_context_switches,
+        processes_show_involuntary_context_switches,
+        processes_show_minor_faults,
+        processes_show_major_faults,
+        processes_show_io_priority,
+        processes_show_scheduling_policy,
+        processes_show_oom_score,
+        processes_show_fd_count,
+        processes_show_listening_ports,
+        processes_show_logical_read_speed,
+        processes_show_logical_write_speed,
+        processes_show_read_syscall_rate,
+        processes_show_write_syscall_rate,
+        processes_show_cpu_wait,
+        processes_show_io_wait,
+        processes_tree_view,
         show_logical_cpus,
         show_graph_grids,
         normalize_cpu_usage,
-        detailed_priority
+        detailed_priority,
+        persistent_privileged_helper
     );
 }
 
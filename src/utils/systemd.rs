@@ -0,0 +1,1675 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use gtk::{
+    gio,
+    glib::{self, variant::ToVariant},
+};
+use log::debug;
+use process_data::unix_as_millis;
+
+use super::{
+    drive::{Drive, DriveType},
+    NUM_CPUS,
+};
+
+pub const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+const SYSTEMD_BUS_NAME: &str = "org.freedesktop.systemd1";
+const SYSTEMD_OBJECT_PATH: &str = "/org/freedesktop/systemd1";
+const SYSTEMD_MANAGER_INTERFACE: &str = "org.freedesktop.systemd1.Manager";
+
+/// How long to wait for calls that might make polkit ask the user to authenticate, in milliseconds
+const AUTHORIZATION_TIMEOUT: i32 = 5 * 60 * 1000;
+
+/// The kinds of systemd units that have a cgroup of their own
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnitKind {
+    Service,
+    Slice,
+    Scope,
+}
+
+impl UnitKind {
+    pub fn from_unit_name<S: AsRef<str>>(name: S) -> Option<Self> {
+        let name = name.as_ref();
+        if name.ends_with(".service") {
+            Some(Self::Service)
+        } else if name.ends_with(".slice") {
+            Some(Self::Slice)
+        } else if name.ends_with(".scope") {
+            Some(Self::Scope)
+        } else {
+            None
+        }
+    }
+}
+
+/// The systemd instance that manages a unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SystemdManager {
+    System,
+    /// The user manager running in `user@<UID>.service`
+    User(u32),
+}
+
+impl SystemdManager {
+    /// Whether we can talk to this manager, we only have access to the user manager of our own user
+    pub fn is_reachable(self) -> bool {
+        match self {
+            SystemdManager::System => true,
+            SystemdManager::User(uid) => uid == unsafe { libc::getuid() },
+        }
+    }
+
+    fn bus_type(self) -> gio::BusType {
+        match self {
+            SystemdManager::System => gio::BusType::System,
+            SystemdManager::User(_) => gio::BusType::Session,
+        }
+    }
+}
+
+/// The kernel's accounting of a cgroup, counters whose controller is not enabled for the cgroup are `None`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CgroupCounters {
+    pub memory_current: Option<u64>,
+    pub cpu_usage_usec: Option<u64>,
+    pub io_read_bytes: Option<u64>,
+    pub io_write_bytes: Option<u64>,
+    pub pids_current: Option<u64>,
+}
+
+impl CgroupCounters {
+    pub fn from_cgroup<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+
+        let read = |file: &str| std::fs::read_to_string(path.join(file)).ok();
+
+        let (io_read_bytes, io_write_bytes) = read("io.stat")
+            .map(parse_io_stat)
+            .map_or((None, None), |(read, write)| (Some(read), Some(write)));
+
+        Self {
+            memory_current: read("memory.current").and_then(|s| s.trim().parse().ok()),
+            cpu_usage_usec: read("cpu.stat").and_then(parse_cpu_stat),
+            io_read_bytes,
+            io_write_bytes,
+            pids_current: read("pids.current").and_then(|s| s.trim().parse().ok()),
+        }
+    }
+}
+
+/// The limits the kernel enforces for a cgroup, `None` means that a resource isn't limited (or that its controller
+/// isn't enabled for the cgroup)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CgroupLimits {
+    /// Microseconds of CPU time the cgroup may use per second, 1,000,000 corresponds to one whole CPU
+    pub cpu_quota_usec: Option<u64>,
+    /// Memory usage above which the cgroup is throttled and put under heavy reclaim pressure
+    pub memory_high: Option<u64>,
+    /// Memory usage the cgroup can't exceed, the OOM killer is invoked if reclaiming memory doesn't suffice
+    pub memory_max: Option<u64>,
+    /// Bytes per second the cgroup may read from each drive
+    pub io_read_bandwidth: Option<u64>,
+    /// Bytes per second the cgroup may write to each drive
+    pub io_write_bandwidth: Option<u64>,
+}
+
+impl CgroupLimits {
+    pub fn from_cgroup<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+
+        let read = |file: &str| std::fs::read_to_string(path.join(file)).ok();
+
+        let (io_read_bandwidth, io_write_bandwidth) =
+            read("io.max").map_or((None, None), parse_io_max);
+
+        Self {
+            cpu_quota_usec: read("cpu.max").and_then(parse_cpu_max),
+            memory_high: read("memory.high").and_then(parse_memory_limit),
+            memory_max: read("memory.max").and_then(parse_memory_limit),
+            io_read_bandwidth,
+            io_write_bandwidth,
+        }
+    }
+}
+
+/// A systemd unit as seen through its cgroup or, if it isn't running, through its manager
+#[derive(Debug, Clone)]
+pub struct UnitData {
+    /// Path of the unit's cgroup relative to the cgroup root (like `system.slice/sshd.service`), identifies the unit.
+    /// For units that aren't running, this is the path their cgroup would have in their manager's default slice.
+    pub cgroup_path: String,
+    /// The unit name as systemd knows it, escaping included
+    pub name: String,
+    pub kind: UnitKind,
+    pub manager: SystemdManager,
+    /// The cgroup path of the unit this one is nested in, `None` for units directly below the root
+    pub parent: Option<String>,
+    pub counters: CgroupCounters,
+    /// The processes in the unit's cgroup and in its sub-cgroups that aren't units on their own
+    pub pids: Vec<libc::pid_t>,
+    pub timestamp: u64,
+    /// Whether the unit has a cgroup, i.e. whether it's running
+    pub running: bool,
+}
+
+impl UnitData {
+    /// Returns all units that currently have a cgroup along with the services of the system manager and of our own
+    /// user's manager that aren't running, so that they can be started
+    pub fn all() -> Result<Vec<Self>> {
+        let mut units = Vec::new();
+        Self::collect(
+            Path::new(CGROUP_ROOT),
+            None,
+            SystemdManager::System,
+            &mut units,
+        )
+        .context("unable to read cgroup hierarchy")?;
+
+        for manager in [
+            SystemdManager::System,
+            SystemdManager::User(unsafe { libc::getuid() }),
+        ] {
+            match DBusSystemd::new(manager).list_units(&["*.service"]) {
+                Ok(names) => Self::add_stopped(&mut units, manager, names),
+                Err(err) => debug!("Unable to list the units of {manager:?}: {err}"),
+            }
+        }
+
+        Ok(units)
+    }
+
+    /// Adds the units of `manager` called `names` that don't have a cgroup to `units`. Since they're not running, they
+    /// are placed in the manager's default slice for services.
+    fn add_stopped(units: &mut Vec<Self>, manager: SystemdManager, names: Vec<String>) {
+        let slice = match manager {
+            SystemdManager::System => Some(String::from("system.slice")),
+            SystemdManager::User(uid) => units
+                .iter()
+                .find(|unit| {
+                    unit.manager == SystemdManager::System
+                        && user_manager_uid(&unit.name) == Some(uid)
+                })
+                .map(|unit| format!("{}/app.slice", unit.cgroup_path)),
+        };
+
+        // without a running user manager, there can't be any of its units
+        let Some(slice) = slice else {
+            return;
+        };
+
+        let parent = units
+            .iter()
+            .any(|unit| unit.cgroup_path == slice)
+            .then(|| slice.clone());
+
+        let running: HashSet<String> = units
+            .iter()
+            .filter(|unit| unit.manager == manager)
+            .map(|unit| unit.name.clone())
+            .collect();
+
+        for name in names {
+            let Some(kind) = UnitKind::from_unit_name(&name) else {
+                continue;
+            };
+
+            if running.contains(&name) {
+                continue;
+            }
+
+            units.push(Self {
+                cgroup_path: format!("{slice}/{name}"),
+                name,
+                kind,
+                manager,
+                parent: parent.clone(),
+                counters: CgroupCounters::default(),
+                pids: Vec::new(),
+                timestamp: unix_as_millis(),
+                running: false,
+            });
+        }
+    }
+
+    fn collect(
+        dir: &Path,
+        parent: Option<&str>,
+        manager: SystemdManager,
+        units: &mut Vec<Self>,
+    ) -> Result<()> {
+        for entry in std::fs::read_dir(dir)?.flatten() {
+            if !entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+                continue;
+            }
+
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            let Some(kind) = UnitKind::from_unit_name(&name) else {
+                continue;
+            };
+
+            let path = entry.path();
+
+            let cgroup_path = match parent {
+                Some(parent) => format!("{parent}/{name}"),
+                None => name.clone(),
+            };
+
+            let mut pids = Vec::new();
+            collect_pids(&path, true, &mut pids);
+
+            // everything below user@<UID>.service is managed by that user's systemd instance
+            let children_manager = user_manager_uid(&name).map_or(manager, SystemdManager::User);
+
+            units.push(Self {
+                cgroup_path: cgroup_path.clone(),
+                name,
+                kind,
+                manager,
+                parent: parent.map(str::to_string),
+                counters: CgroupCounters::from_cgroup(&path),
+                pids,
+                timestamp: unix_as_millis(),
+                running: true,
+            });
+
+            // the unit might have gone away in the meantime
+            let _ = Self::collect(&path, Some(&cgroup_path), children_manager, units);
+        }
+
+        Ok(())
+    }
+}
+
+/// A unit along with the counters of the previous refresh, which are needed to calculate its rates
+#[derive(Debug, Clone)]
+pub struct Unit {
+    pub data: UnitData,
+    counters_last: CgroupCounters,
+    timestamp_last: u64,
+}
+
+impl Unit {
+    fn new(data: UnitData) -> Self {
+        Self {
+            counters_last: data.counters,
+            timestamp_last: data.timestamp,
+            data,
+        }
+    }
+
+    /// Returns the bytes (or microseconds) per second between `last` and `current`
+    fn rate(&self, current: Option<u64>, last: Option<u64>) -> Option<f64> {
+        let delta_time = self.data.timestamp.saturating_sub(self.timestamp_last);
+        if delta_time == 0 {
+            return None;
+        }
+
+        let delta = current?.saturating_sub(last?);
+        Some(delta as f64 / (delta_time as f64 / 1000.0))
+    }
+
+    /// Returns the fraction of the time of all CPUs the unit has used since the last refresh
+    #[must_use]
+    pub fn cpu_usage(&self) -> f32 {
+        self.rate(
+            self.data.counters.cpu_usage_usec,
+            self.counters_last.cpu_usage_usec,
+        )
+        .map_or(0.0, |usec_per_sec| {
+            (usec_per_sec / 1_000_000.0 / *NUM_CPUS as f64) as f32
+        })
+    }
+
+    #[must_use]
+    pub fn read_speed(&self) -> Option<f64> {
+        self.rate(
+            self.data.counters.io_read_bytes,
+            self.counters_last.io_read_bytes,
+        )
+    }
+
+    #[must_use]
+    pub fn write_speed(&self) -> Option<f64> {
+        self.rate(
+            self.data.counters.io_write_bytes,
+            self.counters_last.io_write_bytes,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UnitsContext {
+    /// Key: cgroup path of the unit
+    units: HashMap<String, Unit>,
+}
+
+impl UnitsContext {
+    pub fn refresh(&mut self, units_data: Vec<UnitData>) {
+        let mut units = HashMap::with_capacity(units_data.len());
+
+        for data in units_data {
+            let unit = match self.units.remove(&data.cgroup_path) {
+                Some(old) => Unit {
+                    counters_last: old.data.counters,
+                    timestamp_last: old.data.timestamp,
+                    data,
+                },
+                None => Unit::new(data),
+            };
+
+            units.insert(unit.data.cgroup_path.clone(), unit);
+        }
+
+        self.units = units;
+    }
+
+    pub fn get_unit(&self, cgroup_path: &str) -> Option<&Unit> {
+        self.units.get(cgroup_path)
+    }
+
+    pub fn units_iter(&self) -> impl Iterator<Item = &Unit> {
+        self.units.values()
+    }
+}
+
+/// The unit a cgroup belongs to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnitCgroup {
+    /// Path of the unit's cgroup relative to the cgroup root, like in `UnitData`
+    pub cgroup_path: String,
+    pub name: String,
+    pub kind: UnitKind,
+    pub manager: SystemdManager,
+}
+
+impl UnitCgroup {
+    /// Finds the innermost unit containing the cgroup at `cgroup_path`, which is given like in `/proc/<PID>/cgroup`
+    pub fn containing<S: AsRef<str>>(cgroup_path: S) -> Option<Self> {
+        let mut manager = SystemdManager::System;
+        let mut segments = Vec::new();
+        let mut unit = None;
+
+        for segment in cgroup_path.as_ref().split('/').filter(|s| !s.is_empty()) {
+            segments.push(segment);
+
+            if let Some(kind) = UnitKind::from_unit_name(segment) {
+                unit = Some(Self {
+                    cgroup_path: segments.join("/"),
+                    name: segment.to_string(),
+                    kind,
+                    manager,
+                });
+            }
+
+            if let Some(uid) = user_manager_uid(segment) {
+                manager = SystemdManager::User(uid);
+            }
+        }
+
+        unit
+    }
+
+    pub fn limits(&self) -> CgroupLimits {
+        CgroupLimits::from_cgroup(self.path())
+    }
+
+    /// Returns the memory usage of the unit's cgroup, `None` if the memory controller isn't enabled for it
+    pub fn memory_usage(&self) -> Option<u64> {
+        std::fs::read_to_string(self.path().join("memory.current"))
+            .ok()
+            .and_then(|memory_current| memory_current.trim().parse().ok())
+    }
+
+    fn path(&self) -> PathBuf {
+        Path::new(CGROUP_ROOT).join(&self.cgroup_path)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UnitAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl UnitAction {
+    fn method_name(self) -> &'static str {
+        match self {
+            UnitAction::Start => "StartUnit",
+            UnitAction::Stop => "StopUnit",
+            UnitAction::Restart => "RestartUnit",
+        }
+    }
+
+    /// Scopes are started by whoever created them and starting a slice happens implicitly when one of its units is
+    /// started, so they can only be stopped
+    pub fn is_supported_by(self, kind: UnitKind) -> bool {
+        kind == UnitKind::Service || self == UnitAction::Stop
+    }
+}
+
+/// A resource control property of a unit that can be changed at runtime, see systemd.resource-control(5)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnitProperty {
+    /// `u64::MAX` removes the limit
+    CpuQuotaPerSecUSec(u64),
+    /// `u64::MAX` removes the limit
+    MemoryHigh(u64),
+    /// `u64::MAX` removes the limit
+    MemoryMax(u64),
+    /// Device nodes and their limit in bytes per second, an empty list removes all limits
+    IoReadBandwidthMax(Vec<(String, u64)>),
+    /// Device nodes and their limit in bytes per second, an empty list removes all limits
+    IoWriteBandwidthMax(Vec<(String, u64)>),
+}
+
+impl UnitProperty {
+    fn name(&self) -> &'static str {
+        match self {
+            UnitProperty::CpuQuotaPerSecUSec(_) => "CPUQuotaPerSecUSec",
+            UnitProperty::MemoryHigh(_) => "MemoryHigh",
+            UnitProperty::MemoryMax(_) => "MemoryMax",
+            UnitProperty::IoReadBandwidthMax(_) => "IOReadBandwidthMax",
+            UnitProperty::IoWriteBandwidthMax(_) => "IOWriteBandwidthMax",
+        }
+    }
+
+    fn value(&self) -> glib::Variant {
+        match self {
+            UnitProperty::CpuQuotaPerSecUSec(value)
+            | UnitProperty::MemoryHigh(value)
+            | UnitProperty::MemoryMax(value) => value.to_variant(),
+            UnitProperty::IoReadBandwidthMax(limits)
+            | UnitProperty::IoWriteBandwidthMax(limits) => limits.to_variant(),
+        }
+    }
+}
+
+/// The part of systemd's D-Bus API that is needed to control units, a trait so that it can be mocked
+pub trait SystemdBus {
+    /// Calls `method` of systemd's manager object with a unit name and a job mode and returns the object path of the
+    /// job systemd has queued
+    fn call_manager_method(&self, method: &str, unit: &str, mode: &str) -> Result<String>;
+
+    /// Changes `properties` of the unit called `unit` until it's stopped
+    fn set_unit_properties(&self, unit: &str, properties: &[UnitProperty]) -> Result<()>;
+
+    /// Returns the names of the units the manager has loaded (running or not) whose names match one of `patterns`
+    fn list_units(&self, patterns: &[&str]) -> Result<Vec<String>>;
+
+    /// Freezes (`FreezeUnit`) or thaws (`ThawUnit`) the unit called `unit`
+    fn set_unit_frozen(&self, unit: &str, frozen: bool) -> Result<()>;
+}
+
+/// The D-Bus connection to a systemd instance
+#[derive(Debug, Clone)]
+pub struct DBusSystemd {
+    manager: SystemdManager,
+    connection: Option<gio::DBusConnection>,
+}
+
+impl DBusSystemd {
+    pub fn new(manager: SystemdManager) -> Self {
+        Self {
+            manager,
+            connection: None,
+        }
+    }
+
+    /// Talks to the manager over `connection` instead of the bus it's usually reached through
+    #[cfg(test)]
+    fn with_connection(manager: SystemdManager, connection: gio::DBusConnection) -> Self {
+        Self {
+            manager,
+            connection: Some(connection),
+        }
+    }
+
+    fn connection(&self) -> Result<gio::DBusConnection> {
+        match &self.connection {
+            Some(connection) => Ok(connection.clone()),
+            None => Ok(gio::bus_get_sync(
+                self.manager.bus_type(),
+                gio::Cancellable::NONE,
+            )?),
+        }
+    }
+}
+
+impl SystemdBus for DBusSystemd {
+    fn call_manager_method(&self, method: &str, unit: &str, mode: &str) -> Result<String> {
+        let connection = self.connection()?;
+
+        // polkit might ask the user to authenticate, which can take a while
+        let reply = connection.call_sync(
+            Some(SYSTEMD_BUS_NAME),
+            SYSTEMD_OBJECT_PATH,
+            SYSTEMD_MANAGER_INTERFACE,
+            method,
+            Some(&(unit, mode).to_variant()),
+            Some(glib::VariantTy::new("(o)")?),
+            gio::DBusCallFlags::ALLOW_INTERACTIVE_AUTHORIZATION,
+            AUTHORIZATION_TIMEOUT,
+            gio::Cancellable::NONE,
+        )?;
+
+        reply
+            .child_value(0)
+            .str()
+            .map(str::to_string)
+            .context("systemd replied without a job")
+    }
+
+    fn set_unit_properties(&self, unit: &str, properties: &[UnitProperty]) -> Result<()> {
+        let connection = self.connection()?;
+
+        let properties: Vec<(&str, glib::Variant)> = properties
+            .iter()
+            .map(|property| (property.name(), property.value()))
+            .collect();
+
+        // `true` makes the changes runtime-only, they're gone once the unit has stopped
+        connection.call_sync(
+            Some(SYSTEMD_BUS_NAME),
+            SYSTEMD_OBJECT_PATH,
+            SYSTEMD_MANAGER_INTERFACE,
+            "SetUnitProperties",
+            Some(&(unit, true, properties).to_variant()),
+            None,
+            gio::DBusCallFlags::ALLOW_INTERACTIVE_AUTHORIZATION,
+            AUTHORIZATION_TIMEOUT,
+            gio::Cancellable::NONE,
+        )?;
+
+        Ok(())
+    }
+
+    fn list_units(&self, patterns: &[&str]) -> Result<Vec<String>> {
+        let connection = self.connection()?;
+
+        // an empty list of states matches units in any state
+        let reply = connection.call_sync(
+            Some(SYSTEMD_BUS_NAME),
+            SYSTEMD_OBJECT_PATH,
+            SYSTEMD_MANAGER_INTERFACE,
+            "ListUnitsByPatterns",
+            Some(&(Vec::<String>::new(), patterns).to_variant()),
+            Some(glib::VariantTy::new("(a(ssssssouso))")?),
+            gio::DBusCallFlags::NONE,
+            -1,
+            gio::Cancellable::NONE,
+        )?;
+
+        Ok(reply
+            .child_value(0)
+            .iter()
+            .filter_map(|unit| unit.child_value(0).str().map(str::to_string))
+            .collect())
+    }
+
+    fn set_unit_frozen(&self, unit: &str, frozen: bool) -> Result<()> {
+        let connection = self.connection()?;
+
+        connection.call_sync(
+            Some(SYSTEMD_BUS_NAME),
+            SYSTEMD_OBJECT_PATH,
+            SYSTEMD_MANAGER_INTERFACE,
+            if frozen { "FreezeUnit" } else { "ThawUnit" },
+            Some(&(unit,).to_variant()),
+            None,
+            gio::DBusCallFlags::ALLOW_INTERACTIVE_AUTHORIZATION,
+            AUTHORIZATION_TIMEOUT,
+            gio::Cancellable::NONE,
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Asks systemd to perform `action` on the unit called `name` and returns the object path of the queued job
+pub fn execute_unit_action<B: SystemdBus>(
+    bus: &B,
+    name: &str,
+    kind: UnitKind,
+    action: UnitAction,
+) -> Result<String> {
+    if !action.is_supported_by(kind) {
+        bail!("{action:?} is not supported for {name}");
+    }
+
+    bus.call_manager_method(action.method_name(), name, "replace")
+        .with_context(|| format!("unable to {action:?} {name}"))
+}
+
+/// Asks systemd to apply those `limits` that differ from `previous` to the unit called `name` until it's stopped,
+/// limits that are `None` are removed. Bandwidth limits are applied to each of the drives in `devices`. Limits that
+/// haven't been changed are left alone, so that e.g. bandwidth limits that differ between drives are kept.
+pub fn apply_limits<B: SystemdBus>(
+    bus: &B,
+    name: &str,
+    previous: &CgroupLimits,
+    limits: &CgroupLimits,
+    devices: &[String],
+) -> Result<()> {
+    let bandwidth = |limit: Option<u64>| -> Vec<(String, u64)> {
+        limit.map_or_else(Vec::new, |bytes_per_sec| {
+            devices
+                .iter()
+                .map(|device| (device.clone(), bytes_per_sec))
+                .collect()
+        })
+    };
+
+    let mut properties = Vec::new();
+
+    if limits.cpu_quota_usec != previous.cpu_quota_usec {
+        properties.push(UnitProperty::CpuQuotaPerSecUSec(
+            limits.cpu_quota_usec.unwrap_or(u64::MAX),
+        ));
+    }
+
+    if limits.memory_high != previous.memory_high {
+        properties.push(UnitProperty::MemoryHigh(
+            limits.memory_high.unwrap_or(u64::MAX),
+        ));
+    }
+
+    if limits.memory_max != previous.memory_max {
+        properties.push(UnitProperty::MemoryMax(
+            limits.memory_max.unwrap_or(u64::MAX),
+        ));
+    }
+
+    if limits.io_read_bandwidth != previous.io_read_bandwidth {
+        properties.push(UnitProperty::IoReadBandwidthMax(bandwidth(
+            limits.io_read_bandwidth,
+        )));
+    }
+
+    if limits.io_write_bandwidth != previous.io_write_bandwidth {
+        properties.push(UnitProperty::IoWriteBandwidthMax(bandwidth(
+            limits.io_write_bandwidth,
+        )));
+    }
+
+    if properties.is_empty() {
+        return Ok(());
+    }
+
+    bus.set_unit_properties(name, &properties)
+        .with_context(|| format!("unable to apply limits to {name}"))
+}
+
+/// Returns the device nodes of the drives that bandwidth limits should be applied to, virtual and removable media
+/// drives are left out
+pub fn io_limit_devices() -> Vec<String> {
+    Drive::get_sysfs_paths()
+        .unwrap_or_default()
+        .into_iter()
+        .map(Drive::from_sysfs)
+        .filter(|drive| {
+            !matches!(
+                drive.drive_type,
+                DriveType::LoopDevice
+                    | DriveType::RamDisk
+                    | DriveType::Zram
+                    | DriveType::CdDvdBluray
+                    | DriveType::Floppy
+            )
+        })
+        .map(|drive| format!("/dev/{}", drive.block_device))
+        .collect()
+}
+
+/// Freezes or thaws all processes of `unit` at once. This is done by its manager so that systemd keeps track of the
+/// unit's state and units of the system manager can be frozen after authenticating. If the manager refuses (e.g. because
+/// it's too old to support freezing), the cgroup freezer is used directly.
+pub fn freeze_or_thaw_unit<B: SystemdBus>(bus: &B, unit: &UnitCgroup, frozen: bool) -> Result<()> {
+    bus.set_unit_frozen(&unit.name, frozen).or_else(|err| {
+        debug!(
+            "Unable to {} {} through systemd, using the cgroup freezer: {err}",
+            if frozen { "freeze" } else { "thaw" },
+            unit.name
+        );
+        set_cgroup_frozen(&unit.cgroup_path, frozen)
+    })
+}
+
+/// Freezes or thaws the cgroup at `cgroup_path` (relative to the cgroup root), through the manager of its unit if it's
+/// the cgroup of a unit we can talk to (see `freeze_or_thaw_unit()`)
+pub fn freeze_or_thaw_cgroup(cgroup_path: &str, frozen: bool) -> Result<()> {
+    match UnitCgroup::containing(cgroup_path)
+        .filter(|unit| unit.cgroup_path == cgroup_path && unit.manager.is_reachable())
+    {
+        Some(unit) => freeze_or_thaw_unit(&DBusSystemd::new(unit.manager), &unit, frozen),
+        None => set_cgroup_frozen(cgroup_path, frozen),
+    }
+}
+
+/// Freezes or thaws all processes of the cgroup at `cgroup_path` (relative to the cgroup root) at once using the cgroup
+/// v2 freezer, unlike signals this doesn't race with processes that are being spawned in the meantime
+pub fn set_cgroup_frozen(cgroup_path: &str, frozen: bool) -> Result<()> {
+    std::fs::write(
+        Path::new(CGROUP_ROOT)
+            .join(cgroup_path)
+            .join("cgroup.freeze"),
+        if frozen { "1" } else { "0" },
+    )
+    .with_context(|| {
+        format!(
+            "unable to {} {cgroup_path}",
+            if frozen { "freeze" } else { "thaw" },
+        )
+    })
+}
+
+/// Whether the cgroup at `cgroup_path` (given like in `/proc/<PID>/cgroup`) is the one at `ancestor` (relative to the
+/// cgroup root) or one below it
+pub fn cgroup_contains<S: AsRef<str>>(ancestor: &str, cgroup_path: S) -> bool {
+    cgroup_path
+        .as_ref()
+        .trim_start_matches('/')
+        .strip_prefix(ancestor)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Collects the processes of the cgroup at `path` and of its sub-cgroups. If `skip_units` is set, sub-cgroups that are
+/// units are skipped because they're listed on their own. Units further down (e.g. those of a container running systemd)
+/// belong to a different systemd instance though and count as part of this unit.
+fn collect_pids(path: &Path, skip_units: bool, pids: &mut Vec<libc::pid_t>) {
+    if let Ok(procs) = std::fs::read_to_string(path.join("cgroup.procs")) {
+        pids.extend(
+            procs
+                .lines()
+                .filter_map(|line| line.trim().parse::<i32>().ok()),
+        );
+    }
+
+    let Ok(entries) = std::fs::read_dir(path) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        if !entry.file_type().is_ok_and(|file_type| file_type.is_dir())
+            || (skip_units
+                && UnitKind::from_unit_name(entry.file_name().to_string_lossy()).is_some())
+        {
+            continue;
+        }
+
+        collect_pids(&entry.path(), false, pids);
+    }
+}
+
+/// Returns the UID of the user whose manager runs in the unit called `name`
+fn user_manager_uid(name: &str) -> Option<u32> {
+    name.strip_prefix("user@")?
+        .strip_suffix(".service")?
+        .parse()
+        .ok()
+}
+
+/// Parses the total CPU time in microseconds out of a `cpu.stat` file
+fn parse_cpu_stat<S: AsRef<str>>(cpu_stat: S) -> Option<u64> {
+    cpu_stat.as_ref().lines().find_map(|line| {
+        line.strip_prefix("usage_usec ")
+            .and_then(|usec| usec.trim().parse().ok())
+    })
+}
+
+/// Parses the total read and written bytes of all devices out of an `io.stat` file, which consists of lines like
+/// `8:0 rbytes=1459200 wbytes=314773504 rios=192 wios=353 dbytes=0 dios=0`
+fn parse_io_stat<S: AsRef<str>>(io_stat: S) -> (u64, u64) {
+    let mut read_bytes = 0_u64;
+    let mut write_bytes = 0_u64;
+
+    for key_value in io_stat.as_ref().split_whitespace() {
+        if let Some(bytes) = key_value
+            .strip_prefix("rbytes=")
+            .and_then(|bytes| bytes.parse::<u64>().ok())
+        {
+            read_bytes = read_bytes.saturating_add(bytes);
+        } else if let Some(bytes) = key_value
+            .strip_prefix("wbytes=")
+            .and_then(|bytes| bytes.parse::<u64>().ok())
+        {
+            write_bytes = write_bytes.saturating_add(bytes);
+        }
+    }
+
+    (read_bytes, write_bytes)
+}
+
+/// Parses the CPU time per second in microseconds out of a `cpu.max` file, which contains the quota and the period
+/// like `50000 100000`, or `max 100000` if there is no quota
+fn parse_cpu_max<S: AsRef<str>>(cpu_max: S) -> Option<u64> {
+    let mut values = cpu_max.as_ref().split_whitespace();
+
+    let quota: u64 = values.next()?.parse().ok()?;
+    let period: u64 = values.next()?.parse().ok()?;
+
+    if period == 0 {
+        return None;
+    }
+
+    Some(quota.saturating_mul(1_000_000) / period)
+}
+
+/// Parses a `memory.high` or `memory.max` file, which contain either a number of bytes or `max`
+fn parse_memory_limit<S: AsRef<str>>(limit: S) -> Option<u64> {
+    limit.as_ref().trim().parse().ok()
+}
+
+/// Parses the read and write bandwidth limits out of an `io.max` file, which consists of lines like
+/// `8:0 rbps=2097152 wbps=max riops=max wiops=max`. Drives can have differing limits, the lowest ones are returned,
+/// they're only shown and not applied again as long as they haven't been changed (see `apply_limits()`).
+fn parse_io_max<S: AsRef<str>>(io_max: S) -> (Option<u64>, Option<u64>) {
+    let mut read_bandwidth: Option<u64> = None;
+    let mut write_bandwidth: Option<u64> = None;
+
+    for key_value in io_max.as_ref().split_whitespace() {
+        if let Some(bps) = key_value
+            .strip_prefix("rbps=")
+            .and_then(|bps| bps.parse::<u64>().ok())
+        {
+            read_bandwidth = Some(read_bandwidth.map_or(bps, |lowest| lowest.min(bps)));
+        } else if let Some(bps) = key_value
+            .strip_prefix("wbps=")
+            .and_then(|bps| bps.parse::<u64>().ok())
+        {
+            write_bandwidth = Some(write_bandwidth.map_or(bps, |lowest| lowest.min(bps)));
+        }
+    }
+
+    (read_bandwidth, write_bandwidth)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::RefCell,
+        io::{BufRead, BufReader},
+        process::{Command, Stdio},
+        sync::{mpsc, Arc, Mutex},
+    };
+
+    use anyhow::{bail, Result};
+    use gtk::{
+        gio,
+        glib::{
+            self,
+            variant::{ObjectPath, ToVariant},
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        apply_limits, cgroup_contains, execute_unit_action, freeze_or_thaw_unit, parse_cpu_max,
+        parse_cpu_stat, parse_io_max, parse_io_stat, parse_memory_limit, user_manager_uid,
+        CgroupCounters, CgroupLimits, DBusSystemd, SystemdBus, SystemdManager, UnitAction,
+        UnitCgroup, UnitData, UnitKind, UnitProperty, SYSTEMD_BUS_NAME, SYSTEMD_MANAGER_INTERFACE,
+        SYSTEMD_OBJECT_PATH,
+    };
+
+    /// The parts of systemd's manager interface that `DBusSystemd` uses
+    const MANAGER_XML: &str = r#"
+        <node>
+          <interface name="org.freedesktop.systemd1.Manager">
+            <method name="StartUnit">
+              <arg type="s" direction="in"/>
+              <arg type="s" direction="in"/>
+              <arg type="o" direction="out"/>
+            </method>
+            <method name="SetUnitProperties">
+              <arg type="s" direction="in"/>
+              <arg type="b" direction="in"/>
+              <arg type="a(sv)" direction="in"/>
+            </method>
+            <method name="ListUnitsByPatterns">
+              <arg type="as" direction="in"/>
+              <arg type="as" direction="in"/>
+              <arg type="a(ssssssouso)" direction="out"/>
+            </method>
+            <method name="FreezeUnit">
+              <arg type="s" direction="in"/>
+            </method>
+            <method name="ThawUnit">
+              <arg type="s" direction="in"/>
+            </method>
+          </interface>
+        </node>
+    "#;
+
+    /// A call received by `fake_systemd()`: the method name, its arguments and whether interactive authorization was
+    /// allowed
+    type ReceivedCall = (String, glib::Variant, bool);
+
+    /// Starts a message bus of our own so that the tests neither need nor disturb the session or system bus, returns
+    /// the daemon (to be killed once done) and the address of the bus
+    fn private_bus() -> (std::process::Child, String) {
+        let mut daemon = Command::new("dbus-daemon")
+            .args(["--session", "--nofork", "--print-address"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let mut address = String::new();
+        BufReader::new(daemon.stdout.take().unwrap())
+            .read_line(&mut address)
+            .unwrap();
+
+        (daemon, address.trim().to_string())
+    }
+
+    fn connect(address: &str) -> gio::DBusConnection {
+        gio::DBusConnection::for_address_sync(
+            address,
+            gio::DBusConnectionFlags::AUTHENTICATION_CLIENT
+                | gio::DBusConnectionFlags::MESSAGE_BUS_CONNECTION,
+            None,
+            gio::Cancellable::NONE,
+        )
+        .unwrap()
+    }
+
+    /// Serves systemd's manager object on the bus at `address` from a thread of its own, every call is recorded in
+    /// `calls` and answered like systemd would. Returns the main loop of the thread, which has to be quit once done.
+    fn fake_systemd(address: String, calls: Arc<Mutex<Vec<ReceivedCall>>>) -> glib::MainLoop {
+        let (sender, receiver) = mpsc::channel();
+
+        std::thread::spawn(move || {
+            let context = glib::MainContext::new();
+
+            context
+                .with_thread_default(|| {
+                    let connection = connect(&address);
+
+                    let node = gio::DBusNodeInfo::for_xml(MANAGER_XML).unwrap();
+                    let interface = node.lookup_interface(SYSTEMD_MANAGER_INTERFACE).unwrap();
+
+                    let _registration = connection
+                        .register_object(SYSTEMD_OBJECT_PATH, &interface)
+                        .method_call(move |_, _, _, _, method, parameters, invocation| {
+                            let interactive = invocation
+                                .message()
+                                .flags()
+                                .contains(gio::DBusMessageFlags::ALLOW_INTERACTIVE_AUTHORIZATION);
+
+                            calls.lock().unwrap().push((
+                                method.to_string(),
+                                parameters,
+                                interactive,
+                            ));
+
+                            let reply = match method {
+                                "SetUnitProperties" | "FreezeUnit" | "ThawUnit" => None,
+                                "ListUnitsByPatterns" => Some(
+                                    (vec![
+                                        listed_unit("sshd.service", "active"),
+                                        listed_unit("cups.service", "inactive"),
+                                    ],)
+                                        .to_variant(),
+                                ),
+                                _ => Some(
+                                    (object_path("/org/freedesktop/systemd1/job/42"),).to_variant(),
+                                ),
+                            };
+
+                            invocation.return_value(reply.as_ref());
+                        })
+                        .build()
+                        .unwrap();
+
+                    connection
+                        .call_sync(
+                            Some("org.freedesktop.DBus"),
+                            "/org/freedesktop/DBus",
+                            "org.freedesktop.DBus",
+                            "RequestName",
+                            Some(&(SYSTEMD_BUS_NAME, 0_u32).to_variant()),
+                            None,
+                            gio::DBusCallFlags::NONE,
+                            -1,
+                            gio::Cancellable::NONE,
+                        )
+                        .unwrap();
+
+                    let main_loop = glib::MainLoop::new(Some(&context), false);
+                    sender.send(main_loop.clone()).unwrap();
+                    main_loop.run();
+                })
+                .unwrap();
+        });
+
+        receiver.recv().unwrap()
+    }
+
+    fn object_path(path: &str) -> ObjectPath {
+        ObjectPath::try_from(path.to_string()).unwrap()
+    }
+
+    /// A unit as it's listed by `ListUnitsByPatterns`: its name, description, load state, active state, sub state, the
+    /// unit it follows, its object path and the ID, type and object path of its job
+    type ListedUnit = (
+        String,
+        String,
+        String,
+        String,
+        String,
+        String,
+        ObjectPath,
+        u32,
+        String,
+        ObjectPath,
+    );
+
+    fn listed_unit(name: &str, active_state: &str) -> ListedUnit {
+        (
+            name.to_string(),
+            String::new(),
+            String::from("loaded"),
+            active_state.to_string(),
+            String::new(),
+            String::new(),
+            object_path("/org/freedesktop/systemd1/unit/placeholder"),
+            0,
+            String::new(),
+            object_path("/"),
+        )
+    }
+
+    fn running_unit(cgroup_path: &str, manager: SystemdManager) -> UnitData {
+        let name = cgroup_path.rsplit('/').next().unwrap().to_string();
+        let parent = cgroup_path
+            .rsplit_once('/')
+            .map(|(parent, _)| parent.to_string());
+
+        UnitData {
+            cgroup_path: cgroup_path.to_string(),
+            kind: UnitKind::from_unit_name(&name).unwrap(),
+            name,
+            manager,
+            parent,
+            counters: CgroupCounters::default(),
+            pids: Vec::new(),
+            timestamp: 0,
+            running: true,
+        }
+    }
+
+    /// Records the calls made to it instead of talking to systemd
+    #[derive(Default)]
+    struct MockBus {
+        calls: RefCell<Vec<(String, String, String)>>,
+        properties: RefCell<Vec<(String, Vec<UnitProperty>)>>,
+        frozen: RefCell<Vec<(String, bool)>>,
+        deny: bool,
+    }
+
+    impl SystemdBus for MockBus {
+        fn call_manager_method(&self, method: &str, unit: &str, mode: &str) -> Result<String> {
+            self.calls
+                .borrow_mut()
+                .push((method.to_string(), unit.to_string(), mode.to_string()));
+
+            if self.deny {
+                bail!("Access denied");
+            }
+
+            Ok(format!(
+                "/org/freedesktop/systemd1/job/{}",
+                self.calls.borrow().len()
+            ))
+        }
+
+        fn set_unit_properties(&self, unit: &str, properties: &[UnitProperty]) -> Result<()> {
+            self.properties
+                .borrow_mut()
+                .push((unit.to_string(), properties.to_vec()));
+
+            if self.deny {
+                bail!("Access denied");
+            }
+
+            Ok(())
+        }
+
+        fn list_units(&self, _patterns: &[&str]) -> Result<Vec<String>> {
+            if self.deny {
+                bail!("Access denied");
+            }
+
+            Ok(Vec::new())
+        }
+
+        fn set_unit_frozen(&self, unit: &str, frozen: bool) -> Result<()> {
+            self.frozen.borrow_mut().push((unit.to_string(), frozen));
+
+            if self.deny {
+                bail!("Access denied");
+            }
+
+            Ok(())
+        }
+    }
+
+    fn call(method: &str, unit: &str) -> (String, String, String) {
+        (method.to_string(), unit.to_string(), "replace".to_string())
+    }
+
+    #[test]
+    fn service_actions() {
+        let bus = MockBus::default();
+
+        for action in [UnitAction::Start, UnitAction::Stop, UnitAction::Restart] {
+            assert!(execute_unit_action(&bus, "sshd.service", UnitKind::Service, action).is_ok());
+        }
+
+        assert_eq!(
+            bus.calls.into_inner(),
+            vec![
+                call("StartUnit", "sshd.service"),
+                call("StopUnit", "sshd.service"),
+                call("RestartUnit", "sshd.service"),
+            ]
+        );
+    }
+
+    #[test]
+    fn job_path() {
+        let bus = MockBus::default();
+
+        assert_eq!(
+            execute_unit_action(
+                &bus,
+                "systemd-journald.service",
+                UnitKind::Service,
+                UnitAction::Restart
+            )
+            .unwrap(),
+            "/org/freedesktop/systemd1/job/1"
+        );
+    }
+
+    #[test]
+    fn slice_and_scope_actions() {
+        let bus = MockBus::default();
+
+        assert!(
+            execute_unit_action(&bus, "machine.slice", UnitKind::Slice, UnitAction::Stop).is_ok()
+        );
+        assert!(
+            execute_unit_action(&bus, "machine.slice", UnitKind::Slice, UnitAction::Start).is_err()
+        );
+        assert!(execute_unit_action(
+            &bus,
+            "session-2.scope",
+            UnitKind::Scope,
+            UnitAction::Restart
+        )
+        .is_err());
+
+        // unsupported actions must not reach systemd
+        assert_eq!(
+            bus.calls.into_inner(),
+            vec![call("StopUnit", "machine.slice")]
+        );
+    }
+
+    #[test]
+    fn denied_action() {
+        let bus = MockBus {
+            deny: true,
+            ..Default::default()
+        };
+
+        assert!(
+            execute_unit_action(&bus, "cups.service", UnitKind::Service, UnitAction::Stop).is_err()
+        );
+    }
+
+    #[test]
+    fn applied_limits() {
+        let bus = MockBus::default();
+        let devices = vec!["/dev/nvme0n1".to_string(), "/dev/sda".to_string()];
+
+        let limits = CgroupLimits {
+            cpu_quota_usec: Some(500_000),
+            memory_high: Some(2 * 1024 * 1024 * 1024),
+            memory_max: None,
+            io_read_bandwidth: None,
+            io_write_bandwidth: Some(10 * 1024 * 1024),
+        };
+
+        assert!(apply_limits(
+            &bus,
+            "app-gnome-code-4242.scope",
+            &CgroupLimits::default(),
+            &limits,
+            &devices
+        )
+        .is_ok());
+
+        // limits that weren't set before and still aren't must not be touched
+        assert_eq!(
+            bus.properties.into_inner(),
+            vec![(
+                "app-gnome-code-4242.scope".to_string(),
+                vec![
+                    UnitProperty::CpuQuotaPerSecUSec(500_000),
+                    UnitProperty::MemoryHigh(2 * 1024 * 1024 * 1024),
+                    UnitProperty::IoWriteBandwidthMax(vec![
+                        ("/dev/nvme0n1".to_string(), 10 * 1024 * 1024),
+                        ("/dev/sda".to_string(), 10 * 1024 * 1024),
+                    ]),
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn removed_limits() {
+        let bus = MockBus::default();
+
+        let previous = CgroupLimits {
+            cpu_quota_usec: Some(500_000),
+            memory_high: Some(1024 * 1024 * 1024),
+            memory_max: Some(2 * 1024 * 1024 * 1024),
+            io_read_bandwidth: Some(1024 * 1024),
+            io_write_bandwidth: Some(1024 * 1024),
+        };
+
+        assert!(apply_limits(
+            &bus,
+            "app-flatpak-org.gnome.Maps-1234.scope",
+            &previous,
+            &CgroupLimits::default(),
+            &["/dev/sda".to_string()]
+        )
+        .is_ok());
+
+        assert_eq!(
+            bus.properties.into_inner()[0].1,
+            vec![
+                UnitProperty::CpuQuotaPerSecUSec(u64::MAX),
+                UnitProperty::MemoryHigh(u64::MAX),
+                UnitProperty::MemoryMax(u64::MAX),
+                UnitProperty::IoReadBandwidthMax(Vec::new()),
+                UnitProperty::IoWriteBandwidthMax(Vec::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn denied_limits() {
+        let bus = MockBus {
+            deny: true,
+            ..Default::default()
+        };
+
+        assert!(apply_limits(
+            &bus,
+            "app-gnome-code-4242.scope",
+            &CgroupLimits::default(),
+            &CgroupLimits {
+                memory_max: Some(1024 * 1024 * 1024),
+                ..Default::default()
+            },
+            &[]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn unchanged_limits() {
+        let bus = MockBus::default();
+
+        let limits = CgroupLimits {
+            io_read_bandwidth: Some(1024 * 1024),
+            ..Default::default()
+        };
+
+        // the drives might have differing limits, which must not be overwritten with the lowest one
+        assert!(apply_limits(
+            &bus,
+            "app-gnome-code-4242.scope",
+            &limits,
+            &limits,
+            &["/dev/sda".to_string(), "/dev/sdb".to_string()]
+        )
+        .is_ok());
+
+        assert!(bus.properties.into_inner().is_empty());
+    }
+
+    #[test]
+    fn unit_cgroups() {
+        assert_eq!(
+            UnitCgroup::containing(
+                "/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-code-4242.scope"
+            ),
+            Some(UnitCgroup {
+                cgroup_path:
+                    "user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-code-4242.scope"
+                        .to_string(),
+                name: "app-gnome-code-4242.scope".to_string(),
+                kind: UnitKind::Scope,
+                manager: SystemdManager::User(1000),
+            })
+        );
+
+        // the user manager itself is managed by the system manager
+        assert_eq!(
+            UnitCgroup::containing("/user.slice/user-1000.slice/user@1000.service")
+                .map(|unit| unit.manager),
+            Some(SystemdManager::System)
+        );
+
+        // cgroups below a unit that aren't units themselves belong to it
+        assert_eq!(
+            UnitCgroup::containing("/system.slice/docker-4f2a.scope/init")
+                .map(|unit| unit.cgroup_path),
+            Some("system.slice/docker-4f2a.scope".to_string())
+        );
+
+        assert_eq!(UnitCgroup::containing("/"), None);
+        assert_eq!(UnitCgroup::containing(""), None);
+    }
+
+    #[test]
+    fn cgroup_contents() {
+        let unit =
+            "user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-code-4242.scope";
+
+        assert!(cgroup_contains(
+            unit,
+            "/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-code-4242.scope"
+        ));
+        assert!(cgroup_contains(
+            unit,
+            "/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-code-4242.scope/sub"
+        ));
+        assert!(!cgroup_contains(
+            unit,
+            "/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-code-4242.scope2"
+        ));
+        assert!(!cgroup_contains(
+            unit,
+            "/user.slice/user-1000.slice/user@1000.service/app.slice"
+        ));
+    }
+
+    #[test]
+    fn unit_kinds() {
+        assert_eq!(
+            UnitKind::from_unit_name("sshd.service"),
+            Some(UnitKind::Service)
+        );
+        assert_eq!(
+            UnitKind::from_unit_name("user-1000.slice"),
+            Some(UnitKind::Slice)
+        );
+        assert_eq!(
+            UnitKind::from_unit_name("session-2.scope"),
+            Some(UnitKind::Scope)
+        );
+        assert_eq!(UnitKind::from_unit_name("payload"), None);
+        assert_eq!(UnitKind::from_unit_name("cgroup.procs"), None);
+    }
+
+    #[test]
+    fn dbus_calls() {
+        let (mut daemon, address) = private_bus();
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let main_loop = fake_systemd(address.clone(), calls.clone());
+
+        let bus = DBusSystemd::with_connection(SystemdManager::System, connect(&address));
+
+        let job = execute_unit_action(&bus, "sshd.service", UnitKind::Service, UnitAction::Start);
+        let properties = bus.set_unit_properties(
+            "app-gnome-code-4242.scope",
+            &[UnitProperty::MemoryMax(4096)],
+        );
+        let units = bus.list_units(&["*.service"]);
+        let frozen = bus.set_unit_frozen("app-gnome-code-4242.scope", true);
+        let thawed = bus.set_unit_frozen("app-gnome-code-4242.scope", false);
+
+        main_loop.quit();
+        daemon.kill().unwrap();
+
+        assert_eq!(job.unwrap(), "/org/freedesktop/systemd1/job/42");
+        assert!(properties.is_ok());
+        assert_eq!(
+            units.unwrap(),
+            vec!["sshd.service".to_string(), "cups.service".to_string()]
+        );
+        assert!(frozen.is_ok());
+        assert!(thawed.is_ok());
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 5);
+
+        let (method, arguments, interactive) = &calls[0];
+        assert_eq!(method, "StartUnit");
+        assert_eq!(arguments.type_().as_str(), "(ss)");
+        assert_eq!(
+            arguments.get::<(String, String)>(),
+            Some(("sshd.service".to_string(), "replace".to_string()))
+        );
+        assert!(*interactive);
+
+        let (method, arguments, interactive) = &calls[1];
+        assert_eq!(method, "SetUnitProperties");
+        assert_eq!(arguments.type_().as_str(), "(sba(sv))");
+        assert_eq!(
+            arguments.child_value(0).str(),
+            Some("app-gnome-code-4242.scope")
+        );
+        assert_eq!(arguments.child_value(1).get::<bool>(), Some(true));
+        let (name, value) = arguments
+            .child_value(2)
+            .child_value(0)
+            .get::<(String, glib::Variant)>()
+            .unwrap();
+        assert_eq!(name, "MemoryMax");
+        assert_eq!(value.get::<u64>(), Some(4096));
+        assert!(*interactive);
+
+        let (method, arguments, _) = &calls[2];
+        assert_eq!(method, "ListUnitsByPatterns");
+        assert_eq!(arguments.type_().as_str(), "(asas)");
+        assert_eq!(
+            arguments.get::<(Vec<String>, Vec<String>)>(),
+            Some((Vec::new(), vec!["*.service".to_string()]))
+        );
+
+        for ((method, arguments, interactive), expected_method) in
+            calls[3..].iter().zip(["FreezeUnit", "ThawUnit"])
+        {
+            assert_eq!(method, expected_method);
+            assert_eq!(arguments.type_().as_str(), "(s)");
+            assert_eq!(
+                arguments.child_value(0).str(),
+                Some("app-gnome-code-4242.scope")
+            );
+            assert!(*interactive);
+        }
+    }
+
+    #[test]
+    fn frozen_units() {
+        let bus = MockBus::default();
+
+        let unit = UnitCgroup::containing(
+            "/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-code-4242.scope",
+        )
+        .unwrap();
+
+        assert!(freeze_or_thaw_unit(&bus, &unit, true).is_ok());
+        assert!(freeze_or_thaw_unit(&bus, &unit, false).is_ok());
+
+        assert_eq!(
+            bus.frozen.into_inner(),
+            vec![
+                ("app-gnome-code-4242.scope".to_string(), true),
+                ("app-gnome-code-4242.scope".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn denied_freezing() {
+        let bus = MockBus {
+            deny: true,
+            ..Default::default()
+        };
+
+        // the fallback to the cgroup freezer fails as well since there's no such cgroup
+        let unit =
+            UnitCgroup::containing("/system.slice/resources-test-nonexistent.scope").unwrap();
+
+        assert!(freeze_or_thaw_unit(&bus, &unit, true).is_err());
+        assert_eq!(
+            bus.frozen.into_inner(),
+            vec![("resources-test-nonexistent.scope".to_string(), true)]
+        );
+    }
+
+    #[test]
+    fn stopped_units() {
+        let mut units = vec![
+            running_unit("system.slice", SystemdManager::System),
+            running_unit("system.slice/sshd.service", SystemdManager::System),
+            running_unit("user.slice", SystemdManager::System),
+            running_unit("user.slice/user-1000.slice", SystemdManager::System),
+            running_unit(
+                "user.slice/user-1000.slice/user@1000.service",
+                SystemdManager::System,
+            ),
+        ];
+
+        UnitData::add_stopped(
+            &mut units,
+            SystemdManager::System,
+            vec![
+                "sshd.service".to_string(),
+                "cups.service".to_string(),
+                "dev-sda.device".to_string(),
+            ],
+        );
+
+        // the user manager's app.slice isn't running, so its services are shown at the top level
+        UnitData::add_stopped(
+            &mut units,
+            SystemdManager::User(1000),
+            vec!["sshd.service".to_string()],
+        );
+
+        // without a user manager, its units can't be placed anywhere
+        UnitData::add_stopped(
+            &mut units,
+            SystemdManager::User(1001),
+            vec!["pipewire.service".to_string()],
+        );
+
+        let stopped: Vec<(&str, Option<&str>, SystemdManager)> = units
+            .iter()
+            .filter(|unit| !unit.running)
+            .map(|unit| {
+                (
+                    unit.cgroup_path.as_str(),
+                    unit.parent.as_deref(),
+                    unit.manager,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            stopped,
+            vec![
+                (
+                    "system.slice/cups.service",
+                    Some("system.slice"),
+                    SystemdManager::System
+                ),
+                (
+                    "user.slice/user-1000.slice/user@1000.service/app.slice/sshd.service",
+                    None,
+                    SystemdManager::User(1000)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn user_managers() {
+        assert_eq!(user_manager_uid("user@1000.service"), Some(1000));
+        assert_eq!(user_manager_uid("user-runtime-dir@1000.service"), None);
+        assert_eq!(user_manager_uid("user@.service"), None);
+        assert_eq!(user_manager_uid("user-1000.slice"), None);
+    }
+
+    #[test]
+    fn cpu_stat_parsing() {
+        let cpu_stat = concat!(
+            "usage_usec 2815104\n",
+            "user_usec 1992712\n",
+            "system_usec 822392\n",
+            "nr_periods 0\n",
+            "nr_throttled 0\n",
+            "throttled_usec 0\n",
+        );
+
+        assert_eq!(parse_cpu_stat(cpu_stat), Some(2_815_104));
+        assert_eq!(parse_cpu_stat(""), None);
+    }
+
+    #[test]
+    fn io_stat_parsing() {
+        let io_stat = concat!(
+            "259:0 rbytes=1459200 wbytes=314773504 rios=192 wios=353 dbytes=0 dios=0\n",
+            "8:0 rbytes=4096 wbytes=0 rios=1 wios=0 dbytes=0 dios=0\n",
+        );
+
+        assert_eq!(parse_io_stat(io_stat), (1_463_296, 314_773_504));
+        assert_eq!(parse_io_stat(""), (0, 0));
+    }
+
+    #[test]
+    fn cpu_max_parsing() {
+        assert_eq!(parse_cpu_max("50000 100000\n"), Some(500_000));
+        assert_eq!(parse_cpu_max("200000 100000\n"), Some(2_000_000));
+        assert_eq!(parse_cpu_max("max 100000\n"), None);
+        assert_eq!(parse_cpu_max("50000 0\n"), None);
+        assert_eq!(parse_cpu_max(""), None);
+    }
+
+    #[test]
+    fn memory_limit_parsing() {
+        assert_eq!(parse_memory_limit("2147483648\n"), Some(2_147_483_648));
+        assert_eq!(parse_memory_limit("max\n"), None);
+    }
+
+    #[test]
+    fn io_max_parsing() {
+        let io_max = concat!(
+            "259:0 rbps=max wbps=10485760 riops=max wiops=max\n",
+            "8:0 rbps=max wbps=5242880 riops=max wiops=max\n",
+        );
+
+        assert_eq!(parse_io_max(io_max), (None, Some(5_242_880)));
+        assert_eq!(
+            parse_io_max("8:0 rbps=1048576 wbps=max riops=max wiops=max\n"),
+            (Some(1_048_576), None)
+        );
+        assert_eq!(parse_io_max(""), (None, None));
+    }
+}
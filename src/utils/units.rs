@@ -245,6 +245,11 @@ fn convert_speed_bits_binary(bits_per_second: f64) -> String {
     }
 }
 
+/// Formats how often something happens per second, e.g. `42/s`
+pub fn convert_rate(per_second: f64) -> String {
+    i18n_f("{}/s", &[&format!("{}", per_second.round())])
+}
+
 pub fn convert_frequency(hertz: f64) -> String {
     let (number, prefix) = to_largest_prefix(hertz, Base::Decimal);
     match prefix {